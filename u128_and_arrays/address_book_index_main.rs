@@ -0,0 +1,500 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::ffi::OsStr;
+use toml::Value;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    IndexError(String),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::IndexError(err) => write!(f, "Index Error: {}", err),
+        }
+    }
+}
+
+/// Toml Deserialization: Reads collaborator setup data from TOML files in a specified directory.
+///
+/// Same loader as in `deserialization_from_toml_file_main.rs`; see that file
+/// for the full description of fields, helpers and error handling.
+///
+/// # Returns
+///
+/// Returns a `Result` containing:
+/// - `Ok`: A tuple with:
+///     - A vector of successfully parsed `CollaboratorTomlData` instances.
+///     - A vector of any `ThisProjectError` encountered during parsing.
+/// - `Err`: A `ThisProjectError` if there was an error reading the directory or any file.
+fn read_a_collaborator_setup_toml() -> Result<(Vec<CollaboratorTomlData>, Vec<ThisProjectError>), ThisProjectError> {
+    let mut collaborators = Vec::new();
+    let mut errors = Vec::new();
+    let dir_path = Path::new("project_graph_data/collaborator_files_address_book");
+
+    for entry in fs::read_dir(dir_path)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
+            let toml_string = fs::read_to_string(&path)?;
+
+            match toml::from_str::<Value>(&toml_string) {
+                Ok(toml_value) => {
+                    if let Value::Table(table) = toml_value {
+                        // Extract user_name
+                        let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+                            s.clone()
+                        } else {
+                            errors.push(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+                            continue;
+                        };
+
+                        // Extract user_salt_list
+                        let user_salt_list = if let Some(Value::Array(arr)) = table.get("user_salt_list") {
+                            arr.iter()
+                                .map(|val| {
+                                    if let Value::String(s) = val {
+                                        u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                                            .map_err(ThisProjectError::ParseIntError)
+                                    } else {
+                                        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                                    }
+                                })
+                                .collect::<Result<Vec<u128>, ThisProjectError>>()?
+                        } else {
+                            errors.push(ThisProjectError::TomlVanillaDeserialStrError("Missing user_salt_list".into()));
+                            continue;
+                        };
+
+                        // Extract ipv4_addresses
+                        let ipv4_addresses = extract_ipv4_addresses(&table, "ipv4_addresses", &mut errors)?;
+
+                        // Extract ipv6_addresses
+                        let ipv6_addresses = extract_ipv6_addresses(&table, "ipv6_addresses", &mut errors)?;
+
+                        // Extract gpg_key_public
+                        let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+                            s.clone()
+                        } else {
+                            errors.push(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+                            continue;
+                        };
+
+                        // Extract sync_interval
+                        let sync_interval = extract_u64(&table, "sync_interval", &mut errors)?;
+
+                        // Extract updated_at_timestamp
+                        let updated_at_timestamp = extract_u64(&table, "updated_at_timestamp", &mut errors)?;
+
+                        // Create CollaboratorTomlData instance
+                        collaborators.push(CollaboratorTomlData {
+                            user_name,
+                            user_salt_list,
+                            ipv4_addresses,
+                            ipv6_addresses,
+                            gpg_key_public,
+                            sync_interval,
+                            updated_at_timestamp,
+                        });
+                    } else {
+                        errors.push(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure".into()));
+                    }
+                }
+                Err(e) => {
+                    errors.push(ThisProjectError::TomlVanillaDeserialStrError(e.to_string()));
+                }
+            }
+        }
+    }
+
+    Ok((collaborators, errors))
+}
+
+// Helper function to extract and parse IPv4 addresses from a toml::Value::Table
+fn extract_ipv4_addresses(
+    table: &toml::map::Map<String, Value>,
+    key: &str,
+    errors: &mut Vec<ThisProjectError>
+) -> Result<Option<Vec<Ipv4Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new(); // Create an empty vector to store addresses
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv4Addr>() {
+                    Ok(ip) => addresses.push(ip), // Push successful IP address
+                    Err(e) => errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() { // If no valid addresses were found
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None) // Return None if the key is not present
+    }
+}
+
+// Helper function to extract and parse IPv6 addresses from a toml::Value::Table
+fn extract_ipv6_addresses(table: &toml::map::Map<String, Value>, key: &str, errors: &mut Vec<ThisProjectError>) -> Result<Option<Vec<Ipv6Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new(); // Create an empty vector to store addresses
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv6Addr>() {
+                    Ok(ip) => addresses.push(ip), // Push successful IP address
+                    Err(e) => errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() { // If no valid addresses were found
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None) // Return None if the key is not present
+    }
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str, errors: &mut Vec<ThisProjectError>) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)));
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)));
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+/// One value (an IP address or a salt) that is listed by more than one collaborator.
+///
+/// Each collaborator should own its addresses and salts; a value claimed by
+/// two or more collaborators usually means a copy-paste mistake in the
+/// address book (or, for salts, a badly generated salt).
+#[derive(Debug)]
+enum AddressBookConflict {
+    SharedIpv4 { address: Ipv4Addr, user_names: Vec<String> },
+    SharedIpv6 { address: Ipv6Addr, user_names: Vec<String> },
+    SharedSalt { salt: u128, user_names: Vec<String> },
+}
+
+impl fmt::Display for AddressBookConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AddressBookConflict::SharedIpv4 { address, user_names } => {
+                write!(f, "IPv4 address {} is claimed by: {}", address, user_names.join(", "))
+            }
+            AddressBookConflict::SharedIpv6 { address, user_names } => {
+                write!(f, "IPv6 address {} is claimed by: {}", address, user_names.join(", "))
+            }
+            // Salts are printed the same way serialize_collaborator_to_toml writes them
+            AddressBookConflict::SharedSalt { salt, user_names } => {
+                write!(f, "Salt 0x{:x} is claimed by: {}", salt, user_names.join(", "))
+            }
+        }
+    }
+}
+
+/// Secondary indexes over a loaded address book.
+///
+/// `AddressBookIndex` takes ownership of the `Vec<CollaboratorTomlData>` returned
+/// by `read_a_collaborator_setup_toml` and builds lookup tables so that
+/// questions such as "which collaborator owns IP 10.0.0.1?" or "who has salt X?"
+/// do not need a linear scan over every collaborator and every list.
+///
+/// # Indexes
+///
+/// - `user_name` -> collaborator (user names are unique; see `AddressBookIndex::new`)
+/// - `Ipv4Addr` -> collaborators listing that address in `ipv4_addresses`
+/// - `Ipv6Addr` -> collaborators listing that address in `ipv6_addresses`
+/// - `u128` salt -> collaborators listing that salt in `user_salt_list`
+///
+/// The address and salt indexes map to a list of positions rather than one
+/// position, because the same value can (wrongly) appear under several
+/// collaborators. `find_conflicts` reports exactly those values.
+///
+/// # Example
+///
+/// ```
+/// let (collaborators, _errors) = read_a_collaborator_setup_toml()?;
+/// let index = AddressBookIndex::new(collaborators)?;
+///
+/// let owners = index.find_by_ip("10.0.0.1".parse().unwrap());
+/// for collaborator in owners {
+///     println!("10.0.0.1 belongs to {}", collaborator.user_name);
+/// }
+///
+/// for conflict in index.find_conflicts() {
+///     println!("{}", conflict);
+/// }
+/// ```
+#[derive(Debug)]
+struct AddressBookIndex {
+    collaborators: Vec<CollaboratorTomlData>,
+    by_user_name: HashMap<String, usize>,
+    by_ipv4: HashMap<Ipv4Addr, Vec<usize>>,
+    by_ipv6: HashMap<Ipv6Addr, Vec<usize>>,
+    by_salt: HashMap<u128, Vec<usize>>,
+}
+
+impl AddressBookIndex {
+    /// Builds all indexes from a list of collaborators.
+    ///
+    /// # Error Handling
+    ///
+    /// Returns `ThisProjectError::IndexError` if two collaborators have the same
+    /// `user_name`, since the user name is the key of the address book
+    /// (files are named `{user_name}__collaborator.toml`).
+    ///
+    /// A collaborator listing the same address or salt twice in its own lists
+    /// is indexed only once for that value, so it is not reported as a conflict
+    /// with itself.
+    fn new(collaborators: Vec<CollaboratorTomlData>) -> Result<AddressBookIndex, ThisProjectError> {
+        let mut by_user_name = HashMap::new();
+        let mut by_ipv4: HashMap<Ipv4Addr, Vec<usize>> = HashMap::new();
+        let mut by_ipv6: HashMap<Ipv6Addr, Vec<usize>> = HashMap::new();
+        let mut by_salt: HashMap<u128, Vec<usize>> = HashMap::new();
+
+        for (position, collaborator) in collaborators.iter().enumerate() {
+            // Index user_name (must be unique)
+            if by_user_name.insert(collaborator.user_name.clone(), position).is_some() {
+                return Err(ThisProjectError::IndexError(format!(
+                    "Duplicate user_name in address book: {}",
+                    collaborator.user_name
+                )));
+            }
+
+            // Index ipv4_addresses
+            if let Some(addresses) = &collaborator.ipv4_addresses {
+                for address in addresses {
+                    push_position(by_ipv4.entry(*address).or_default(), position);
+                }
+            }
+
+            // Index ipv6_addresses
+            if let Some(addresses) = &collaborator.ipv6_addresses {
+                for address in addresses {
+                    push_position(by_ipv6.entry(*address).or_default(), position);
+                }
+            }
+
+            // Index user_salt_list
+            for salt in &collaborator.user_salt_list {
+                push_position(by_salt.entry(*salt).or_default(), position);
+            }
+        }
+
+        Ok(AddressBookIndex {
+            collaborators,
+            by_user_name,
+            by_ipv4,
+            by_ipv6,
+            by_salt,
+        })
+    }
+
+    /// All indexed collaborators, in the order they were given to `new`.
+    fn collaborators(&self) -> &[CollaboratorTomlData] {
+        &self.collaborators
+    }
+
+    /// Looks up one collaborator by `user_name`.
+    fn get_by_user_name(&self, user_name: &str) -> Option<&CollaboratorTomlData> {
+        self.by_user_name
+            .get(user_name)
+            .map(|position| &self.collaborators[*position])
+    }
+
+    /// Collaborators listing `address` in their `ipv4_addresses`.
+    fn find_by_ipv4(&self, address: &Ipv4Addr) -> Vec<&CollaboratorTomlData> {
+        self.resolve(self.by_ipv4.get(address))
+    }
+
+    /// Collaborators listing `address` in their `ipv6_addresses`.
+    fn find_by_ipv6(&self, address: &Ipv6Addr) -> Vec<&CollaboratorTomlData> {
+        self.resolve(self.by_ipv6.get(address))
+    }
+
+    /// Collaborators listing `address` in the list matching its IP version.
+    fn find_by_ip(&self, address: IpAddr) -> Vec<&CollaboratorTomlData> {
+        match address {
+            IpAddr::V4(v4) => self.find_by_ipv4(&v4),
+            IpAddr::V6(v6) => self.find_by_ipv6(&v6),
+        }
+    }
+
+    /// Collaborators listing `salt` in their `user_salt_list`.
+    fn find_by_salt(&self, salt: u128) -> Vec<&CollaboratorTomlData> {
+        self.resolve(self.by_salt.get(&salt))
+    }
+
+    /// Reports every IPv4 address, IPv6 address and salt that is claimed by
+    /// more than one collaborator.
+    ///
+    /// The result is sorted (IPv4, then IPv6, then salts, each in ascending
+    /// order) so that the report is stable from one run to the next.
+    fn find_conflicts(&self) -> Vec<AddressBookConflict> {
+        let mut conflicts = Vec::new();
+
+        let mut shared_ipv4: Vec<(&Ipv4Addr, &Vec<usize>)> = self.by_ipv4.iter()
+            .filter(|(_, positions)| positions.len() > 1)
+            .collect();
+        shared_ipv4.sort();
+        for (address, positions) in shared_ipv4 {
+            conflicts.push(AddressBookConflict::SharedIpv4 {
+                address: *address,
+                user_names: self.user_names_at(positions),
+            });
+        }
+
+        let mut shared_ipv6: Vec<(&Ipv6Addr, &Vec<usize>)> = self.by_ipv6.iter()
+            .filter(|(_, positions)| positions.len() > 1)
+            .collect();
+        shared_ipv6.sort();
+        for (address, positions) in shared_ipv6 {
+            conflicts.push(AddressBookConflict::SharedIpv6 {
+                address: *address,
+                user_names: self.user_names_at(positions),
+            });
+        }
+
+        let mut shared_salts: Vec<(&u128, &Vec<usize>)> = self.by_salt.iter()
+            .filter(|(_, positions)| positions.len() > 1)
+            .collect();
+        shared_salts.sort();
+        for (salt, positions) in shared_salts {
+            conflicts.push(AddressBookConflict::SharedSalt {
+                salt: *salt,
+                user_names: self.user_names_at(positions),
+            });
+        }
+
+        conflicts
+    }
+
+    // Helper function to turn a list of positions into collaborator references
+    fn resolve(&self, positions: Option<&Vec<usize>>) -> Vec<&CollaboratorTomlData> {
+        match positions {
+            Some(positions) => positions.iter().map(|position| &self.collaborators[*position]).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    // Helper function to turn a list of positions into user names
+    fn user_names_at(&self, positions: &[usize]) -> Vec<String> {
+        positions.iter()
+            .map(|position| self.collaborators[*position].user_name.clone())
+            .collect()
+    }
+}
+
+// Helper function to record a position once per value, even if a collaborator
+// lists the same value twice (positions are pushed in ascending order)
+fn push_position(positions: &mut Vec<usize>, position: usize) {
+    if positions.last() != Some(&position) {
+        positions.push(position);
+    }
+}
+
+fn main() {
+    match read_a_collaborator_setup_toml() {
+        Ok((collaborators, errors)) => {
+            if !errors.is_empty() {
+                println!("Errors encountered:");
+                for err in errors {
+                    println!("{}", err);
+                }
+            }
+
+            let index = match AddressBookIndex::new(collaborators) {
+                Ok(index) => index,
+                Err(e) => {
+                    println!("Error indexing address book: {}", e);
+                    return;
+                }
+            };
+            println!("Indexed {} collaborators", index.collaborators().len());
+
+            // Example lookups
+            if let Some(alice) = index.get_by_user_name("alice") {
+                println!(
+                    "Found by user_name: {} (sync every {}s, updated at {}, key {})",
+                    alice.user_name,
+                    alice.sync_interval,
+                    alice.updated_at_timestamp,
+                    alice.gpg_key_public.lines().next().unwrap_or("")
+                );
+            }
+
+            let address: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+            for collaborator in index.find_by_ip(address) {
+                println!("{} is owned by {}", address, collaborator.user_name);
+            }
+
+            for collaborator in index.find_by_salt(0x11111111111111111111111111111111) {
+                println!("Salt 0x11111111111111111111111111111111 is owned by {}", collaborator.user_name);
+            }
+
+            // Report IPs and salts claimed by more than one collaborator
+            let conflicts = index.find_conflicts();
+            if !conflicts.is_empty() {
+                println!("Conflicts found:");
+                for conflict in conflicts {
+                    println!("{}", conflict);
+                }
+            }
+        }
+        Err(e) => {
+            println!("Error reading TOML files: {}", e);
+        }
+    }
+}
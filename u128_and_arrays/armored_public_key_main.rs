@@ -0,0 +1,1116 @@
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::ffi::OsStr;
+use toml::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    gpg_key_public: ArmoredPublicKey,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    ArmorError(String),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::ArmorError(err) => write!(f, "Armor Error: {}", err),
+        }
+    }
+}
+
+const ARMOR_BEGIN: &str = "-----BEGIN PGP PUBLIC KEY BLOCK-----";
+const ARMOR_END: &str = "-----END PGP PUBLIC KEY BLOCK-----";
+
+/// Body lines of emitted armor are this many base64 characters long
+/// (GnuPG uses 64).
+const ARMOR_LINE_LENGTH: usize = 64;
+
+/// OpenPGP packet tag of a public-key packet (RFC 9580, section 5.5).
+const PUBLIC_KEY_PACKET_TAG: u8 = 6;
+
+/// An OpenPGP public key in ASCII armor (RFC 9580, section 6), as stored in
+/// the `gpg_key_public` field.
+///
+/// Parsing checks the armor lines, the base64 body, the CRC24 checksum (if
+/// present; RFC 9580 makes it optional), and the packet framing of the
+/// decoded data, whose first packet must be a public-key packet. The
+/// cryptographic content of the key (algorithm parameters, self-signatures)
+/// is not checked.
+///
+/// # Example
+///
+/// ```
+/// let key = ArmoredPublicKey::parse(armor_text)?;
+/// println!("{}", key.fingerprint_hex()); // e.g. 2F7C572D6E71001D2C85D447EE9220A414FA605F
+/// let text = key.to_armored_string();   // normalized armor, CRC24 included
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+struct ArmoredPublicKey {
+    /// Armor headers such as `("Comment", "...")`, in file order.
+    headers: Vec<(String, String)>,
+    /// The decoded (binary) OpenPGP data: the key packet and the packets after it.
+    packet_data: Vec<u8>,
+    /// Position of the public-key packet body inside `packet_data`.
+    key_packet_start: usize,
+    key_packet_end: usize,
+}
+
+impl ArmoredPublicKey {
+    /// Parses an ASCII-armored public key block.
+    ///
+    /// Leading and trailing whitespace around the block and trailing
+    /// whitespace (including `\r`) on each line are ignored.
+    ///
+    /// # Error Handling
+    ///
+    /// Returns `ThisProjectError::ArmorError` for a missing or wrong BEGIN or
+    /// END line, a malformed header line, invalid base64, a CRC24 mismatch,
+    /// truncated or malformed packets, or a first packet that is not a
+    /// version 4, 5 or 6 public-key packet.
+    fn parse(armor: &str) -> Result<ArmoredPublicKey, ThisProjectError> {
+        let armor_error = |message: String| ThisProjectError::ArmorError(message);
+        let mut lines = armor.trim().lines().map(|line| line.trim_end());
+
+        // 1. BEGIN line
+        match lines.next() {
+            Some(ARMOR_BEGIN) => {}
+            Some(other) => return Err(armor_error(format!("Expected '{}', found '{}'", ARMOR_BEGIN, other))),
+            None => return Err(armor_error("Empty public key".into())),
+        }
+
+        // 2. Headers, up to the first blank line
+        let mut headers = Vec::new();
+        loop {
+            match lines.next() {
+                Some("") => break,
+                Some(line) => match line.split_once(": ") {
+                    Some((name, value)) if !name.is_empty() && !name.contains(char::is_whitespace) => {
+                        headers.push((name.to_string(), value.to_string()));
+                    }
+                    _ => return Err(armor_error(format!("Invalid armor header line '{}' (missing blank line after headers?)", line))),
+                },
+                None => return Err(armor_error("Armor ends inside the header section".into())),
+            }
+        }
+
+        // 3. Base64 body, optional "=XXXX" checksum line, END line
+        let mut body = String::new();
+        let mut checksum_line = None;
+        loop {
+            match lines.next() {
+                Some(ARMOR_END) => break,
+                Some(line) if line.starts_with('=') => {
+                    if checksum_line.is_some() {
+                        return Err(armor_error("More than one checksum line".into()));
+                    }
+                    checksum_line = Some(line);
+                }
+                Some(line) => {
+                    if checksum_line.is_some() {
+                        return Err(armor_error("Armor body continues after the checksum line".into()));
+                    }
+                    body.push_str(line);
+                }
+                None => return Err(armor_error(format!("Missing '{}'", ARMOR_END))),
+            }
+        }
+        if lines.next().is_some() {
+            return Err(armor_error(format!("Unexpected text after '{}'", ARMOR_END)));
+        }
+
+        let packet_data = decode_base64(&body).ok_or_else(|| armor_error("Invalid base64 in armor body".into()))?;
+        if packet_data.is_empty() {
+            return Err(armor_error("Armor body is empty".into()));
+        }
+
+        // 4. CRC24 checksum
+        if let Some(line) = checksum_line {
+            let checksum = decode_base64(&line[1..])
+                .filter(|bytes| bytes.len() == 3 && line.len() == 5)
+                .ok_or_else(|| armor_error(format!("Invalid armor checksum line '{}'", line)))?;
+            let expected = ((checksum[0] as u32) << 16) | ((checksum[1] as u32) << 8) | checksum[2] as u32;
+            let actual = crc24(&packet_data);
+            if expected != actual {
+                return Err(armor_error(format!(
+                    "Armor checksum mismatch: expected {:06X}, computed {:06X}",
+                    expected, actual
+                )));
+            }
+        }
+
+        // 5. Packets: all must be well-formed, the first must be a public key
+        let packets = split_openpgp_packets(&packet_data)?;
+        let (tag, key_packet_start, key_packet_end) = packets[0];
+        if tag != PUBLIC_KEY_PACKET_TAG {
+            return Err(armor_error(format!("First packet has tag {}, expected a public-key packet (tag 6)", tag)));
+        }
+        if key_packet_start == key_packet_end {
+            return Err(armor_error("Public-key packet is empty".into()));
+        }
+        let key = ArmoredPublicKey { headers, packet_data, key_packet_start, key_packet_end };
+
+        let minimum_length = match key.key_version() {
+            4 => 6,     // version, creation time, algorithm
+            5 | 6 => 10, // ... plus the 4-byte key material length
+            version => return Err(armor_error(format!("Unsupported public key version {}", version))),
+        };
+        if key.key_packet_bytes().len() < minimum_length {
+            return Err(armor_error("Public-key packet is too short".into()));
+        }
+
+        Ok(key)
+    }
+
+    /// Armor headers (e.g. `Comment`), in the order they appeared.
+    fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// All decoded OpenPGP data: the public-key packet followed by user IDs,
+    /// signatures and subkeys, including packet headers.
+    fn packet_bytes(&self) -> &[u8] {
+        &self.packet_data
+    }
+
+    /// The body of the primary public-key packet (without its packet header).
+    fn key_packet_bytes(&self) -> &[u8] {
+        &self.packet_data[self.key_packet_start..self.key_packet_end]
+    }
+
+    /// The key packet version: 4 (RFC 4880), 5 (LibrePGP) or 6 (RFC 9580).
+    fn key_version(&self) -> u8 {
+        self.key_packet_bytes()[0]
+    }
+
+    /// The key fingerprint (RFC 9580, section 5.5.4): SHA-1 (20 bytes) for
+    /// version 4 keys, SHA-256 (32 bytes) for version 5 and 6 keys.
+    fn fingerprint(&self) -> Vec<u8> {
+        let body = self.key_packet_bytes();
+        let mut hashed = Vec::with_capacity(body.len() + 5);
+        match self.key_version() {
+            4 => {
+                hashed.push(0x99);
+                hashed.extend_from_slice(&(body.len() as u16).to_be_bytes());
+                hashed.extend_from_slice(body);
+                sha1(&hashed).to_vec()
+            }
+            version => {
+                hashed.push(if version == 5 { 0x9a } else { 0x9b });
+                hashed.extend_from_slice(&(body.len() as u32).to_be_bytes());
+                hashed.extend_from_slice(body);
+                sha256(&hashed).to_vec()
+            }
+        }
+    }
+
+    /// The fingerprint as upper-case hex, as GnuPG prints it.
+    fn fingerprint_hex(&self) -> String {
+        self.fingerprint().iter().map(|b| format!("{:02X}", b)).collect()
+    }
+
+    /// Writes the key back as armor: BEGIN line, headers, blank line, base64
+    /// body in lines of `ARMOR_LINE_LENGTH`, CRC24 line, END line, each line
+    /// ending in `\n`.
+    fn to_armored_string(&self) -> String {
+        let mut armor = String::new();
+        armor.push_str(ARMOR_BEGIN);
+        armor.push('\n');
+        for (name, value) in &self.headers {
+            armor.push_str(&format!("{}: {}\n", name, value));
+        }
+        armor.push('\n');
+
+        let body = encode_base64(&self.packet_data);
+        for chunk in body.as_bytes().chunks(ARMOR_LINE_LENGTH) {
+            // base64 output is ASCII, so byte chunks are valid str
+            armor.push_str(std::str::from_utf8(chunk).unwrap_or_default());
+            armor.push('\n');
+        }
+
+        armor.push('=');
+        armor.push_str(&encode_base64(&crc24(&self.packet_data).to_be_bytes()[1..]));
+        armor.push('\n');
+        armor.push_str(ARMOR_END);
+        armor.push('\n');
+        armor
+    }
+}
+
+impl fmt::Display for ArmoredPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_armored_string())
+    }
+}
+
+impl std::str::FromStr for ArmoredPublicKey {
+    type Err = ThisProjectError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ArmoredPublicKey::parse(s)
+    }
+}
+
+/// Splits binary OpenPGP data into packets (RFC 9580, section 4.2).
+///
+/// Returns `(tag, body_start, body_end)` for each packet. Both the legacy
+/// and the current packet header formats are accepted. Partial body lengths
+/// are rejected: they are only allowed for data packets, which never appear
+/// in a transferable public key.
+fn split_openpgp_packets(data: &[u8]) -> Result<Vec<(u8, usize, usize)>, ThisProjectError> {
+    let truncated = || ThisProjectError::ArmorError("Truncated OpenPGP packet".into());
+    let mut packets = Vec::new();
+    let mut pos = 0;
+
+    while pos < data.len() {
+        let header = data[pos];
+        if header & 0x80 == 0 {
+            return Err(ThisProjectError::ArmorError(format!("Invalid OpenPGP packet header at byte {}", pos)));
+        }
+        pos += 1;
+
+        let read_be = |pos: usize, count: usize| -> Result<usize, ThisProjectError> {
+            let bytes = data.get(pos..pos + count).ok_or_else(truncated)?;
+            Ok(bytes.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize))
+        };
+
+        let (tag, length) = if header & 0x40 != 0 {
+            // Current format: 6-bit tag, 1, 2 or 5 octet length
+            let first = *data.get(pos).ok_or_else(truncated)? as usize;
+            let length = match first {
+                0..=191 => {
+                    pos += 1;
+                    first
+                }
+                192..=223 => {
+                    let second = *data.get(pos + 1).ok_or_else(truncated)? as usize;
+                    pos += 2;
+                    ((first - 192) << 8) + second + 192
+                }
+                255 => {
+                    let length = read_be(pos + 1, 4)?;
+                    pos += 5;
+                    length
+                }
+                _ => return Err(ThisProjectError::ArmorError("Partial body length in a key packet".into())),
+            };
+            (header & 0x3f, length)
+        } else {
+            // Legacy format: 4-bit tag, 1, 2 or 4 octet length
+            let length = match header & 0x03 {
+                0 => read_be(pos, 1)?,
+                1 => read_be(pos, 2)?,
+                2 => read_be(pos, 4)?,
+                _ => return Err(ThisProjectError::ArmorError("Indeterminate packet length in a key packet".into())),
+            };
+            pos += [1, 2, 4][(header & 0x03) as usize];
+            ((header >> 2) & 0x0f, length)
+        };
+
+        let end = pos.checked_add(length).filter(|end| *end <= data.len()).ok_or_else(truncated)?;
+        packets.push((tag, pos, end));
+        pos = end;
+    }
+
+    Ok(packets)
+}
+
+/// CRC-24 of the armor checksum (RFC 9580, section 6.1).
+fn crc24(data: &[u8]) -> u32 {
+    const CRC24_INIT: u32 = 0xb704ce;
+    const CRC24_POLY: u32 = 0x1864cfb;
+
+    let mut crc = CRC24_INIT;
+    for byte in data {
+        crc ^= (*byte as u32) << 16;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x1000000 != 0 {
+                crc ^= CRC24_POLY;
+            }
+        }
+    }
+    crc & 0xffffff
+}
+
+// Helper function to decode standard base64 (RFC 4648), with or without '=' padding
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim_end_matches('=');
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in text.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    // Leftover bits must be padding zeros, and one leftover character is never valid
+    if bits >= 6 || buffer & ((1 << bits) - 1) != 0 {
+        return None;
+    }
+    Some(bytes)
+}
+
+// Helper function to encode standard base64 (RFC 4648) with '=' padding
+fn encode_base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(ALPHABET[((group >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+/// SHA-1 (FIPS 180-4) of a byte slice, for version 4 key fingerprints only.
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut state: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+    for block in md_padded(data).chunks(64) {
+        let mut w = [0u32; 80];
+        for i in 0..16 {
+            w[i] = u32::from_be_bytes([block[i * 4], block[i * 4 + 1], block[i * 4 + 2], block[i * 4 + 3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+        for (i, word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5a827999),
+                20..=39 => (b ^ c ^ d, 0x6ed9eba1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+                _ => (b ^ c ^ d, 0xca62c1d6),
+            };
+            let temp = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        for (word, value) in state.iter_mut().zip([a, b, c, d, e]) {
+            *word = word.wrapping_add(value);
+        }
+    }
+
+    let mut digest = [0u8; 20];
+    for (i, word) in state.iter().enumerate() {
+        digest[i * 4..i * 4 + 4].copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+/// SHA-256 round constants (FIPS 180-4, section 4.2.2).
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// SHA-256 initial hash value (FIPS 180-4, section 5.3.3).
+const SHA256_H0: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Incremental SHA-256 (FIPS 180-4), implemented here to avoid a crypto dependency.
+///
+/// # use with
+/// let mut hasher = Sha256::new();
+/// hasher.update(b"part one");
+/// hasher.update(b"part two");
+/// let digest: [u8; 32] = hasher.finalize();
+struct Sha256 {
+    state: [u32; 8],
+    buffer: [u8; 64],
+    buffer_len: usize,
+    total_len: u64, // in bytes
+}
+
+impl Sha256 {
+    fn new() -> Sha256 {
+        Sha256 { state: SHA256_H0, buffer: [0; 64], buffer_len: 0, total_len: 0 }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        self.total_len = self.total_len.wrapping_add(data.len() as u64);
+
+        // Fill up a partial block first
+        if self.buffer_len > 0 {
+            let take = (64 - self.buffer_len).min(data.len());
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+            if self.buffer_len < 64 {
+                return;
+            }
+            let block = self.buffer;
+            self.compress(&block);
+            self.buffer_len = 0;
+        }
+
+        // Whole blocks straight from the input
+        while data.len() >= 64 {
+            let mut block = [0u8; 64];
+            block.copy_from_slice(&data[..64]);
+            self.compress(&block);
+            data = &data[64..];
+        }
+
+        // Keep the rest for later
+        self.buffer[..data.len()].copy_from_slice(data);
+        self.buffer_len = data.len();
+    }
+
+    fn finalize(mut self) -> [u8; 32] {
+        let bit_len = self.total_len.wrapping_mul(8);
+
+        // Padding: 0x80, zeros, then the message length in bits (big-endian u64)
+        let mut padding = vec![0x80u8];
+        let pad_zeros = (64 + 56 - (self.buffer_len + 1) % 64) % 64;
+        padding.resize(1 + pad_zeros, 0);
+        padding.extend_from_slice(&bit_len.to_be_bytes());
+        let total_len = self.total_len;
+        self.update(&padding);
+        self.total_len = total_len;
+
+        let mut digest = [0u8; 32];
+        for (i, word) in self.state.iter().enumerate() {
+            digest[i * 4..i * 4 + 4].copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+
+    fn compress(&mut self, block: &[u8; 64]) {
+        let mut w = [0u32; 64];
+        for i in 0..16 {
+            w[i] = u32::from_be_bytes([block[i * 4], block[i * 4 + 1], block[i * 4 + 2], block[i * 4 + 3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let temp1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(SHA256_K[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(maj);
+
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp1.wrapping_add(temp2);
+        }
+
+        for (word, value) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *word = word.wrapping_add(value);
+        }
+    }
+}
+
+/// SHA-256 of a byte slice in one call.
+fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+// Helper function: SHA-1 message padding (0x80, zeros, then the message
+// length in bits as a big-endian u64)
+fn md_padded(data: &[u8]) -> Vec<u8> {
+    let mut padded = data.to_vec();
+    padded.push(0x80);
+    while padded.len() % 64 != 56 {
+        padded.push(0);
+    }
+    padded.extend_from_slice(&((data.len() as u64).wrapping_mul(8)).to_be_bytes());
+    padded
+}
+
+/// Toml Deserialization: Reads collaborator setup data from TOML files in a
+/// specified directory.
+///
+/// Each `*.toml` file in `project_graph_data/collaborator_files_address_book`
+/// becomes one collaborator, with `gpg_key_public` parsed into an
+/// `ArmoredPublicKey`. A file with a missing field, or whose key armor is
+/// corrupt (bad base64, CRC24 mismatch, truncated packets, ...), is skipped
+/// and its error is added to the error vector.
+///
+/// # Returns
+///
+/// Returns a `Result` containing:
+/// - `Ok`: A tuple with:
+///     - A vector of successfully parsed `CollaboratorTomlData` instances.
+///     - A vector of any `ThisProjectError` encountered during parsing.
+/// - `Err`: A `ThisProjectError` if there was an error reading the directory or any file.
+fn read_a_collaborator_setup_toml() -> Result<(Vec<CollaboratorTomlData>, Vec<ThisProjectError>), ThisProjectError> {
+    let mut collaborators = Vec::new();
+    let mut errors = Vec::new();
+    let dir_path = Path::new("project_graph_data/collaborator_files_address_book");
+
+    for entry in fs::read_dir(dir_path)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
+            let toml_string = fs::read_to_string(&path)?;
+
+            match toml::from_str::<Value>(&toml_string) {
+                Ok(toml_value) => {
+                    if let Value::Table(table) = toml_value {
+                        // Extract user_name
+                        let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+                            s.clone()
+                        } else {
+                            errors.push(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+                            continue;
+                        };
+
+                        // Extract user_salt_list
+                        let user_salt_list = if let Some(Value::Array(arr)) = table.get("user_salt_list") {
+                            arr.iter()
+                                .map(|val| {
+                                    if let Value::String(s) = val {
+                                        u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                                            .map_err(ThisProjectError::ParseIntError)
+                                    } else {
+                                        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                                    }
+                                })
+                                .collect::<Result<Vec<u128>, ThisProjectError>>()?
+                        } else {
+                            errors.push(ThisProjectError::TomlVanillaDeserialStrError("Missing user_salt_list".into()));
+                            continue;
+                        };
+
+                        // Extract ipv4_addresses
+                        let ipv4_addresses = extract_ipv4_addresses(&table, "ipv4_addresses", &mut errors)?;
+
+                        // Extract ipv6_addresses
+                        let ipv6_addresses = extract_ipv6_addresses(&table, "ipv6_addresses", &mut errors)?;
+
+                        // Extract gpg_key_public
+                        let gpg_key_public = match extract_armored_public_key(&table, "gpg_key_public") {
+                            Ok(key) => key,
+                            Err(e) => {
+                                errors.push(e);
+                                continue;
+                            }
+                        };
+
+                        // Extract sync_interval
+                        let sync_interval = extract_u64(&table, "sync_interval", &mut errors)?;
+
+                        // Extract updated_at_timestamp
+                        let updated_at_timestamp = extract_u64(&table, "updated_at_timestamp", &mut errors)?;
+
+                        // Create CollaboratorTomlData instance
+                        let collaborator = CollaboratorTomlData {
+                            user_name,
+                            user_salt_list,
+                            ipv4_addresses,
+                            ipv6_addresses,
+                            gpg_key_public,
+                            sync_interval,
+                            updated_at_timestamp,
+                        };
+
+                        collaborators.push(collaborator);
+                    } else {
+                        errors.push(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure".into()));
+                    }
+                }
+                Err(e) => {
+                    errors.push(ThisProjectError::TomlVanillaDeserialStrError(e.to_string()));
+                }
+            }
+        }
+    }
+
+    Ok((collaborators, errors))
+}
+
+// Helper function to extract and parse IPv4 addresses from a toml::Value::Table
+fn extract_ipv4_addresses(
+    table: &toml::map::Map<String, Value>,
+    key: &str,
+    errors: &mut Vec<ThisProjectError>
+) -> Result<Option<Vec<Ipv4Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new(); // Create an empty vector to store addresses
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv4Addr>() {
+                    Ok(ip) => addresses.push(ip), // Push successful IP address
+                    Err(e) => errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() { // If no valid addresses were found
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None) // Return None if the key is not present
+    }
+}
+
+// Helper function to extract and parse IPv6 addresses from a toml::Value::Table
+fn extract_ipv6_addresses(table: &toml::map::Map<String, Value>, key: &str, errors: &mut Vec<ThisProjectError>) -> Result<Option<Vec<Ipv6Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new(); // Create an empty vector to store addresses
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv6Addr>() {
+                    Ok(ip) => addresses.push(ip), // Push successful IP address
+                    Err(e) => errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() { // If no valid addresses were found
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None) // Return None if the key is not present
+    }
+}
+
+// Helper function to extract and parse an armored public key from a toml::Value::Table
+fn extract_armored_public_key(table: &toml::map::Map<String, Value>, key: &str) -> Result<ArmoredPublicKey, ThisProjectError> {
+    if let Some(Value::String(s)) = table.get(key) {
+        ArmoredPublicKey::parse(s).map_err(|e| match e {
+            ThisProjectError::ArmorError(message) => ThisProjectError::ArmorError(format!("Invalid {}: {}", key, message)),
+            other => other,
+        })
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str, errors: &mut Vec<ThisProjectError>) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)));
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)));
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+/// Reads collaborator setup data from a TOML file for a specific user.
+///
+/// Reads `{collaborator_name}__collaborator.toml` from the address book and
+/// returns the first problem found as the error: a missing field, a bad
+/// address, or corrupt `gpg_key_public` armor
+/// (`ThisProjectError::ArmorError`).
+fn read_one_collaborator_setup_toml(collaborator_name: &str) -> Result<CollaboratorTomlData, ThisProjectError> {
+
+    // 1. Construct File Path
+    let file_path = Path::new("project_graph_data/collaborator_files_address_book")
+        .join(format!("{}__collaborator.toml", collaborator_name));
+
+    // 2. Read TOML File
+    let toml_string = fs::read_to_string(&file_path)?;
+
+    // 3. Parse TOML Data (handle potential toml::de::Error)
+    let toml_value = match toml::from_str::<Value>(&toml_string) {
+        Ok(value) => value,
+        Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(e.to_string())),
+    };
+
+    // 4. Extract Data from TOML Value
+    if let Value::Table(table) = toml_value {
+        // The address extractors collect their errors here; the first one is
+        // returned once both lists are read. Any other problem returns early
+        let mut errors = Vec::new();
+
+        // Extract user_name
+        let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+            s.clone()
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+        };
+
+        // Extract user_salt_list
+        let user_salt_list = if let Some(Value::Array(arr)) = table.get("user_salt_list") {
+            arr.iter()
+                .map(|val| {
+                    if let Value::String(s) = val {
+                        u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                            .map_err(ThisProjectError::ParseIntError)
+                    } else {
+                        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                    }
+                })
+                .collect::<Result<Vec<u128>, ThisProjectError>>()?
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_salt_list".into()));
+        };
+
+        // Extract ipv4_addresses
+        let ipv4_addresses = extract_ipv4_addresses(&table, "ipv4_addresses", &mut errors)?;
+
+        // Extract ipv6_addresses
+        let ipv6_addresses = extract_ipv6_addresses(&table, "ipv6_addresses", &mut errors)?;
+        if !errors.is_empty() {
+            return Err(errors.remove(0));
+        }
+
+        // Extract gpg_key_public
+        let gpg_key_public = extract_armored_public_key(&table, "gpg_key_public")?;
+
+        // Extract sync_interval
+        let sync_interval = extract_u64(&table, "sync_interval", &mut errors)?;
+
+        // Extract updated_at_timestamp
+        let updated_at_timestamp = extract_u64(&table, "updated_at_timestamp", &mut errors)?;
+
+        // 5. Return CollaboratorTomlData
+        Ok(CollaboratorTomlData {
+            user_name,
+            user_salt_list,
+            ipv4_addresses,
+            ipv6_addresses,
+            gpg_key_public,
+            sync_interval,
+            updated_at_timestamp,
+        })
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure: Expected a table".into()))
+    }
+}
+
+/// Serializes a `CollaboratorTomlData` struct into a TOML-formatted string.
+///
+/// One `key = value` line per field, in struct order; salts are `0x` hex
+/// strings and an IP list that is `None` is left out. `gpg_key_public` is
+/// written as its normalized armor in a TOML multi-line basic string, one
+/// armor line per TOML line:
+///
+/// ```toml
+/// gpg_key_public = """
+/// -----BEGIN PGP PUBLIC KEY BLOCK-----
+///
+/// mDMEatTFwhYJKwYBBAHaRw8BAQdAA6yKGkV+diS/JzYY8KjpK6XQiajW0ETCvjJJ
+/// ...
+/// =zSrI
+/// -----END PGP PUBLIC KEY BLOCK-----
+/// """
+/// ```
+///
+/// TOML drops the newline right after the opening `"""`, so the value read
+/// back starts with the BEGIN line.
+fn serialize_collaborator_to_toml(collaborator: &CollaboratorTomlData) -> Result<String, ThisProjectError> {
+    let mut toml_string = String::new();
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", escape_toml_basic_string(&collaborator.user_name)));
+
+    // Add user_salt_list
+    toml_string.push_str("user_salt_list = [\n");
+    for salt in &collaborator.user_salt_list {
+        toml_string.push_str(&format!("    \"0x{:x}\",\n", salt));
+    }
+    toml_string.push_str("]\n");
+
+    // Add ipv4_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv4_addresses", &collaborator.ipv4_addresses)?;
+
+    // Add ipv6_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv6_addresses", &collaborator.ipv6_addresses)?;
+
+    // Add gpg_key_public
+    toml_string.push_str(&format!(
+        "gpg_key_public = \"\"\"\n{}\"\"\"\n",
+        escape_toml_multiline_basic_string(&collaborator.gpg_key_public.to_armored_string())
+    ));
+
+    // Add sync_interval
+    toml_string.push_str(&format!("sync_interval = {}\n", collaborator.sync_interval));
+
+    // Add updated_at_timestamp
+    toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp));
+
+    Ok(toml_string)
+}
+
+// Helper function to serialize IP addresses to TOML array format
+fn serialize_ip_addresses<T: std::fmt::Display>(
+    toml_string: &mut String,
+    key: &str,
+    addresses: &Option<Vec<T>>
+) -> Result<(), ThisProjectError> {
+    if let Some(addr_vec) = addresses {
+        toml_string.push_str(&format!("{} = [\n", key));
+        for addr in addr_vec {
+            toml_string.push_str(&format!("    \"{}\",\n", addr));
+        }
+        toml_string.push_str("]\n");
+    }
+    Ok(()) // Return Ok(()) if the addresses field is None
+}
+
+// Helper function to escape a value for a TOML basic string ("...")
+fn escape_toml_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Helper function to escape a value for a TOML multi-line basic string ("""...""");
+// newlines stay literal, quotes are escaped so that '"""' cannot end the string early
+fn escape_toml_multiline_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\n' => escaped.push('\n'),
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push('\t'),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Function to write a TOML string to a file
+fn write_toml_to_file(file_path: &str, toml_string: &str) -> Result<(), ThisProjectError> {
+    // Attempt to create the file.
+    let mut file = match File::create(file_path) {
+        Ok(file) => file,
+        Err(e) => return Err(ThisProjectError::IoError(e)),
+    };
+
+    // Attempt to write to the file.
+    if let Err(e) = file.write_all(toml_string.as_bytes()) {
+        return Err(ThisProjectError::IoError(e));
+    }
+
+    // Everything successful!
+    Ok(())
+}
+
+/// An Ed25519 public key exported with `gpg --armor --export bob@example.com`,
+/// used as the demo value.
+const EXAMPLE_ARMORED_PUBLIC_KEY: &str = "-----BEGIN PGP PUBLIC KEY BLOCK-----
+
+mDMEatTFwhYJKwYBBAHaRw8BAQdAA6yKGkV+diS/JzYY8KjpK6XQiajW0ETCvjJJ
+sPMLTdO0FUJvYiA8Ym9iQGV4YW1wbGUuY29tPoiQBBMWCAA4FiEEL3xXLW5xAB0s
+hdRH7pIgpBT6YF8FAmrUxcICGwMFCwkIBwIGFQoJCAsCBBYCAwECHgECF4AACgkQ
+7pIgpBT6YF/qbAEA7MqNJ0YUVaAM+ScBlNPHt18OVbY2YHyEXJXDp1uxKVYBAPKo
+6AblX83srlwlnVjYZ3kjjObiNSJLW8NEpXnyk/kO
+=zSrI
+-----END PGP PUBLIC KEY BLOCK-----
+";
+
+fn main() {
+    // Parse the example key
+    let gpg_key_public = match ArmoredPublicKey::parse(EXAMPLE_ARMORED_PUBLIC_KEY) {
+        Ok(key) => key,
+        Err(e) => {
+            println!("Error parsing example key: {}", e);
+            return;
+        }
+    };
+    println!("Key version: {}", gpg_key_public.key_version());
+    println!("Key fingerprint: {}", gpg_key_public.fingerprint_hex());
+    println!(
+        "Key packet: {} bytes of {} bytes of packet data, {} armor headers",
+        gpg_key_public.key_packet_bytes().len(),
+        gpg_key_public.packet_bytes().len(),
+        gpg_key_public.headers().len()
+    );
+
+    // Corrupt armor is rejected
+    let corrupted = EXAMPLE_ARMORED_PUBLIC_KEY.replace("mDMEatTFwhYJ", "mDMEatTFwhYK");
+    if let Err(e) = corrupted.parse::<ArmoredPublicKey>() {
+        println!("Corrupted key rejected: {}", e);
+    }
+
+    // Example CollaboratorTomlData instance
+    let collaborator = CollaboratorTomlData {
+        user_name: "Bob".to_string(),
+        user_salt_list: vec![0x123456789abcdef0, 0xabcdef0123456789],
+        ipv4_addresses: Some(vec![Ipv4Addr::new(192, 168, 1, 1), Ipv4Addr::new(10, 0, 0, 1)]),
+        ipv6_addresses: Some(vec![Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)]),
+        gpg_key_public,
+        sync_interval: 300,
+        updated_at_timestamp: 1728308000,
+    };
+
+    // Serialize the collaborator data to a TOML string
+    match serialize_collaborator_to_toml(&collaborator) {
+        Ok(toml_string) => {
+            println!("Serialized TOML:\n{}", toml_string);
+
+            // Write the TOML string to a file (example file path)
+            match write_toml_to_file("collaborator_data.toml", &toml_string) {
+                Ok(_) => println!("TOML data written to file successfully."),
+                Err(e) => println!("Error writing to file: {}", e),
+            }
+        }
+        Err(e) => println!("Error serializing to TOML: {}", e),
+    }
+
+    // Read one collaborator back
+    match read_one_collaborator_setup_toml("alice") {
+        Ok(collaborator) => println!("alice's key fingerprint: {}", collaborator.gpg_key_public.fingerprint_hex()),
+        Err(e) => println!("Error reading collaborator data for alice: {}", e),
+    }
+
+    // Read the whole address book
+    match read_a_collaborator_setup_toml() {
+        Ok((collaborators, errors)) => {
+            if !errors.is_empty() {
+                println!("Errors encountered:");
+                for err in errors {
+                    println!("{}", err);
+                }
+            }
+
+            println!("Collaborators:");
+            for collaborator in collaborators {
+                println!("{}: {}", collaborator.user_name, collaborator.gpg_key_public.fingerprint_hex());
+            }
+        }
+        Err(e) => {
+            println!("Error reading TOML files: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    // Known-answer vectors from FIPS 180-4 / the NIST SHA examples
+
+    #[test]
+    fn sha1_known_answers() {
+        assert_eq!(to_hex(&sha1(b"")), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        assert_eq!(to_hex(&sha1(b"abc")), "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(
+            to_hex(&sha1(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")),
+            "84983e441c3bd26ebaae4aa1f95129e5e54670f1"
+        );
+        assert_eq!(to_hex(&sha1(&vec![b'a'; 1_000_000])), "34aa973cd4c4daa4f61eeb2bdbad27316534016f");
+    }
+
+    #[test]
+    fn sha256_known_answers() {
+        assert_eq!(to_hex(&sha256(b"")), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(to_hex(&sha256(b"abc")), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(
+            to_hex(&sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+        assert_eq!(
+            to_hex(&sha256(&vec![b'a'; 1_000_000])),
+            "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+        );
+    }
+
+    #[test]
+    fn version_4_fingerprint_matches_gnupg() {
+        let key = ArmoredPublicKey::parse(EXAMPLE_ARMORED_PUBLIC_KEY).unwrap();
+        assert_eq!(key.key_version(), 4);
+        // As printed by `gpg --fingerprint` for this key
+        assert_eq!(key.fingerprint_hex(), "2F7C572D6E71001D2C85D447EE9220A414FA605F");
+    }
+}
@@ -0,0 +1,492 @@
+use std::fmt;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+#[derive(Debug, PartialEq)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    BinaryDecodeError(String),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::BinaryDecodeError(err) => write!(f, "Binary Decode Error: {}", err),
+        }
+    }
+}
+
+/// First four bytes of every encoded collaborator ("Collaborator Toml Data Binary").
+const BINARY_MAGIC: [u8; 4] = *b"CTDB";
+
+/// Version of the layout described in `encode_collaborator_to_bytes`.
+/// Bump this when the layout changes; the decoder rejects unknown versions.
+const BINARY_FORMAT_VERSION: u8 = 1;
+
+/// Tags for `Option` fields.
+const TAG_NONE: u8 = 0;
+const TAG_SOME: u8 = 1;
+
+/// Encodes a `CollaboratorTomlData` struct into a compact binary format.
+///
+/// This is meant for on-the-wire sync between peers, where the TOML text
+/// (and especially the 32-hex-digit salts) is wasteful.
+///
+/// # Binary Format (version 1)
+///
+/// ```text
+/// magic                 4 bytes   "CTDB"
+/// version               1 byte    0x01
+/// user_name             varint length, then UTF-8 bytes
+/// user_salt_list        varint count, then count x 16 bytes (u128, big-endian)
+/// ipv4_addresses        tag (0 = None, 1 = Some), if Some: varint count, then count x 4 bytes
+/// ipv6_addresses        tag (0 = None, 1 = Some), if Some: varint count, then count x 16 bytes
+/// gpg_key_public        varint length, then UTF-8 bytes
+/// sync_interval         varint
+/// updated_at_timestamp  varint
+/// ```
+///
+/// # Varints
+///
+/// Lengths, counts and `u64` values are written as unsigned LEB128 varints:
+/// 7 bits per byte, least significant group first, high bit set on every
+/// byte except the last. A `u64` takes 1 to 10 bytes (e.g. `300` is `ac 02`).
+///
+/// IP addresses and salts are written raw, in network (big-endian) byte order,
+/// the same order as `Ipv4Addr::octets` / `Ipv6Addr::octets`.
+///
+/// # use with
+/// let bytes = encode_collaborator_to_bytes(&collaborator);
+/// let collaborator_again = decode_collaborator_from_bytes(&bytes)?;
+fn encode_collaborator_to_bytes(collaborator: &CollaboratorTomlData) -> Vec<u8> {
+    let mut bytes = Vec::new();
+
+    // Add header
+    bytes.extend_from_slice(&BINARY_MAGIC);
+    bytes.push(BINARY_FORMAT_VERSION);
+
+    // Add user_name
+    encode_str(&mut bytes, &collaborator.user_name);
+
+    // Add user_salt_list
+    encode_varint(&mut bytes, collaborator.user_salt_list.len() as u64);
+    for salt in &collaborator.user_salt_list {
+        bytes.extend_from_slice(&salt.to_be_bytes());
+    }
+
+    // Add ipv4_addresses
+    match &collaborator.ipv4_addresses {
+        Some(addresses) => {
+            bytes.push(TAG_SOME);
+            encode_varint(&mut bytes, addresses.len() as u64);
+            for address in addresses {
+                bytes.extend_from_slice(&address.octets());
+            }
+        }
+        None => bytes.push(TAG_NONE),
+    }
+
+    // Add ipv6_addresses
+    match &collaborator.ipv6_addresses {
+        Some(addresses) => {
+            bytes.push(TAG_SOME);
+            encode_varint(&mut bytes, addresses.len() as u64);
+            for address in addresses {
+                bytes.extend_from_slice(&address.octets());
+            }
+        }
+        None => bytes.push(TAG_NONE),
+    }
+
+    // Add gpg_key_public
+    encode_str(&mut bytes, &collaborator.gpg_key_public);
+
+    // Add sync_interval
+    encode_varint(&mut bytes, collaborator.sync_interval);
+
+    // Add updated_at_timestamp
+    encode_varint(&mut bytes, collaborator.updated_at_timestamp);
+
+    bytes
+}
+
+// Helper function to write an unsigned LEB128 varint
+fn encode_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+// Helper function to write a length-prefixed UTF-8 string
+fn encode_str(bytes: &mut Vec<u8>, s: &str) {
+    encode_varint(bytes, s.len() as u64);
+    bytes.extend_from_slice(s.as_bytes());
+}
+
+/// Decodes a `CollaboratorTomlData` struct from the binary format written
+/// by `encode_collaborator_to_bytes`.
+///
+/// # Strict Bounds Checks
+///
+/// The input may come from a peer over the network, so it is treated as
+/// untrusted:
+///
+/// - Every read checks that enough bytes remain; truncated input is an error,
+///   never a panic.
+/// - Counts and lengths are checked against the number of remaining bytes
+///   *before* anything is allocated, so a forged count such as `2^60`
+///   cannot trigger a huge allocation.
+/// - Varints longer than 10 bytes, overflowing a `u64`, or padded with
+///   redundant zero bytes are rejected, so each value has exactly one encoding.
+/// - Option tags other than 0 and 1, invalid UTF-8, an unknown magic or
+///   version, and trailing bytes after the last field are rejected.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::BinaryDecodeError` naming the field and the
+/// byte offset where decoding failed, e.g.
+/// `"user_salt_list: count 1000 needs 16000 bytes but only 12 remain (offset 9)"`.
+fn decode_collaborator_from_bytes(bytes: &[u8]) -> Result<CollaboratorTomlData, ThisProjectError> {
+    let mut reader = BinaryReader { bytes, pos: 0 };
+
+    // Check header
+    let magic = reader.read_exact(BINARY_MAGIC.len(), "magic")?;
+    if magic != BINARY_MAGIC {
+        return Err(ThisProjectError::BinaryDecodeError("Not a collaborator binary: bad magic bytes".into()));
+    }
+    let version = reader.read_u8("version")?;
+    if version != BINARY_FORMAT_VERSION {
+        return Err(ThisProjectError::BinaryDecodeError(format!(
+            "Unsupported binary format version {} (expected {})",
+            version, BINARY_FORMAT_VERSION
+        )));
+    }
+
+    // Extract user_name
+    let user_name = reader.read_str("user_name")?;
+
+    // Extract user_salt_list
+    let salt_count = reader.read_count(16, "user_salt_list")?;
+    let mut user_salt_list = Vec::with_capacity(salt_count);
+    for _ in 0..salt_count {
+        let mut raw = [0u8; 16];
+        raw.copy_from_slice(reader.read_exact(16, "user_salt_list")?);
+        user_salt_list.push(u128::from_be_bytes(raw));
+    }
+
+    // Extract ipv4_addresses
+    let ipv4_addresses = if reader.read_option_tag("ipv4_addresses")? {
+        let count = reader.read_count(4, "ipv4_addresses")?;
+        let mut addresses = Vec::with_capacity(count);
+        for _ in 0..count {
+            let mut raw = [0u8; 4];
+            raw.copy_from_slice(reader.read_exact(4, "ipv4_addresses")?);
+            addresses.push(Ipv4Addr::from(raw));
+        }
+        Some(addresses)
+    } else {
+        None
+    };
+
+    // Extract ipv6_addresses
+    let ipv6_addresses = if reader.read_option_tag("ipv6_addresses")? {
+        let count = reader.read_count(16, "ipv6_addresses")?;
+        let mut addresses = Vec::with_capacity(count);
+        for _ in 0..count {
+            let mut raw = [0u8; 16];
+            raw.copy_from_slice(reader.read_exact(16, "ipv6_addresses")?);
+            addresses.push(Ipv6Addr::from(raw));
+        }
+        Some(addresses)
+    } else {
+        None
+    };
+
+    // Extract gpg_key_public
+    let gpg_key_public = reader.read_str("gpg_key_public")?;
+
+    // Extract sync_interval
+    let sync_interval = reader.read_varint("sync_interval")?;
+
+    // Extract updated_at_timestamp
+    let updated_at_timestamp = reader.read_varint("updated_at_timestamp")?;
+
+    if reader.remaining() != 0 {
+        return Err(ThisProjectError::BinaryDecodeError(format!(
+            "{} trailing bytes after last field (offset {})",
+            reader.remaining(), reader.pos
+        )));
+    }
+
+    Ok(CollaboratorTomlData {
+        user_name,
+        user_salt_list,
+        ipv4_addresses,
+        ipv6_addresses,
+        gpg_key_public,
+        sync_interval,
+        updated_at_timestamp,
+    })
+}
+
+// Cursor over untrusted input; every read is bounds-checked
+struct BinaryReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BinaryReader<'a> {
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+
+    fn read_exact(&mut self, len: usize, field: &str) -> Result<&'a [u8], ThisProjectError> {
+        if len > self.remaining() {
+            return Err(ThisProjectError::BinaryDecodeError(format!(
+                "{}: truncated input, needs {} bytes but only {} remain (offset {})",
+                field, len, self.remaining(), self.pos
+            )));
+        }
+        let slice = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(slice)
+    }
+
+    fn read_u8(&mut self, field: &str) -> Result<u8, ThisProjectError> {
+        Ok(self.read_exact(1, field)?[0])
+    }
+
+    fn read_varint(&mut self, field: &str) -> Result<u64, ThisProjectError> {
+        let start = self.pos;
+        let mut value: u64 = 0;
+        for i in 0..10 {
+            let byte = self.read_u8(field)?;
+            let group = (byte & 0x7f) as u64;
+
+            // The 10th byte may only carry the single remaining bit of a u64
+            if i == 9 && group > 1 {
+                return Err(ThisProjectError::BinaryDecodeError(format!(
+                    "{}: varint overflows u64 (offset {})", field, start
+                )));
+            }
+            value |= group << (7 * i);
+
+            if byte & 0x80 == 0 {
+                // A last byte of zero (after the first) means the encoding was padded
+                if i > 0 && byte == 0 {
+                    return Err(ThisProjectError::BinaryDecodeError(format!(
+                        "{}: non-canonical varint (offset {})", field, start
+                    )));
+                }
+                return Ok(value);
+            }
+        }
+        Err(ThisProjectError::BinaryDecodeError(format!(
+            "{}: varint longer than 10 bytes (offset {})", field, start
+        )))
+    }
+
+    // Reads a count and checks that count x element_size bytes are actually present
+    fn read_count(&mut self, element_size: usize, field: &str) -> Result<usize, ThisProjectError> {
+        let count = self.read_varint(field)?;
+        let needed = count.checked_mul(element_size as u64);
+        match needed {
+            Some(needed) if needed <= self.remaining() as u64 => Ok(count as usize),
+            _ => Err(ThisProjectError::BinaryDecodeError(format!(
+                "{}: count {} needs {} bytes but only {} remain (offset {})",
+                field,
+                count,
+                needed.map_or_else(|| "more than u64::MAX".to_string(), |n| n.to_string()),
+                self.remaining(),
+                self.pos
+            ))),
+        }
+    }
+
+    fn read_str(&mut self, field: &str) -> Result<String, ThisProjectError> {
+        let len = self.read_count(1, field)?;
+        let start = self.pos;
+        let raw = self.read_exact(len, field)?;
+        match std::str::from_utf8(raw) {
+            Ok(s) => Ok(s.to_string()),
+            Err(e) => Err(ThisProjectError::BinaryDecodeError(format!(
+                "{}: invalid UTF-8: {} (offset {})", field, e, start
+            ))),
+        }
+    }
+
+    fn read_option_tag(&mut self, field: &str) -> Result<bool, ThisProjectError> {
+        let start = self.pos;
+        match self.read_u8(field)? {
+            TAG_NONE => Ok(false),
+            TAG_SOME => Ok(true),
+            tag => Err(ThisProjectError::BinaryDecodeError(format!(
+                "{}: invalid option tag {} (offset {})", field, tag, start
+            ))),
+        }
+    }
+}
+
+fn main() {
+    // Example CollaboratorTomlData instance
+    let collaborator = CollaboratorTomlData {
+        user_name: "Bob".to_string(),
+        user_salt_list: vec![0x123456789abcdef0, 0xabcdef0123456789],
+        ipv4_addresses: Some(vec![Ipv4Addr::new(192, 168, 1, 1), Ipv4Addr::new(10, 0, 0, 1)]),
+        ipv6_addresses: Some(vec![Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)]),
+        gpg_key_public: "-----BEGIN PGP PUBLIC KEY BLOCK----- ...".to_string(),
+        sync_interval: 300,
+        updated_at_timestamp: 1728308000,
+    };
+
+    // Encode the collaborator data
+    let bytes = encode_collaborator_to_bytes(&collaborator);
+    println!("Encoded {} bytes:", bytes.len());
+    let hex: Vec<String> = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    println!("{}", hex.join(" "));
+
+    // Decode it again
+    match decode_collaborator_from_bytes(&bytes) {
+        Ok(decoded) => {
+            println!("Decoded:\n{:#?}", decoded);
+            println!("Round trip lossless: {}", decoded == collaborator);
+        }
+        Err(e) => println!("Error decoding: {}", e),
+    }
+
+    // Truncated input must be rejected, never panic
+    let truncated = &bytes[..bytes.len() - 3];
+    match decode_collaborator_from_bytes(truncated) {
+        Ok(_) => println!("Unexpected: truncated input was accepted"),
+        Err(e) => println!("Truncated input rejected: {}", e),
+    }
+
+    // A forged salt count must be rejected before allocating
+    let mut forged = bytes[..9].to_vec();
+    encode_varint(&mut forged, u64::MAX);
+    match decode_collaborator_from_bytes(&forged) {
+        Ok(_) => println!("Unexpected: forged input was accepted"),
+        Err(e) => println!("Forged input rejected: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bob() -> CollaboratorTomlData {
+        CollaboratorTomlData {
+            user_name: "Bob".to_string(),
+            user_salt_list: vec![1, 2],
+            ipv4_addresses: Some(vec![Ipv4Addr::new(10, 0, 0, 1)]),
+            ipv6_addresses: None,
+            gpg_key_public: "key".to_string(),
+            sync_interval: 300,
+            updated_at_timestamp: 1728308000,
+        }
+    }
+
+    fn decode_error(bytes: &[u8]) -> String {
+        match decode_collaborator_from_bytes(bytes) {
+            Ok(decoded) => panic!("accepted {:?}", decoded),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    // Offset of the user_salt_list count in `bob()`: magic, version, "Bob" with its length
+    const SALT_COUNT_OFFSET: usize = 9;
+
+    #[test]
+    fn round_trip_is_lossless() {
+        let bytes = encode_collaborator_to_bytes(&bob());
+        assert_eq!(decode_collaborator_from_bytes(&bytes).unwrap(), bob());
+    }
+
+    #[test]
+    fn every_truncation_is_rejected() {
+        let bytes = encode_collaborator_to_bytes(&bob());
+        for len in 0..bytes.len() {
+            assert!(decode_collaborator_from_bytes(&bytes[..len]).is_err(), "prefix of {} bytes accepted", len);
+        }
+    }
+
+    #[test]
+    fn forged_count_is_rejected_before_reading_the_list() {
+        let mut forged = encode_collaborator_to_bytes(&bob())[..SALT_COUNT_OFFSET].to_vec();
+        encode_varint(&mut forged, 1000);
+        forged.extend_from_slice(&[0u8; 12]);
+        assert_eq!(
+            decode_error(&forged),
+            "Binary Decode Error: user_salt_list: count 1000 needs 16000 bytes but only 12 remain (offset 11)"
+        );
+
+        let mut forged = encode_collaborator_to_bytes(&bob())[..SALT_COUNT_OFFSET].to_vec();
+        encode_varint(&mut forged, u64::MAX);
+        assert!(decode_error(&forged).contains("needs more than u64::MAX bytes"));
+    }
+
+    #[test]
+    fn bad_varints_are_rejected() {
+        let bytes = encode_collaborator_to_bytes(&bob());
+        let with_user_name_length = |length: &[u8]| {
+            let mut forged = bytes[..5].to_vec();
+            forged.extend_from_slice(length);
+            forged.extend_from_slice(&bytes[6..]);
+            forged
+        };
+
+        // 3 padded to two bytes
+        assert!(decode_error(&with_user_name_length(&[0x83, 0x00])).contains("user_name: non-canonical varint (offset 5)"));
+        // 11 continuation bytes
+        assert!(decode_error(&with_user_name_length(&[0x80; 11])).contains("user_name: varint longer than 10 bytes (offset 5)"));
+        // 2^64
+        let mut too_big = vec![0x80; 9];
+        too_big.push(0x02);
+        assert!(decode_error(&with_user_name_length(&too_big)).contains("user_name: varint overflows u64 (offset 5)"));
+    }
+
+    #[test]
+    fn bad_header_is_rejected() {
+        let mut bytes = encode_collaborator_to_bytes(&bob());
+        bytes[4] = BINARY_FORMAT_VERSION + 1;
+        assert_eq!(decode_error(&bytes), "Binary Decode Error: Unsupported binary format version 2 (expected 1)");
+
+        bytes[0] = b'X';
+        assert_eq!(decode_error(&bytes), "Binary Decode Error: Not a collaborator binary: bad magic bytes");
+    }
+
+    #[test]
+    fn bad_option_tag_and_trailing_bytes_are_rejected() {
+        let mut bytes = encode_collaborator_to_bytes(&bob());
+        let ipv4_tag_offset = SALT_COUNT_OFFSET + 1 + 2 * 16;
+        bytes[ipv4_tag_offset] = 2;
+        assert!(decode_error(&bytes).contains("ipv4_addresses: invalid option tag 2"));
+
+        let mut bytes = encode_collaborator_to_bytes(&bob());
+        let len = bytes.len();
+        bytes.push(0);
+        assert_eq!(
+            decode_error(&bytes),
+            format!("Binary Decode Error: 1 trailing bytes after last field (offset {})", len)
+        );
+    }
+}
@@ -0,0 +1,559 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use toml::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+        }
+    }
+}
+
+/// Serializes a `CollaboratorTomlData` struct into canonical TOML.
+///
+/// The canonical form is a byte-stable representation: two collaborator files
+/// that hold the same data always give exactly the same bytes, whatever
+/// their spacing, comments, key order, quoting, hex case or line endings.
+/// This is what gets hashed (`canonical_digest`) and signed.
+///
+/// # Canonical Rules
+///
+/// - Keys are always in this order: `user_name`, `user_salt_list`,
+///   `ipv4_addresses`, `ipv6_addresses`, `gpg_key_public`, `sync_interval`,
+///   `updated_at_timestamp`. No other keys are written.
+/// - `ipv4_addresses` and `ipv6_addresses` are left out when `None`.
+/// - Strings are TOML basic strings (`"..."`), escaped by
+///   `escape_toml_basic_string`: `"` `\` and control characters are escaped,
+///   using `\n` `\r` `\t` or `\uXXXX` (upper-case hex); nothing else is.
+/// - Salts are `"0x"` followed by exactly 32 lower-case hex digits
+///   (zero-padded), so `0xA`, `0x0a` and `0x000...00a` all become the same text.
+/// - Integers are plain decimal: no sign, no underscores, no leading zeros.
+/// - IPv4 addresses are dotted decimal without leading zeros (`10.0.0.1`).
+/// - IPv6 addresses use the RFC 5952 text form: lower-case hex, leading
+///   zeros dropped, the longest run of zero groups compressed to `::`
+///   (`fe80:0:0:0:0:0:0:1` and `FE80::0001` both become `fe80::1`).
+/// - Arrays have one item per line, indented by four spaces, each followed
+///   by a comma, with `[` and `]` on their own lines.
+/// - Every line ends with LF (`\n`), there is no trailing whitespace and no
+///   blank line, and the output ends with exactly one LF.
+///
+/// # Canonical Output
+///
+/// ```toml
+/// user_name = "Bob"
+/// user_salt_list = [
+///     "0x0000000000000000123456789abcdef0",
+///     "0x0000000000000000abcdef0123456789",
+/// ]
+/// ipv4_addresses = [
+///     "192.168.1.1",
+///     "10.0.0.1",
+/// ]
+/// ipv6_addresses = [
+///     "fe80::1",
+///     "::1",
+/// ]
+/// gpg_key_public = "-----BEGIN PGP PUBLIC KEY BLOCK----- ..."
+/// sync_interval = 300
+/// updated_at_timestamp = 1728308000
+/// ```
+///
+/// List order is kept as-is: the order of salts and addresses is data.
+fn serialize_collaborator_to_canonical_toml(collaborator: &CollaboratorTomlData) -> String {
+    let mut toml_string = String::new();
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", escape_toml_basic_string(&collaborator.user_name)));
+
+    // Add user_salt_list
+    toml_string.push_str("user_salt_list = [\n");
+    for salt in &collaborator.user_salt_list {
+        toml_string.push_str(&format!("    \"0x{:032x}\",\n", salt));
+    }
+    toml_string.push_str("]\n");
+
+    // Add ipv4_addresses
+    serialize_canonical_ip_addresses(&mut toml_string, "ipv4_addresses", &collaborator.ipv4_addresses);
+
+    // Add ipv6_addresses (Ipv6Addr's Display already follows RFC 5952)
+    serialize_canonical_ip_addresses(&mut toml_string, "ipv6_addresses", &collaborator.ipv6_addresses);
+
+    // Add gpg_key_public
+    toml_string.push_str(&format!("gpg_key_public = \"{}\"\n", escape_toml_basic_string(&collaborator.gpg_key_public)));
+
+    // Add sync_interval
+    toml_string.push_str(&format!("sync_interval = {}\n", collaborator.sync_interval));
+
+    // Add updated_at_timestamp
+    toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp));
+
+    toml_string
+}
+
+// Helper function to serialize IP addresses to canonical TOML array format
+fn serialize_canonical_ip_addresses<T: std::fmt::Display>(
+    toml_string: &mut String,
+    key: &str,
+    addresses: &Option<Vec<T>>
+) {
+    if let Some(addr_vec) = addresses {
+        toml_string.push_str(&format!("{} = [\n", key));
+        for addr in addr_vec {
+            toml_string.push_str(&format!("    \"{}\",\n", addr));
+        }
+        toml_string.push_str("]\n");
+    }
+}
+
+// Helper function to escape a value for a TOML basic string ("...")
+fn escape_toml_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Computes the SHA-256 digest of the canonical TOML form of a collaborator.
+///
+/// Two semantically identical collaborator files always give the same
+/// digest (see `serialize_collaborator_to_canonical_toml`), so the digest
+/// can be signed with the collaborator's key and checked later.
+///
+/// # use with
+/// let collaborator = read_one_collaborator_setup_toml("alice")?;
+/// let digest = canonical_digest(&collaborator);
+/// println!("{}", digest_to_hex(&digest));
+fn canonical_digest(collaborator: &CollaboratorTomlData) -> [u8; 32] {
+    sha256(serialize_collaborator_to_canonical_toml(collaborator).as_bytes())
+}
+
+/// Lower-case hex text of a digest.
+fn digest_to_hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// SHA-256 round constants (FIPS 180-4, section 4.2.2).
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// SHA-256 initial hash value (FIPS 180-4, section 5.3.3).
+const SHA256_H0: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Incremental SHA-256 (FIPS 180-4), implemented here to avoid a crypto dependency.
+///
+/// # use with
+/// let mut hasher = Sha256::new();
+/// hasher.update(b"part one");
+/// hasher.update(b"part two");
+/// let digest: [u8; 32] = hasher.finalize();
+struct Sha256 {
+    state: [u32; 8],
+    buffer: [u8; 64],
+    buffer_len: usize,
+    total_len: u64, // in bytes
+}
+
+impl Sha256 {
+    fn new() -> Sha256 {
+        Sha256 { state: SHA256_H0, buffer: [0; 64], buffer_len: 0, total_len: 0 }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        self.total_len = self.total_len.wrapping_add(data.len() as u64);
+
+        // Fill up a partial block first
+        if self.buffer_len > 0 {
+            let take = (64 - self.buffer_len).min(data.len());
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+            if self.buffer_len < 64 {
+                return;
+            }
+            let block = self.buffer;
+            self.compress(&block);
+            self.buffer_len = 0;
+        }
+
+        // Whole blocks straight from the input
+        while data.len() >= 64 {
+            let mut block = [0u8; 64];
+            block.copy_from_slice(&data[..64]);
+            self.compress(&block);
+            data = &data[64..];
+        }
+
+        // Keep the rest for later
+        self.buffer[..data.len()].copy_from_slice(data);
+        self.buffer_len = data.len();
+    }
+
+    fn finalize(mut self) -> [u8; 32] {
+        let bit_len = self.total_len.wrapping_mul(8);
+
+        // Padding: 0x80, zeros, then the message length in bits (big-endian u64)
+        let mut padding = vec![0x80u8];
+        let pad_zeros = (64 + 56 - (self.buffer_len + 1) % 64) % 64;
+        padding.resize(1 + pad_zeros, 0);
+        padding.extend_from_slice(&bit_len.to_be_bytes());
+        let total_len = self.total_len;
+        self.update(&padding);
+        self.total_len = total_len;
+
+        let mut digest = [0u8; 32];
+        for (i, word) in self.state.iter().enumerate() {
+            digest[i * 4..i * 4 + 4].copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+
+    fn compress(&mut self, block: &[u8; 64]) {
+        let mut w = [0u32; 64];
+        for i in 0..16 {
+            w[i] = u32::from_be_bytes([block[i * 4], block[i * 4 + 1], block[i * 4 + 2], block[i * 4 + 3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let temp1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(SHA256_K[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(maj);
+
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp1.wrapping_add(temp2);
+        }
+
+        for (word, value) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *word = word.wrapping_add(value);
+        }
+    }
+}
+
+/// SHA-256 of a byte slice in one call.
+fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+/// Parses one collaborator from TOML text and returns its canonical TOML.
+///
+/// # use with
+/// let toml_string = fs::read_to_string(file_path)?;
+/// let canonical = canonicalize_collaborator_toml(&toml_string)?;
+fn canonicalize_collaborator_toml(toml_string: &str) -> Result<String, ThisProjectError> {
+    let collaborator = deserialize_collaborator_from_toml(toml_string)?;
+    Ok(serialize_collaborator_to_canonical_toml(&collaborator))
+}
+
+/// Reads one collaborator from TOML text.
+///
+/// Same extraction rules as `read_one_collaborator_setup_toml` in
+/// `deserialize_one_file_main.rs`.
+fn deserialize_collaborator_from_toml(toml_string: &str) -> Result<CollaboratorTomlData, ThisProjectError> {
+    let toml_value = match toml::from_str::<Value>(toml_string) {
+        Ok(value) => value,
+        Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(e.to_string())),
+    };
+
+    if let Value::Table(table) = toml_value {
+
+        // Extract user_name
+        let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+            s.clone()
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+        };
+
+        // Extract user_salt_list
+        let user_salt_list = if let Some(Value::Array(arr)) = table.get("user_salt_list") {
+            arr.iter()
+                .map(|val| {
+                    if let Value::String(s) = val {
+                        u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                            .map_err(ThisProjectError::ParseIntError)
+                    } else {
+                        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                    }
+                })
+                .collect::<Result<Vec<u128>, ThisProjectError>>()?
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_salt_list".into()));
+        };
+
+        // Extract ipv4_addresses
+        let ipv4_addresses = extract_ipv4_addresses(&table, "ipv4_addresses")?;
+
+        // Extract ipv6_addresses
+        let ipv6_addresses = extract_ipv6_addresses(&table, "ipv6_addresses")?;
+
+        // Extract gpg_key_public
+        let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+            s.clone()
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+        };
+
+        // Extract sync_interval
+        let sync_interval = extract_u64(&table, "sync_interval")?;
+
+        // Extract updated_at_timestamp
+        let updated_at_timestamp = extract_u64(&table, "updated_at_timestamp")?;
+
+        Ok(CollaboratorTomlData {
+            user_name,
+            user_salt_list,
+            ipv4_addresses,
+            ipv6_addresses,
+            gpg_key_public,
+            sync_interval,
+            updated_at_timestamp,
+        })
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure: Expected a table".into()))
+    }
+}
+
+fn extract_ipv4_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv4Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv4Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+fn extract_ipv6_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv6Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv6Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+fn main() {
+    // Two files with the same data, written differently
+    let hand_written = "# Bob, edited by hand\r\n\
+        sync_interval = 300\r\n\
+        user_name = 'Bob'\r\n\
+        user_salt_list = [\"0x123456789ABCDEF0\", \"0x0000abcdef0123456789\"]\r\n\
+        ipv6_addresses = [\"FE80:0:0:0:0:0:0:0001\", \"0:0:0:0:0:0:0:1\"]\r\n\
+        ipv4_addresses = [\"192.168.1.1\", \"10.0.0.1\"]   \r\n\
+        gpg_key_public = \"-----BEGIN PGP PUBLIC KEY BLOCK----- ...\"\r\n\
+        updated_at_timestamp = 1_728_308_000\r\n";
+    let generated = "user_name = \"Bob\"\n\
+        user_salt_list = [\n    \"0x123456789abcdef0\",\n    \"0xabcdef0123456789\",\n]\n\
+        ipv4_addresses = [\n    \"192.168.1.1\",\n    \"10.0.0.1\",\n]\n\
+        ipv6_addresses = [\n    \"fe80::1\",\n    \"::1\",\n]\n\
+        gpg_key_public = \"-----BEGIN PGP PUBLIC KEY BLOCK----- ...\"\n\
+        sync_interval = 300\n\
+        updated_at_timestamp = 1728308000\n";
+
+    let mut digests = Vec::new();
+    for toml_string in [hand_written, generated] {
+        match deserialize_collaborator_from_toml(toml_string) {
+            Ok(collaborator) => {
+                println!("Canonical TOML:\n{}", serialize_collaborator_to_canonical_toml(&collaborator));
+                digests.push(digest_to_hex(&canonical_digest(&collaborator)));
+            }
+            Err(e) => println!("Error reading collaborator data: {}", e),
+        }
+    }
+    for digest in &digests {
+        println!("SHA-256: {}", digest);
+    }
+    if digests.len() == 2 {
+        println!("Same canonical digest: {}", digests[0] == digests[1]);
+    }
+
+    // Digest of every file in the address book
+    let dir_path = Path::new("project_graph_data/collaborator_files_address_book");
+    if let Ok(entries) = fs::read_dir(dir_path) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("toml") {
+                continue;
+            }
+            match fs::read_to_string(&path).map_err(ThisProjectError::from).and_then(|s| canonicalize_collaborator_toml(&s)) {
+                Ok(canonical) => println!("{}  {}", digest_to_hex(&sha256(canonical.as_bytes())), path.display()),
+                Err(e) => println!("Error reading {}: {}", path.display(), e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Known-answer vectors from FIPS 180-4 / the NIST SHA examples
+
+    #[test]
+    fn sha256_empty_input() {
+        assert_eq!(
+            digest_to_hex(&sha256(b"")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn sha256_one_block() {
+        assert_eq!(
+            digest_to_hex(&sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn sha256_two_blocks() {
+        assert_eq!(
+            digest_to_hex(&sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+    }
+
+    #[test]
+    fn sha256_one_million_a_in_uneven_updates() {
+        // Uneven chunks exercise the partial-block buffering in `update`
+        let mut hasher = Sha256::new();
+        let chunk = [b'a'; 997];
+        let mut remaining = 1_000_000;
+        while remaining > 0 {
+            let take = remaining.min(chunk.len());
+            hasher.update(&chunk[..take]);
+            remaining -= take;
+        }
+        assert_eq!(
+            digest_to_hex(&hasher.finalize()),
+            "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+        );
+    }
+
+    #[test]
+    fn sha256_padding_boundaries() {
+        // 55, 56 and 64 bytes are the lengths where the padding spills into
+        // an extra block; compare the streaming path against one-shot hashing
+        for len in [55usize, 56, 63, 64, 65] {
+            let data = vec![0x5au8; len];
+            let mut hasher = Sha256::new();
+            for byte in &data {
+                hasher.update(std::slice::from_ref(byte));
+            }
+            assert_eq!(hasher.finalize(), sha256(&data), "length {}", len);
+        }
+    }
+}
@@ -0,0 +1,769 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::ffi::OsStr;
+use toml::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    CsvError(String),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::CsvError(err) => write!(f, "CSV Error: {}", err),
+        }
+    }
+}
+
+/// An error for one CSV row during import.
+///
+/// `row` is the 1-based data row (the header is not counted) and `line` is
+/// the line in the CSV file where that row starts, which differs from
+/// `row + 1` when a quoted cell (such as `gpg_key_public`) spans several lines.
+#[derive(Debug)]
+struct CsvRowError {
+    row: usize,
+    line: usize,
+    error: ThisProjectError,
+}
+
+impl fmt::Display for CsvRowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Row {} (line {}): {}", self.row, self.line, self.error)
+    }
+}
+
+/// Settings for CSV export and import.
+///
+/// - `list_separator`: joins the items of list fields (`user_salt_list`,
+///   `ipv4_addresses`, `ipv6_addresses`) inside one cell. Default `';'`.
+///   Must not be a character that can appear in a salt or IP address
+///   (letters, digits, `:` or `.`), or a `"`.
+#[derive(Debug, Clone)]
+struct CsvOptions {
+    list_separator: char,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions { list_separator: ';' }
+    }
+}
+
+/// CSV column order, which is also the field order of `serialize_collaborator_to_toml`.
+const CSV_HEADER: [&str; 7] = [
+    "user_name",
+    "user_salt_list",
+    "ipv4_addresses",
+    "ipv6_addresses",
+    "gpg_key_public",
+    "sync_interval",
+    "updated_at_timestamp",
+];
+
+/// Toml Deserialization: Reads collaborator setup data from TOML files in a specified directory.
+///
+/// Same loader as in `deserialization_from_toml_file_main.rs`; see that file
+/// for the full description of fields, helpers and error handling.
+fn read_a_collaborator_setup_toml() -> Result<(Vec<CollaboratorTomlData>, Vec<ThisProjectError>), ThisProjectError> {
+    let mut collaborators = Vec::new();
+    let mut errors = Vec::new();
+    let dir_path = Path::new("project_graph_data/collaborator_files_address_book");
+
+    for entry in fs::read_dir(dir_path)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
+            let toml_string = fs::read_to_string(&path)?;
+
+            match toml::from_str::<Value>(&toml_string) {
+                Ok(toml_value) => {
+                    if let Value::Table(table) = toml_value {
+                        // Extract user_name
+                        let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+                            s.clone()
+                        } else {
+                            errors.push(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+                            continue;
+                        };
+
+                        // Extract user_salt_list
+                        let user_salt_list = if let Some(Value::Array(arr)) = table.get("user_salt_list") {
+                            arr.iter()
+                                .map(|val| {
+                                    if let Value::String(s) = val {
+                                        u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                                            .map_err(ThisProjectError::ParseIntError)
+                                    } else {
+                                        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                                    }
+                                })
+                                .collect::<Result<Vec<u128>, ThisProjectError>>()?
+                        } else {
+                            errors.push(ThisProjectError::TomlVanillaDeserialStrError("Missing user_salt_list".into()));
+                            continue;
+                        };
+
+                        // Extract ipv4_addresses
+                        let ipv4_addresses = extract_ipv4_addresses(&table, "ipv4_addresses", &mut errors)?;
+
+                        // Extract ipv6_addresses
+                        let ipv6_addresses = extract_ipv6_addresses(&table, "ipv6_addresses", &mut errors)?;
+
+                        // Extract gpg_key_public
+                        let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+                            s.clone()
+                        } else {
+                            errors.push(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+                            continue;
+                        };
+
+                        // Extract sync_interval
+                        let sync_interval = extract_u64(&table, "sync_interval", &mut errors)?;
+
+                        // Extract updated_at_timestamp
+                        let updated_at_timestamp = extract_u64(&table, "updated_at_timestamp", &mut errors)?;
+
+                        // Create CollaboratorTomlData instance
+                        collaborators.push(CollaboratorTomlData {
+                            user_name,
+                            user_salt_list,
+                            ipv4_addresses,
+                            ipv6_addresses,
+                            gpg_key_public,
+                            sync_interval,
+                            updated_at_timestamp,
+                        });
+                    } else {
+                        errors.push(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure".into()));
+                    }
+                }
+                Err(e) => {
+                    errors.push(ThisProjectError::TomlVanillaDeserialStrError(e.to_string()));
+                }
+            }
+        }
+    }
+
+    Ok((collaborators, errors))
+}
+
+// Helper function to extract and parse IPv4 addresses from a toml::Value::Table
+fn extract_ipv4_addresses(
+    table: &toml::map::Map<String, Value>,
+    key: &str,
+    errors: &mut Vec<ThisProjectError>
+) -> Result<Option<Vec<Ipv4Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new(); // Create an empty vector to store addresses
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv4Addr>() {
+                    Ok(ip) => addresses.push(ip), // Push successful IP address
+                    Err(e) => errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() { // If no valid addresses were found
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None) // Return None if the key is not present
+    }
+}
+
+// Helper function to extract and parse IPv6 addresses from a toml::Value::Table
+fn extract_ipv6_addresses(table: &toml::map::Map<String, Value>, key: &str, errors: &mut Vec<ThisProjectError>) -> Result<Option<Vec<Ipv6Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new(); // Create an empty vector to store addresses
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv6Addr>() {
+                    Ok(ip) => addresses.push(ip), // Push successful IP address
+                    Err(e) => errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() { // If no valid addresses were found
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None) // Return None if the key is not present
+    }
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str, errors: &mut Vec<ThisProjectError>) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)));
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)));
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+/// Serializes a `CollaboratorTomlData` struct into a TOML-formatted string.
+///
+/// Same output as `serialize_collaborator_to_toml` in `serialize_to_toml_main.rs`,
+/// except that `user_name` and `gpg_key_public` are escaped: cells imported
+/// from a spreadsheet may contain quotes or line breaks.
+fn serialize_collaborator_to_toml(collaborator: &CollaboratorTomlData) -> Result<String, ThisProjectError> {
+    let mut toml_string = String::new();
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", escape_toml_basic_string(&collaborator.user_name)));
+
+    // Add user_salt_list
+    toml_string.push_str("user_salt_list = [\n");
+    for salt in &collaborator.user_salt_list {
+        toml_string.push_str(&format!("    \"0x{:x}\",\n", salt));
+    }
+    toml_string.push_str("]\n");
+
+    // Add ipv4_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv4_addresses", &collaborator.ipv4_addresses)?;
+
+    // Add ipv6_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv6_addresses", &collaborator.ipv6_addresses)?;
+
+    // Add gpg_key_public
+    toml_string.push_str(&format!("gpg_key_public = \"{}\"\n", escape_toml_basic_string(&collaborator.gpg_key_public)));
+
+    // Add sync_interval
+    toml_string.push_str(&format!("sync_interval = {}\n", collaborator.sync_interval));
+
+    // Add updated_at_timestamp
+    toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp));
+
+    Ok(toml_string)
+}
+
+// Helper function to escape a value for a TOML basic string ("...")
+fn escape_toml_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Helper function to serialize IP addresses to TOML array format
+fn serialize_ip_addresses<T: std::fmt::Display>(
+    toml_string: &mut String,
+    key: &str,
+    addresses: &Option<Vec<T>>
+) -> Result<(), ThisProjectError> {
+    if let Some(addr_vec) = addresses {
+        toml_string.push_str(&format!("{} = [\n", key));
+        for addr in addr_vec {
+            toml_string.push_str(&format!("    \"{}\",\n", addr));
+        }
+        toml_string.push_str("]\n");
+    }
+    Ok(()) // Return Ok(()) if the addresses field is None
+}
+
+// Function to write a TOML string to a file
+fn write_toml_to_file(file_path: &str, toml_string: &str) -> Result<(), ThisProjectError> {
+    // Attempt to create the file.
+    let mut file = match File::create(file_path) {
+        Ok(file) => file,
+        Err(e) => return Err(ThisProjectError::IoError(e)),
+    };
+
+    // Attempt to write to the file.
+    if let Err(e) = file.write_all(toml_string.as_bytes()) {
+        return Err(ThisProjectError::IoError(e));
+    }
+
+    // Everything successful!
+    Ok(())
+}
+
+// Helper function to reject list separators that could appear inside a list item
+fn check_list_separator(options: &CsvOptions) -> Result<(), ThisProjectError> {
+    let separator = options.list_separator;
+    if separator.is_alphanumeric() || separator == ':' || separator == '.' || separator == '"' {
+        return Err(ThisProjectError::CsvError(format!(
+            "Invalid list separator '{}': it can appear inside salts or IP addresses",
+            separator
+        )));
+    }
+    Ok(())
+}
+
+/// Exports collaborators to RFC 4180 CSV, one row per collaborator.
+///
+/// The first row is the header (`CSV_HEADER`). Each following row holds one
+/// collaborator, with the same cell formats as the TOML files:
+///
+/// - `user_salt_list`: `0x`-prefixed hex salts joined with `options.list_separator`
+/// - `ipv4_addresses` / `ipv6_addresses`: addresses joined with
+///   `options.list_separator`; an empty cell means `None`
+/// - `sync_interval` / `updated_at_timestamp`: decimal integers
+///
+/// # RFC 4180
+///
+/// - Rows end with CRLF.
+/// - A cell containing `,`, `"`, CR or LF is quoted, and `"` inside it is
+///   doubled. This matters mostly for `gpg_key_public`, since an armored
+///   key is multi-line text.
+///
+/// # Example Output
+///
+/// ```text
+/// user_name,user_salt_list,ipv4_addresses,ipv6_addresses,gpg_key_public,sync_interval,updated_at_timestamp
+/// alice,0x11111111111111111111111111111111;0x11111111111111111111111111111112,192.168.1.1;10.0.0.1,fe80::1;::1,-----BEGIN PGP PUBLIC KEY BLOCK----- ...,60,1728307160
+/// ```
+///
+/// # use with
+/// let (collaborators, errors) = read_a_collaborator_setup_toml()?;
+/// let csv_string = export_collaborators_to_csv(&collaborators, &CsvOptions::default())?;
+fn export_collaborators_to_csv(collaborators: &[CollaboratorTomlData], options: &CsvOptions) -> Result<String, ThisProjectError> {
+    check_list_separator(options)?;
+    let separator = options.list_separator.to_string();
+
+    let mut csv_string = String::new();
+    let header: Vec<String> = CSV_HEADER.iter().map(|name| name.to_string()).collect();
+    push_csv_row(&mut csv_string, &header);
+
+    for collaborator in collaborators {
+        let salts: Vec<String> = collaborator.user_salt_list.iter()
+            .map(|salt| format!("0x{:x}", salt))
+            .collect();
+        let ipv4_addresses: Vec<String> = collaborator.ipv4_addresses.iter()
+            .flatten()
+            .map(|ip| ip.to_string())
+            .collect();
+        let ipv6_addresses: Vec<String> = collaborator.ipv6_addresses.iter()
+            .flatten()
+            .map(|ip| ip.to_string())
+            .collect();
+
+        let row = vec![
+            collaborator.user_name.clone(),
+            salts.join(&separator),
+            ipv4_addresses.join(&separator),
+            ipv6_addresses.join(&separator),
+            collaborator.gpg_key_public.clone(),
+            collaborator.sync_interval.to_string(),
+            collaborator.updated_at_timestamp.to_string(),
+        ];
+        push_csv_row(&mut csv_string, &row);
+    }
+
+    Ok(csv_string)
+}
+
+// Helper function to append one CSV row, quoting cells as needed
+fn push_csv_row(csv_string: &mut String, cells: &[String]) {
+    for (i, cell) in cells.iter().enumerate() {
+        if i > 0 {
+            csv_string.push(',');
+        }
+        if cell.contains([',', '"', '\r', '\n']) {
+            csv_string.push('"');
+            csv_string.push_str(&cell.replace('"', "\"\""));
+            csv_string.push('"');
+        } else {
+            csv_string.push_str(cell);
+        }
+    }
+    csv_string.push_str("\r\n");
+}
+
+/// Splits RFC 4180 CSV text into records of cells.
+///
+/// Accepts CRLF or bare LF line endings, quoted cells with doubled quotes and
+/// embedded line breaks, and an optional final line ending. Returns each
+/// record together with the 1-based line number where it starts.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::CsvError` for an unterminated quoted cell or
+/// for text directly after a closing quote (e.g. `"abc"def`).
+fn parse_csv_records(csv_string: &str) -> Result<Vec<(usize, Vec<String>)>, ThisProjectError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut cell = String::new();
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = csv_string.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if cell.is_empty() => {
+                // Quoted cell: read until the closing quote
+                let quote_line = line;
+                loop {
+                    match chars.next() {
+                        Some('"') => {
+                            if chars.peek() == Some(&'"') {
+                                chars.next();
+                                cell.push('"');
+                            } else {
+                                break;
+                            }
+                        }
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            cell.push(c);
+                        }
+                        None => {
+                            return Err(ThisProjectError::CsvError(format!(
+                                "Unterminated quoted cell starting on line {}",
+                                quote_line
+                            )));
+                        }
+                    }
+                }
+                match chars.peek() {
+                    Some(',') | Some('\r') | Some('\n') | None => {}
+                    Some(_) => {
+                        return Err(ThisProjectError::CsvError(format!(
+                            "Unexpected text after closing quote on line {}",
+                            line
+                        )));
+                    }
+                }
+            }
+            ',' => record.push(std::mem::take(&mut cell)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(std::mem::take(&mut cell));
+                records.push((record_line, std::mem::take(&mut record)));
+                line += 1;
+                record_line = line;
+            }
+            c => cell.push(c),
+        }
+    }
+
+    // Last record without a final line ending
+    if !cell.is_empty() || !record.is_empty() {
+        record.push(cell);
+        records.push((record_line, record));
+    }
+
+    Ok(records)
+}
+
+/// Imports collaborators from CSV into individual `{user_name}__collaborator.toml` files.
+///
+/// The CSV must start with a header row naming the `CSV_HEADER` columns;
+/// the columns may be in any order (a spreadsheet user may have moved them),
+/// but all seven must be present. Every following row is parsed, serialized
+/// with `serialize_collaborator_to_toml`, and written with `write_toml_to_file`
+/// into `output_dir`. Existing files with the same name are overwritten,
+/// but two rows with the same `user_name` are not: the first row is
+/// imported and each later one is reported as a row error.
+///
+/// # Error Handling
+///
+/// - Problems with the CSV as a whole (invalid list separator, unterminated
+///   quote, missing header columns, unreadable output directory) return `Err`.
+/// - Problems with a single row (wrong cell count, bad salt hex, bad IP
+///   address, bad integer, empty, unsafe or duplicate `user_name`, write
+///   failure) are collected as `CsvRowError`s and the import continues
+///   with the next row.
+///
+/// # Returns
+///
+/// Returns a `Result` containing:
+/// - `Ok`: A tuple with:
+///     - The paths of the TOML files written.
+///     - A vector of `CsvRowError` for the rows that were skipped.
+/// - `Err`: A `ThisProjectError` if the CSV could not be imported at all.
+///
+/// # use with
+/// let csv_string = fs::read_to_string("collaborators.csv")?;
+/// let (written, row_errors) = import_collaborators_from_csv(
+///     &csv_string,
+///     &CsvOptions::default(),
+///     Path::new("project_graph_data/collaborator_files_address_book"),
+/// )?;
+/// for err in row_errors {
+///     println!("{}", err);
+/// }
+fn import_collaborators_from_csv(
+    csv_string: &str,
+    options: &CsvOptions,
+    output_dir: &Path,
+) -> Result<(Vec<String>, Vec<CsvRowError>), ThisProjectError> {
+    check_list_separator(options)?;
+
+    let mut records = parse_csv_records(csv_string)?.into_iter();
+    let mut written = Vec::new();
+    let mut row_errors = Vec::new();
+    let mut rows_by_user_name: HashMap<String, usize> = HashMap::new();
+
+    // Map each expected column to its position in the header row
+    let header = match records.next() {
+        Some((_, header)) => header,
+        None => return Err(ThisProjectError::CsvError("Empty CSV: missing header row".into())),
+    };
+    let mut column_positions = [0usize; CSV_HEADER.len()];
+    for (i, name) in CSV_HEADER.iter().enumerate() {
+        match header.iter().position(|cell| cell.trim() == *name) {
+            Some(position) => column_positions[i] = position,
+            None => return Err(ThisProjectError::CsvError(format!("Missing column in header: {}", name))),
+        }
+    }
+
+    for (row, (line, record)) in records.enumerate() {
+        let row = row + 1;
+
+        // Skip blank lines (e.g. at the end of a file saved by a spreadsheet)
+        if record.len() == 1 && record[0].trim().is_empty() {
+            continue;
+        }
+
+        if record.len() != header.len() {
+            row_errors.push(CsvRowError {
+                row,
+                line,
+                error: ThisProjectError::CsvError(format!(
+                    "Expected {} cells, found {}",
+                    header.len(),
+                    record.len()
+                )),
+            });
+            continue;
+        }
+
+        let cells: Vec<&str> = column_positions.iter().map(|position| record[*position].as_str()).collect();
+        let result = collaborator_from_csv_cells(&cells, options).and_then(|collaborator| {
+            if let Some(first_row) = rows_by_user_name.get(&collaborator.user_name) {
+                return Err(ThisProjectError::CsvError(format!(
+                    "Duplicate user_name \"{}\" (first used in row {})",
+                    collaborator.user_name, first_row
+                )));
+            }
+            rows_by_user_name.insert(collaborator.user_name.clone(), row);
+
+            let file_path = output_dir.join(format!("{}__collaborator.toml", collaborator.user_name));
+            let toml_string = serialize_collaborator_to_toml(&collaborator)?;
+            write_toml_to_file(&file_path.to_string_lossy(), &toml_string)?;
+            Ok(file_path.to_string_lossy().into_owned())
+        });
+
+        match result {
+            Ok(path) => written.push(path),
+            Err(error) => row_errors.push(CsvRowError { row, line, error }),
+        }
+    }
+
+    Ok((written, row_errors))
+}
+
+// Helper function to build one collaborator from cells in CSV_HEADER order
+fn collaborator_from_csv_cells(cells: &[&str], options: &CsvOptions) -> Result<CollaboratorTomlData, ThisProjectError> {
+    // Extract user_name (also used as a file name, so no path tricks)
+    let user_name = cells[0].trim().to_string();
+    if user_name.is_empty() {
+        return Err(ThisProjectError::CsvError("Missing user_name".into()));
+    }
+    if user_name.contains(['/', '\\']) || user_name == "." || user_name == ".." {
+        return Err(ThisProjectError::CsvError(format!("Invalid user_name for a file name: {}", user_name)));
+    }
+
+    // Extract user_salt_list
+    let user_salt_list = split_list(cells[1], options)
+        .into_iter()
+        .map(|s| {
+            u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                .map_err(|e| ThisProjectError::CsvError(format!("Invalid user_salt_list item \"{}\": {}", s, e)))
+        })
+        .collect::<Result<Vec<u128>, ThisProjectError>>()?;
+
+    // Extract ipv4_addresses
+    let ipv4_addresses = parse_csv_ip_list::<Ipv4Addr>(cells[2], "ipv4_addresses", options)?;
+
+    // Extract ipv6_addresses
+    let ipv6_addresses = parse_csv_ip_list::<Ipv6Addr>(cells[3], "ipv6_addresses", options)?;
+
+    // Extract gpg_key_public (kept as-is, including line breaks)
+    let gpg_key_public = cells[4].to_string();
+
+    // Extract sync_interval
+    let sync_interval = parse_csv_u64(cells[5], "sync_interval")?;
+
+    // Extract updated_at_timestamp
+    let updated_at_timestamp = parse_csv_u64(cells[6], "updated_at_timestamp")?;
+
+    Ok(CollaboratorTomlData {
+        user_name,
+        user_salt_list,
+        ipv4_addresses,
+        ipv6_addresses,
+        gpg_key_public,
+        sync_interval,
+        updated_at_timestamp,
+    })
+}
+
+// Helper function to split a list cell; an empty cell is an empty list
+fn split_list<'a>(cell: &'a str, options: &CsvOptions) -> Vec<&'a str> {
+    cell.split(options.list_separator)
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+// Helper function to parse an IP address list cell (empty cell -> None)
+fn parse_csv_ip_list<T>(cell: &str, key: &str, options: &CsvOptions) -> Result<Option<Vec<T>>, ThisProjectError>
+where
+    T: std::str::FromStr,
+    T::Err: fmt::Display,
+{
+    let mut addresses = Vec::new();
+    for item in split_list(cell, options) {
+        match item.parse::<T>() {
+            Ok(ip) => addresses.push(ip),
+            Err(e) => return Err(ThisProjectError::CsvError(format!("Invalid {} item \"{}\": {}", key, item, e))),
+        }
+    }
+
+    if addresses.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(addresses))
+    }
+}
+
+// Helper function to parse an integer cell, with the same range as extract_u64
+fn parse_csv_u64(cell: &str, key: &str) -> Result<u64, ThisProjectError> {
+    match cell.trim().parse::<u64>() {
+        Ok(i) if i <= i64::MAX as u64 => Ok(i),
+        Ok(_) => Err(ThisProjectError::CsvError(format!("Invalid {}: Out of range for u64", key))),
+        Err(e) => Err(ThisProjectError::CsvError(format!("Invalid {}: {}", key, e))),
+    }
+}
+
+fn main() {
+    let csv_path = "collaborators_export.csv";
+    let options = CsvOptions::default();
+
+    // Export the address book to CSV
+    match read_a_collaborator_setup_toml() {
+        Ok((collaborators, errors)) => {
+            if !errors.is_empty() {
+                println!("Errors encountered:");
+                for err in errors {
+                    println!("{}", err);
+                }
+            }
+
+            match export_collaborators_to_csv(&collaborators, &options) {
+                Ok(csv_string) => {
+                    println!("Exported CSV:\n{}", csv_string);
+                    if let Err(e) = fs::write(csv_path, &csv_string) {
+                        println!("Error writing {}: {}", csv_path, e);
+                        return;
+                    }
+                }
+                Err(e) => {
+                    println!("Error exporting CSV: {}", e);
+                    return;
+                }
+            }
+        }
+        Err(e) => {
+            println!("Error reading TOML files: {}", e);
+            return;
+        }
+    }
+
+    // Import the CSV back into individual TOML files (in a separate directory)
+    let output_dir = Path::new("project_graph_data/collaborator_files_csv_import");
+    if let Err(e) = fs::create_dir_all(output_dir) {
+        println!("Error creating {}: {}", output_dir.display(), e);
+        return;
+    }
+
+    let csv_string = match fs::read_to_string(csv_path) {
+        Ok(csv_string) => csv_string,
+        Err(e) => {
+            println!("Error reading {}: {}", csv_path, e);
+            return;
+        }
+    };
+
+    match import_collaborators_from_csv(&csv_string, &options, output_dir) {
+        Ok((written, row_errors)) => {
+            for path in written {
+                println!("Wrote {}", path);
+            }
+            if !row_errors.is_empty() {
+                println!("Rows skipped:");
+                for err in row_errors {
+                    println!("{}", err);
+                }
+            }
+        }
+        Err(e) => println!("Error importing CSV: {}", e),
+    }
+}
@@ -0,0 +1,1945 @@
+use std::fmt;
+use std::env;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::ffi::OsStr;
+use std::str::FromStr;
+use toml::Value;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ip_addresses: Option<Vec<IpAddr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    DiagnosticExportError(String),
+    ReaderError(CodedError),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::DiagnosticExportError(err) => write!(f, "Diagnostic Export Error: {}", err),
+            ThisProjectError::ReaderError(err) => write!(f, "Reader Error: {}", err),
+        }
+    }
+}
+
+/*
+Diagnostic codes
+
+Every error and warning the readers produce has a stable code, so CI
+output, suppression lists and documentation can refer to it. Codes are
+never reused or renumbered; new findings get new codes at the end.
+`explain("CT0003")` gives the long description and how to fix it.
+*/
+
+/// The kind of a `Diagnostic`; see `explain` for what each one means.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiagnosticCode {
+    TomlSyntax,
+    MissingField,
+    WrongType,
+    InvalidSaltHex,
+    InvalidIpv4Address,
+    InvalidIpv6Address,
+    InvalidIpAddress,
+    U64OutOfRange,
+    UnknownKey,
+    NameMismatch,
+    LegacyKey,
+    ListLength,
+    DisallowedValue,
+    AddressNormalized,
+    DuplicateAddress,
+    FileUnreadable,
+    AddressPolicy,
+    InvalidArgument,
+}
+
+impl DiagnosticCode {
+    const ALL: [DiagnosticCode; 18] = [
+        DiagnosticCode::TomlSyntax,
+        DiagnosticCode::MissingField,
+        DiagnosticCode::WrongType,
+        DiagnosticCode::InvalidSaltHex,
+        DiagnosticCode::InvalidIpv4Address,
+        DiagnosticCode::InvalidIpv6Address,
+        DiagnosticCode::InvalidIpAddress,
+        DiagnosticCode::U64OutOfRange,
+        DiagnosticCode::UnknownKey,
+        DiagnosticCode::NameMismatch,
+        DiagnosticCode::LegacyKey,
+        DiagnosticCode::ListLength,
+        DiagnosticCode::DisallowedValue,
+        DiagnosticCode::AddressNormalized,
+        DiagnosticCode::DuplicateAddress,
+        DiagnosticCode::FileUnreadable,
+        DiagnosticCode::AddressPolicy,
+        DiagnosticCode::InvalidArgument,
+    ];
+
+    /// The stable code, e.g. `CT0003`.
+    fn as_str(self) -> &'static str {
+        match self {
+            DiagnosticCode::TomlSyntax => "CT0001",
+            DiagnosticCode::MissingField => "CT0002",
+            DiagnosticCode::WrongType => "CT0003",
+            DiagnosticCode::InvalidSaltHex => "CT0004",
+            DiagnosticCode::InvalidIpv4Address => "CT0005",
+            DiagnosticCode::InvalidIpv6Address => "CT0006",
+            DiagnosticCode::InvalidIpAddress => "CT0007",
+            DiagnosticCode::U64OutOfRange => "CT0008",
+            DiagnosticCode::UnknownKey => "CT0009",
+            DiagnosticCode::NameMismatch => "CT0010",
+            DiagnosticCode::LegacyKey => "CT0011",
+            DiagnosticCode::ListLength => "CT0012",
+            DiagnosticCode::DisallowedValue => "CT0013",
+            DiagnosticCode::AddressNormalized => "CT0014",
+            DiagnosticCode::DuplicateAddress => "CT0015",
+            DiagnosticCode::FileUnreadable => "CT0016",
+            DiagnosticCode::AddressPolicy => "CT0017",
+            DiagnosticCode::InvalidArgument => "CT0018",
+        }
+    }
+
+    /// A short title, e.g. for SARIF rule descriptions.
+    fn title(self) -> &'static str {
+        match self {
+            DiagnosticCode::TomlSyntax => "file is not valid TOML",
+            DiagnosticCode::MissingField => "required field is missing",
+            DiagnosticCode::WrongType => "value has the wrong type",
+            DiagnosticCode::InvalidSaltHex => "salt is not valid hex",
+            DiagnosticCode::InvalidIpv4Address => "invalid IPv4 address",
+            DiagnosticCode::InvalidIpv6Address => "invalid IPv6 address",
+            DiagnosticCode::InvalidIpAddress => "invalid IP address",
+            DiagnosticCode::U64OutOfRange => "integer out of range for u64",
+            DiagnosticCode::UnknownKey => "unknown key",
+            DiagnosticCode::NameMismatch => "file name does not match user_name",
+            DiagnosticCode::LegacyKey => "legacy address key",
+            DiagnosticCode::ListLength => "list has too few or too many entries",
+            DiagnosticCode::DisallowedValue => "value is not allowed",
+            DiagnosticCode::AddressNormalized => "address was normalized",
+            DiagnosticCode::DuplicateAddress => "duplicate address removed",
+            DiagnosticCode::FileUnreadable => "file could not be read",
+            DiagnosticCode::AddressPolicy => "address flagged by the address policy",
+            DiagnosticCode::InvalidArgument => "invalid command-line argument",
+        }
+    }
+
+    // Helper function: (what it means, how to fix it) for `explain`
+    fn description(self) -> (&'static str, &'static str) {
+        match self {
+            DiagnosticCode::TomlSyntax => (
+                "The file could not be parsed as TOML, or its top level is not a table. \
+                 Nothing in the file was read.",
+                "Correct the syntax at the reported line and column; common causes are \
+                 an unclosed string or array, or a missing `=`.",
+            ),
+            DiagnosticCode::MissingField => (
+                "A field every collaborator file must have is absent: user_name, \
+                 user_salt_list, gpg_key_public, sync_interval or updated_at_timestamp.",
+                "Add the field at the top of the file, before any [table] header.",
+            ),
+            DiagnosticCode::WrongType => (
+                "A field or list element is present but has a different TOML type than \
+                 expected, e.g. a number where a string is expected, or a string where a \
+                 list is expected.",
+                "Write the value with the expected type: strings in double quotes, \
+                 integers without quotes, lists in [ ].",
+            ),
+            DiagnosticCode::InvalidSaltHex => (
+                "An entry of user_salt_list is not a hexadecimal u128. Salts are written \
+                 as \"0x\" followed by up to 32 hex digits.",
+                "Write the salt as a quoted hex string such as \"0x1a2b3c\"; remove any \
+                 other characters.",
+            ),
+            DiagnosticCode::InvalidIpv4Address => (
+                "An entry of ipv4_addresses is not an IP address. The entry is skipped; \
+                 the rest of the file is still read.",
+                "Write the address in dotted form such as \"10.0.0.1\", or remove it. \
+                 IPv6 addresses belong in ipv6_addresses or ip_addresses.",
+            ),
+            DiagnosticCode::InvalidIpv6Address => (
+                "An entry of ipv6_addresses is not an IP address. The entry is skipped; \
+                 the rest of the file is still read.",
+                "Write the address in standard form such as \"fe80::1\", or remove it. \
+                 IPv4 addresses belong in ipv4_addresses or ip_addresses.",
+            ),
+            DiagnosticCode::InvalidIpAddress => (
+                "An entry of ip_addresses is neither an IPv4 nor an IPv6 address. The \
+                 entry is skipped; the rest of the file is still read.",
+                "Write the address as \"10.0.0.1\" or \"fe80::1\", without a port, zone \
+                 or prefix length, or remove it.",
+            ),
+            DiagnosticCode::U64OutOfRange => (
+                "An integer field that must fit in a u64 (sync_interval, \
+                 updated_at_timestamp) is negative.",
+                "Use a value of zero or more; timestamps are seconds since 1970-01-01 UTC.",
+            ),
+            DiagnosticCode::UnknownKey => (
+                "The file has a top-level key the reader does not know. It is ignored, \
+                 and is lost when the file is written back.",
+                "Check the key for typos, or remove it.",
+            ),
+            DiagnosticCode::NameMismatch => (
+                "Collaborator files are named after the lowercased user_name, e.g. \
+                 `alice__collaborator.toml` for user_name \"Alice\", and are looked up by \
+                 that name; case is ignored. This file's name does not match its \
+                 user_name, so a lookup by name finds the wrong file or none.",
+                "Rename the file to match user_name, or correct user_name.",
+            ),
+            DiagnosticCode::LegacyKey => (
+                "A note: ipv4_addresses and ipv6_addresses are merged into \
+                 ip_addresses on reading. Writing the file back keeps them, since \
+                 readers that predate ip_addresses only see these two lists.",
+                "Nothing needs to change. Once every peer reads ip_addresses, the \
+                 entries can be moved there and the old keys deleted.",
+            ),
+            DiagnosticCode::ListLength => (
+                "A list has fewer or more entries than allowed: user_salt_list needs 1 \
+                 to 64 salts, and each address list in the file takes at most 64 \
+                 addresses.",
+                "Add or remove entries to bring the list within the limits.",
+            ),
+            DiagnosticCode::DisallowedValue => (
+                "A list entry is well-formed but not allowed: a salt of zero, or the \
+                 unspecified address (0.0.0.0 or ::) in an address list.",
+                "Replace the entry with a usable value, or remove it.",
+            ),
+            DiagnosticCode::AddressNormalized => (
+                "An IPv4-mapped IPv6 address (::ffff:a.b.c.d) was read as the IPv4 \
+                 address. Nothing was lost.",
+                "Write the plain IPv4 address instead.",
+            ),
+            DiagnosticCode::DuplicateAddress => (
+                "The same address appears more than once across ip_addresses, \
+                 ipv4_addresses and ipv6_addresses; later copies are dropped.",
+                "Remove the duplicate entry.",
+            ),
+            DiagnosticCode::FileUnreadable => (
+                "The file could not be opened or read, or it is not UTF-8 text, e.g. \
+                 an encrypted collaborator file or one saved in another encoding. \
+                 Nothing in the file was read; the other files are still checked.",
+                "Check that the file is readable, decrypt it first if it is encrypted, \
+                 and save it as UTF-8.",
+            ),
+            DiagnosticCode::AddressPolicy => (
+                "An address is of a class (loopback, link-local, private, global or \
+                 special-purpose) that the address policy flags. As a warning the \
+                 address is kept, or dropped if the message says so; as an error the \
+                 policy rejects it and the collaborator is not loaded.",
+                "Replace the address with one reachable from the other collaborators, \
+                 or remove it.",
+            ),
+            DiagnosticCode::InvalidArgument => (
+                "An argument given to the program, such as an output format or a \
+                 diagnostic code, is not one it knows. No file was checked.",
+                "Run the program with one of the values listed in the message.",
+            ),
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for DiagnosticCode {
+    type Err = ThisProjectError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DiagnosticCode::ALL
+            .iter()
+            .copied()
+            .find(|code| code.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| ThisProjectError::DiagnosticExportError(format!("Unknown diagnostic code \"{}\"", s)))
+    }
+}
+
+/// Long-form description of a diagnostic code (e.g. `"CT0003"`) and how to
+/// fix it, or `None` for an unknown code.
+fn explain(code: &str) -> Option<String> {
+    let code = code.parse::<DiagnosticCode>().ok()?;
+    let (description, fix) = code.description();
+    Some(format!("{}: {}\n\n{}\n\nHow to fix: {}", code, code.title(), description, fix))
+}
+
+/// An error from the fail-fast reader, with its code and (if it is about
+/// one value) the path of that value.
+#[derive(Debug)]
+struct CodedError {
+    code: DiagnosticCode,
+    key: Option<String>,
+    message: String,
+}
+
+// Helper function: a `ReaderError` with its code and key
+fn reader_error(code: DiagnosticCode, key: Option<&str>, message: String) -> ThisProjectError {
+    ThisProjectError::ReaderError(CodedError {
+        code,
+        key: key.map(|k| k.to_string()),
+        message,
+    })
+}
+
+/// e.g. `[CT0004] user_salt_list[1]: "0xzz": invalid digit found in string`
+impl fmt::Display for CodedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] ", self.code)?;
+        if let Some(key) = &self.key {
+            write!(f, "{}: ", key)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+/// A check on one list element; returns why the element is not allowed.
+type ElementPolicy<T> = fn(&T) -> Result<(), String>;
+
+/// How `extract_parsed_list` reads one list field.
+///
+/// - `required`: a missing key is an error. Otherwise a missing key, or an
+///   empty list, reads as `None`.
+/// - `min_len`, `max_len`: allowed number of entries when the key is present.
+/// - `element_policies`: checked for every parsed element, in order.
+/// - `invalid_element`: the code for an element that does not parse.
+struct ListRules<T: 'static> {
+    required: bool,
+    min_len: usize,
+    max_len: usize,
+    element_policies: &'static [ElementPolicy<T>],
+    invalid_element: DiagnosticCode,
+}
+
+/// `user_salt_list`: at least one salt, no zero salts.
+const SALT_LIST_RULES: ListRules<u128> = ListRules {
+    required: true,
+    min_len: 1,
+    max_len: 64,
+    element_policies: &[reject_zero_salt],
+    invalid_element: DiagnosticCode::InvalidSaltHex,
+};
+
+fn reject_zero_salt(salt: &u128) -> Result<(), String> {
+    if *salt == 0 {
+        Err("a salt of zero is not allowed".into())
+    } else {
+        Ok(())
+    }
+}
+
+// Helper function: salts are written as "0x" + hex
+fn parse_hex_salt(s: &str) -> Result<u128, String> {
+    u128::from_str_radix(s.trim_start_matches("0x"), 16).map_err(|e| e.to_string())
+}
+
+// Helper function: the counterpart of `parse_hex_salt`
+fn format_hex_salt(salt: &u128) -> String {
+    format!("0x{:x}", salt)
+}
+
+/// Extracts a list of strings at `key` and parses each with `T::from_str`,
+/// under the same `rules` as `extract_parsed_list_with`.
+fn extract_parsed_list<T>(
+    table: &toml::map::Map<String, Value>,
+    key: &str,
+    rules: &ListRules<T>,
+) -> Result<Option<Vec<T>>, ThisProjectError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    extract_parsed_list_with(table, key, rules, |s| s.parse::<T>().map_err(|e| e.to_string()))
+}
+
+/// Extracts a list of strings at `key`, parsing each with `parse` and
+/// checking it against `rules`.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::ReaderError` with the key, and for element
+/// errors the element index, e.g. `[CT0004] user_salt_list[1]: "0xzz":
+/// invalid digit found in string`. Fails on the first error:
+/// - the key is missing and `rules.required` (`MissingField`)
+/// - the value is not an array, or an element is not a string (`WrongType`)
+/// - the number of entries is outside `rules.min_len..=rules.max_len`
+///   (`ListLength`)
+/// - an element does not parse (`rules.invalid_element`) or fails a policy
+///   (`DisallowedValue`)
+fn extract_parsed_list_with<T>(
+    table: &toml::map::Map<String, Value>,
+    key: &str,
+    rules: &ListRules<T>,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<Option<Vec<T>>, ThisProjectError> {
+    let arr = match table.get(key) {
+        Some(Value::Array(arr)) => arr,
+        Some(_) => return Err(reader_error(DiagnosticCode::WrongType, Some(key), "expected an array".into())),
+        None if rules.required => return Err(reader_error(DiagnosticCode::MissingField, None, format!("Missing {}", key))),
+        None => return Ok(None),
+    };
+
+    if arr.len() < rules.min_len || arr.len() > rules.max_len {
+        return Err(reader_error(
+            DiagnosticCode::ListLength,
+            Some(key),
+            format!("{} entries, expected {} to {}", arr.len(), rules.min_len, rules.max_len),
+        ));
+    }
+
+    let mut items = Vec::with_capacity(arr.len());
+    for (index, val) in arr.iter().enumerate() {
+        let element_key = format!("{}[{}]", key, index);
+        let s = match val {
+            Value::String(s) => s,
+            _ => return Err(reader_error(DiagnosticCode::WrongType, Some(&element_key), "expected a string".into())),
+        };
+        let item = parse(s).map_err(|e| reader_error(rules.invalid_element, Some(&element_key), format!("\"{}\": {}", s, e)))?;
+        for policy in rules.element_policies {
+            policy(&item).map_err(|e| reader_error(DiagnosticCode::DisallowedValue, Some(&element_key), format!("\"{}\": {}", s, e)))?;
+        }
+        items.push(item);
+    }
+
+    if items.is_empty() && !rules.required {
+        Ok(None)
+    } else {
+        Ok(Some(items))
+    }
+}
+
+/*
+Unified address list
+
+`ip_addresses` holds IPv4 and IPv6 addresses together:
+
+    ip_addresses = [
+        "10.0.0.1",
+        "fe80::1",
+    ]
+
+The legacy `ipv4_addresses` and `ipv6_addresses` lists are still read, and
+either family is accepted in either list. On reading, all three lists are
+merged in the order ip_addresses, ipv4_addresses, ipv6_addresses; each
+entry is normalized (an IPv4-mapped address such as `::ffff:10.0.0.1`
+becomes `10.0.0.1`) and later duplicates are dropped. The 64-entry limit
+applies to each list in the file, not to the merged list.
+
+The serializer still writes `ipv4_addresses` and `ipv6_addresses`, split by
+family, so readers that predate `ip_addresses` see every address.
+*/
+
+/// `ip_addresses`, `ipv4_addresses`, `ipv6_addresses` as written in a file:
+/// optional, no unspecified address.
+const IP_LIST_RULES: ListRules<IpAddr> = ListRules {
+    required: false,
+    min_len: 0,
+    max_len: 64,
+    element_policies: &[reject_unspecified_ip],
+    invalid_element: DiagnosticCode::InvalidIpAddress,
+};
+
+/// The three source lists after they are merged into `ip_addresses`: each
+/// source is already limited by `IP_LIST_RULES`, so the merged list may hold
+/// up to all of their entries.
+const MERGED_IP_LIST_RULES: ListRules<IpAddr> = ListRules {
+    max_len: 3 * IP_LIST_RULES.max_len,
+    ..IP_LIST_RULES
+};
+
+/// The legacy lists: as `IP_LIST_RULES`, with their own code for a bad entry.
+const IPV4_LIST_RULES: ListRules<IpAddr> = ListRules {
+    invalid_element: DiagnosticCode::InvalidIpv4Address,
+    ..IP_LIST_RULES
+};
+
+const IPV6_LIST_RULES: ListRules<IpAddr> = ListRules {
+    invalid_element: DiagnosticCode::InvalidIpv6Address,
+    ..IP_LIST_RULES
+};
+
+fn reject_unspecified_ip(addr: &IpAddr) -> Result<(), String> {
+    if normalize_ip_addr(*addr).is_unspecified() {
+        Err(format!("the unspecified address {} is not a peer address", addr))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the canonical form of `addr`: IPv4-mapped IPv6 addresses
+/// (`::ffff:a.b.c.d`) become the IPv4 address; all others are unchanged.
+fn normalize_ip_addr(addr: IpAddr) -> IpAddr {
+    match addr {
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => IpAddr::V4(v4),
+            None => IpAddr::V6(v6),
+        },
+        IpAddr::V4(v4) => IpAddr::V4(v4),
+    }
+}
+
+/*
+Reachability classes
+
+    Loopback    127.0.0.0/8, ::1
+    LinkLocal   169.254.0.0/16, fe80::/10
+    Private     10.0.0.0/8, 172.16.0.0/12, 192.168.0.0/16,
+                100.64.0.0/10 (shared/CGNAT), fc00::/7 (unique local)
+    Special     not a usable peer address: unspecified, multicast,
+                broadcast, documentation, benchmarking and reserved ranges
+    Global      everything else
+
+Addresses are classified after `normalize_ip_addr`, so `::ffff:10.0.0.1`
+is Private.
+*/
+
+/// Where an address can be reached from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Reachability {
+    Loopback,
+    LinkLocal,
+    Private,
+    Global,
+    Special,
+}
+
+impl fmt::Display for Reachability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Reachability::Loopback => "loopback",
+            Reachability::LinkLocal => "link-local",
+            Reachability::Private => "private",
+            Reachability::Global => "global",
+            Reachability::Special => "special-purpose",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Classifies one address (see the table above).
+fn classify_ip_addr(addr: IpAddr) -> Reachability {
+    match normalize_ip_addr(addr) {
+        IpAddr::V4(v4) => {
+            let [a, b, c, _] = v4.octets();
+            if v4.is_loopback() {
+                Reachability::Loopback
+            } else if v4.is_link_local() {
+                Reachability::LinkLocal
+            } else if v4.is_private() || (a == 100 && (64..128).contains(&b)) {
+                Reachability::Private
+            } else if v4.is_unspecified()
+                || a == 0
+                || v4.is_multicast()
+                || a >= 240
+                || v4.is_documentation()
+                || (a == 198 && (b == 18 || b == 19))
+                || (a == 192 && b == 0 && c == 0)
+            {
+                Reachability::Special
+            } else {
+                Reachability::Global
+            }
+        }
+        IpAddr::V6(v6) => {
+            let segments = v6.segments();
+            if v6.is_loopback() {
+                Reachability::Loopback
+            } else if segments[0] & 0xffc0 == 0xfe80 {
+                Reachability::LinkLocal
+            } else if segments[0] & 0xfe00 == 0xfc00 {
+                Reachability::Private
+            } else if v6.is_unspecified()
+                || v6.is_multicast()
+                || (segments[0] == 0x2001 && segments[1] == 0x0db8)
+                || (segments[0] == 0x0100 && segments[1..4] == [0, 0, 0])
+            {
+                Reachability::Special
+            } else {
+                Reachability::Global
+            }
+        }
+    }
+}
+
+impl CollaboratorTomlData {
+    /// Every address in `ip_addresses` with its class, in file order.
+    fn classified_addresses(&self) -> Vec<(IpAddr, Reachability)> {
+        self.ip_addresses
+            .iter()
+            .flatten()
+            .map(|addr| (*addr, classify_ip_addr(*addr)))
+            .collect()
+    }
+}
+
+/// What `apply_address_policy` does with an address of a given class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReachabilityAction {
+    /// Keep it.
+    Allow,
+    /// Keep it and report a `Warning`.
+    Warn,
+    /// Remove it from the collaborator and report a `Warning`.
+    Drop,
+    /// Do not load the collaborator at all (an `Error`).
+    Reject,
+}
+
+/// Load-time address policy: one action per class, plus ordering.
+///
+/// - `prefer_global_ipv6`: move global IPv6 addresses to the front of
+///   `ip_addresses` (otherwise in file order), so they are tried first.
+#[derive(Debug, Clone)]
+struct AddressPolicy {
+    loopback: ReachabilityAction,
+    link_local: ReachabilityAction,
+    private: ReachabilityAction,
+    global: ReachabilityAction,
+    special: ReachabilityAction,
+    prefer_global_ipv6: bool,
+}
+
+impl AddressPolicy {
+    /// Everything is kept; special-purpose addresses are warned about.
+    fn development() -> Self {
+        AddressPolicy {
+            loopback: ReachabilityAction::Allow,
+            link_local: ReachabilityAction::Allow,
+            private: ReachabilityAction::Allow,
+            global: ReachabilityAction::Allow,
+            special: ReachabilityAction::Warn,
+            prefer_global_ipv6: false,
+        }
+    }
+
+    /// Loopback and special-purpose addresses are dropped, link-local ones
+    /// warned about, and global IPv6 is tried first.
+    fn production() -> Self {
+        AddressPolicy {
+            loopback: ReachabilityAction::Drop,
+            link_local: ReachabilityAction::Warn,
+            private: ReachabilityAction::Allow,
+            global: ReachabilityAction::Allow,
+            special: ReachabilityAction::Drop,
+            prefer_global_ipv6: true,
+        }
+    }
+
+    fn action_for(&self, reachability: Reachability) -> ReachabilityAction {
+        match reachability {
+            Reachability::Loopback => self.loopback,
+            Reachability::LinkLocal => self.link_local,
+            Reachability::Private => self.private,
+            Reachability::Global => self.global,
+            Reachability::Special => self.special,
+        }
+    }
+}
+
+impl Default for AddressPolicy {
+    fn default() -> Self {
+        AddressPolicy::development()
+    }
+}
+
+/// How serious a `Diagnostic` is.
+///
+/// - `Error`: the file could not be loaded; no collaborator is returned.
+/// - `Warning`: something was ignored; the collaborator is still returned.
+/// - `Note`: something was changed on reading (e.g. normalized); nothing
+///   was lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// One finding about one file.
+///
+/// - `file`: the file it is about, set by the directory loader.
+/// - `key`: the path of the value it is about, e.g. `ipv4_addresses[2]`,
+///   or `None` for the file as a whole.
+/// - `line`, `column`: where in the file (1-based), if it could be found;
+///   see "Source locations" below.
+/// - `code`: the kind of finding (see "Diagnostic codes" above).
+#[derive(Debug, Clone, PartialEq)]
+struct Diagnostic {
+    severity: Severity,
+    file: Option<PathBuf>,
+    line: Option<usize>,
+    column: Option<usize>,
+    code: DiagnosticCode,
+    key: Option<String>,
+    message: String,
+}
+
+impl Diagnostic {
+    fn new(severity: Severity, code: DiagnosticCode, key: Option<&str>, message: String) -> Self {
+        Diagnostic {
+            severity,
+            file: None,
+            line: None,
+            column: None,
+            code,
+            key: key.map(|k| k.to_string()),
+            message,
+        }
+    }
+
+    /// An `Error` for a `ThisProjectError`. A `ReaderError` keeps its code
+    /// and key; every other kind of error has one code of its own.
+    fn from_error(err: &ThisProjectError) -> Self {
+        match err {
+            ThisProjectError::ReaderError(err) => Diagnostic::new(Severity::Error, err.code, err.key.as_deref(), err.message.clone()),
+            ThisProjectError::TomlVanillaDeserialStrError(_) => Diagnostic::new(Severity::Error, DiagnosticCode::TomlSyntax, None, err.to_string()),
+            ThisProjectError::IoError(_) => Diagnostic::new(Severity::Error, DiagnosticCode::FileUnreadable, None, err.to_string()),
+            // Salts are the only integers parsed from text
+            ThisProjectError::ParseIntError(_) => Diagnostic::new(Severity::Error, DiagnosticCode::InvalidSaltHex, None, err.to_string()),
+            ThisProjectError::DiagnosticExportError(_) => {
+                Diagnostic::new(Severity::Error, DiagnosticCode::InvalidArgument, None, err.to_string())
+            }
+        }
+    }
+}
+
+/// e.g. `warning[CT0005]: alice__collaborator.toml:4:33: ipv4_addresses[2]: skipped "bad": invalid IP address syntax`
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}[{}]: ", self.severity, self.code)?;
+        if let Some(file) = &self.file {
+            write!(f, "{}", file.display())?;
+            if let Some(line) = self.line {
+                write!(f, ":{}", line)?;
+                if let Some(column) = self.column {
+                    write!(f, ":{}", column)?;
+                }
+            }
+            write!(f, ": ")?;
+        }
+        if let Some(key) = &self.key {
+            write!(f, "{}: ", key)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+/// All diagnostics of a load, in the order they were found.
+#[derive(Debug, Clone, Default)]
+struct Diagnostics {
+    items: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    fn push(&mut self, diagnostic: Diagnostic) {
+        self.items.push(diagnostic);
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.items.iter()
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.items.iter().filter(|d| d.severity == severity).count()
+    }
+
+    fn has_errors(&self) -> bool {
+        self.count(Severity::Error) > 0
+    }
+
+    /// Only the diagnostics at `severity` or more serious.
+    fn at_least(&self, severity: Severity) -> impl Iterator<Item = &Diagnostic> {
+        self.items.iter().filter(move |d| d.severity <= severity)
+    }
+}
+
+/// One line per diagnostic, then e.g. `1 error, 2 warnings, 0 notes`.
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for diagnostic in &self.items {
+            writeln!(f, "{}", diagnostic)?;
+        }
+        let plural = |n: usize, word: &str| if n == 1 { format!("{} {}", n, word) } else { format!("{} {}s", n, word) };
+        write!(
+            f,
+            "{}, {}, {}",
+            plural(self.count(Severity::Error), "error"),
+            plural(self.count(Severity::Warning), "warning"),
+            plural(self.count(Severity::Note), "note")
+        )
+    }
+}
+
+/// Keys a collaborator file may have; any other top-level key is a warning.
+const KNOWN_KEYS: [&str; 8] = [
+    "user_name",
+    "user_salt_list",
+    "ip_addresses",
+    "ipv4_addresses",
+    "ipv6_addresses",
+    "gpg_key_public",
+    "sync_interval",
+    "updated_at_timestamp",
+];
+
+/// The legacy address keys, with the key they are merged into on reading.
+/// The serializer still writes them (see `serialize_collaborator_to_toml`),
+/// so they are reported as notes, not as something to fix.
+const LEGACY_KEYS: [(&str, &str); 2] = [
+    ("ipv4_addresses", "ip_addresses"),
+    ("ipv6_addresses", "ip_addresses"),
+];
+
+/// Lenient counterpart of `extract_parsed_list_with`: an element that is
+/// not a string, does not parse or fails a policy is skipped with a
+/// `Warning`, as are entries beyond `rules.max_len`. A value that is not an
+/// array is ignored with a `Warning`. `rules.required` and `rules.min_len`
+/// are not checked; use the strict extractor for lists that must be complete.
+///
+/// Returns the kept elements with their index in the file's array.
+fn extract_parsed_list_skipping<T>(
+    table: &toml::map::Map<String, Value>,
+    key: &str,
+    rules: &ListRules<T>,
+    parse: impl Fn(&str) -> Result<T, String>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Vec<(usize, T)> {
+    let arr = match table.get(key) {
+        Some(Value::Array(arr)) => arr,
+        Some(_) => {
+            diagnostics.push(Diagnostic::new(Severity::Warning, DiagnosticCode::WrongType, Some(key), "expected an array; ignored".into()));
+            return Vec::new();
+        }
+        None => return Vec::new(),
+    };
+
+    let mut items = Vec::new();
+    for (index, val) in arr.iter().enumerate() {
+        let element_key = format!("{}[{}]", key, index);
+        if index >= rules.max_len {
+            diagnostics.push(Diagnostic::new(
+                Severity::Warning,
+                DiagnosticCode::ListLength,
+                Some(&element_key),
+                format!("skipped: more than {} entries", rules.max_len),
+            ));
+            continue;
+        }
+        let s = match val {
+            Value::String(s) => s,
+            _ => {
+                diagnostics.push(Diagnostic::new(Severity::Warning, DiagnosticCode::WrongType, Some(&element_key), "skipped: expected a string".into()));
+                continue;
+            }
+        };
+        let checked = parse(s)
+            .map_err(|e| (rules.invalid_element, e))
+            .and_then(|item| {
+                for policy in rules.element_policies {
+                    policy(&item).map_err(|e| (DiagnosticCode::DisallowedValue, e))?;
+                }
+                Ok(item)
+            });
+        match checked {
+            Ok(item) => items.push((index, item)),
+            Err((code, e)) => diagnostics.push(Diagnostic::new(Severity::Warning, code, Some(&element_key), format!("skipped \"{}\": {}", s, e))),
+        }
+    }
+    items
+}
+
+/// Merges `ip_addresses`, `ipv4_addresses` and `ipv6_addresses` into
+/// `ip_addresses` in a TOML table, in that order, and removes the legacy
+/// keys. Entries that cannot be used are skipped with a `Warning` (see
+/// `extract_parsed_list_skipping`); IPv4-mapped entries are unwrapped
+/// (`AddressNormalized`) and later duplicates dropped (`DuplicateAddress`),
+/// each with a `Note`.
+///
+/// Returns the file key of each merged address (e.g. `ipv6_addresses[1]`),
+/// in the order of the new `ip_addresses`.
+fn merge_address_lists_with_diagnostics(table: &mut toml::map::Map<String, Value>, diagnostics: &mut Vec<Diagnostic>) -> Vec<String> {
+    let mut merged: Vec<IpAddr> = Vec::new();
+    let mut merged_keys = Vec::new();
+    for (key, rules) in [("ip_addresses", &IP_LIST_RULES), ("ipv4_addresses", &IPV4_LIST_RULES), ("ipv6_addresses", &IPV6_LIST_RULES)] {
+        let entries = extract_parsed_list_skipping(table, key, rules, |s| s.parse::<IpAddr>().map_err(|e| e.to_string()), diagnostics);
+        for (index, addr) in entries {
+            let normalized = normalize_ip_addr(addr);
+            let element_key = format!("{}[{}]", key, index);
+            if normalized != addr {
+                diagnostics.push(Diagnostic::new(Severity::Note, DiagnosticCode::AddressNormalized, Some(&element_key), format!("{} read as {}", addr, normalized)));
+            }
+            if merged.contains(&normalized) {
+                diagnostics.push(Diagnostic::new(Severity::Note, DiagnosticCode::DuplicateAddress, Some(&element_key), format!("duplicate {} removed", normalized)));
+            } else {
+                merged.push(normalized);
+                merged_keys.push(element_key);
+            }
+        }
+    }
+
+    table.remove("ipv4_addresses");
+    table.remove("ipv6_addresses");
+    if merged.is_empty() {
+        table.remove("ip_addresses");
+    } else {
+        let values = merged.iter().map(|addr| Value::String(addr.to_string())).collect();
+        table.insert("ip_addresses".to_string(), Value::Array(values));
+    }
+    merged_keys
+}
+
+/// Applies `policy` to the addresses of `collaborator`: drops and reorders
+/// them as configured, with an `AddressPolicy` warning for each flagged or
+/// dropped address. `address_keys` holds the file key of each entry of
+/// `ip_addresses`, as returned by `merge_address_lists_with_diagnostics`, so
+/// the diagnostics point at the entry in the file.
+///
+/// Returns `false`, with an `AddressPolicy` error and `collaborator`
+/// unchanged, for the first address whose class is
+/// `ReachabilityAction::Reject`.
+fn apply_address_policy(
+    collaborator: &mut CollaboratorTomlData,
+    policy: &AddressPolicy,
+    address_keys: &[String],
+    diagnostics: &mut Vec<Diagnostic>,
+) -> bool {
+    let mut findings = Vec::new();
+    let mut kept = Vec::new();
+
+    for (index, (address, reachability)) in collaborator.classified_addresses().into_iter().enumerate() {
+        let key = address_keys.get(index).map(String::as_str);
+        let action = policy.action_for(reachability);
+        let finding = |severity, message| Diagnostic::new(severity, DiagnosticCode::AddressPolicy, key, message);
+        match action {
+            ReachabilityAction::Reject => {
+                diagnostics.push(finding(
+                    Severity::Error,
+                    format!("{} is {}, which the address policy rejects", address, reachability),
+                ));
+                return false;
+            }
+            ReachabilityAction::Warn => findings.push(finding(Severity::Warning, format!("{} is {}", address, reachability))),
+            ReachabilityAction::Drop => findings.push(finding(Severity::Warning, format!("{} is {}; dropped", address, reachability))),
+            ReachabilityAction::Allow => {}
+        }
+        if action != ReachabilityAction::Drop {
+            kept.push((address, reachability));
+        }
+    }
+
+    if policy.prefer_global_ipv6 {
+        // Stable: the order within each group is kept
+        kept.sort_by_key(|(address, reachability)| !(address.is_ipv6() && *reachability == Reachability::Global));
+    }
+
+    collaborator.ip_addresses = if kept.is_empty() {
+        None
+    } else {
+        Some(kept.into_iter().map(|(address, _)| address).collect())
+    };
+    diagnostics.extend(findings);
+    true
+}
+
+/*
+Source locations
+
+Diagnostics point at a line and column (both 1-based, columns counted in
+characters) so CI can annotate the offending line:
+
+    parse errors          the span reported by the TOML parser
+    "key" diagnostics     the start of the key, e.g. `nickname = ...`
+    "key[i]" diagnostics  the start of array element i
+
+Keys are looked up among the top-level keys only (before the first
+`[table]` header), which is where every collaborator key lives. Diagnostics
+without a key, such as a missing field, have no location.
+*/
+
+// Helper function: 1-based (line, column) of a byte offset
+fn line_column_at(source: &str, offset: usize) -> (usize, usize) {
+    let offset = offset.min(source.len());
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = source[line_start..offset].chars().count() + 1;
+    (line, column)
+}
+
+// Helper function: byte offset just after the string starting at `start`
+// (basic, literal, or their multi-line forms), or the end of input
+fn skip_toml_string(bytes: &[u8], start: usize) -> usize {
+    let quote = bytes[start];
+    let multi_line = bytes.len() >= start + 3 && bytes[start + 1] == quote && bytes[start + 2] == quote;
+    let mut i = if multi_line { start + 3 } else { start + 1 };
+    while i < bytes.len() {
+        if quote == b'"' && bytes[i] == b'\\' {
+            i += 2;
+            continue;
+        }
+        if bytes[i] == quote {
+            if !multi_line {
+                return i + 1;
+            }
+            if bytes.len() >= i + 3 && bytes[i + 1] == quote && bytes[i + 2] == quote {
+                // A multi-line string may end with up to two extra quotes
+                let mut end = i + 3;
+                while end < bytes.len() && bytes[end] == quote && end < i + 5 {
+                    end += 1;
+                }
+                return end;
+            }
+        }
+        if !multi_line && bytes[i] == b'\n' {
+            return i;
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+// Helper function: scan the value starting at `start`; returns the offset of
+// its end and, for an array, the offsets where its elements start
+fn scan_toml_value(bytes: &[u8], start: usize) -> (usize, Vec<usize>) {
+    let mut element_starts = Vec::new();
+    let mut depth = 0usize;
+    let mut expecting_element = false;
+    let mut i = start;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        let at_element = depth == 1 && expecting_element && !c.is_ascii_whitespace() && c != b'#' && c != b']' && c != b',';
+        if at_element {
+            element_starts.push(i);
+            expecting_element = false;
+        }
+        match c {
+            b'"' | b'\'' => {
+                i = skip_toml_string(bytes, i);
+                continue;
+            }
+            b'#' => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            b'[' | b'{' => {
+                depth += 1;
+                if depth == 1 {
+                    expecting_element = true;
+                }
+            }
+            b']' | b'}' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return (i + 1, element_starts);
+                }
+            }
+            b',' if depth == 1 => expecting_element = true,
+            b'\n' if depth == 0 => return (i, element_starts),
+            _ => {}
+        }
+        i += 1;
+    }
+    (bytes.len(), element_starts)
+}
+
+// Helper function: split "key[3]" into ("key", Some(3))
+fn split_key_path(key_path: &str) -> (&str, Option<usize>) {
+    if let Some(open) = key_path.find('[') {
+        if let Some(index) = key_path[open + 1..].strip_suffix(']').and_then(|i| i.parse::<usize>().ok()) {
+            return (&key_path[..open], Some(index));
+        }
+    }
+    (key_path, None)
+}
+
+/// Finds the 1-based (line, column) of a top-level `key` or `key[index]`
+/// in TOML source text (see "Source locations" above).
+fn locate_key_path(source: &str, key_path: &str) -> Option<(usize, usize)> {
+    let (key, index) = split_key_path(key_path);
+    let bytes = source.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        // At the start of a line: skip indentation, blank lines and comments
+        while i < bytes.len() && (bytes[i] == b' ' || bytes[i] == b'\t' || bytes[i] == b'\r' || bytes[i] == b'\n') {
+            i += 1;
+        }
+        if i >= bytes.len() || bytes[i] == b'[' {
+            return None; // end of input, or the first table header
+        }
+        if bytes[i] == b'#' {
+            while i < bytes.len() && bytes[i] != b'\n' {
+                i += 1;
+            }
+            continue;
+        }
+
+        // Key (bare or quoted)
+        let key_start = i;
+        let this_key = if bytes[i] == b'"' || bytes[i] == b'\'' {
+            i = skip_toml_string(bytes, i);
+            source[key_start + 1..i.saturating_sub(1).max(key_start + 1)].to_string()
+        } else {
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_' || bytes[i] == b'-') {
+                i += 1;
+            }
+            source[key_start..i].to_string()
+        };
+        while i < bytes.len() && (bytes[i] == b' ' || bytes[i] == b'\t') {
+            i += 1;
+        }
+        if i >= bytes.len() || bytes[i] != b'=' {
+            // Not a key/value line (e.g. a dotted key); skip the line
+            while i < bytes.len() && bytes[i] != b'\n' {
+                i += 1;
+            }
+            continue;
+        }
+        i += 1;
+        while i < bytes.len() && (bytes[i] == b' ' || bytes[i] == b'\t') {
+            i += 1;
+        }
+
+        let (value_end, element_starts) = scan_toml_value(bytes, i);
+        if this_key == key {
+            return match index {
+                None => Some(line_column_at(source, key_start)),
+                Some(index) => element_starts.get(index).map(|offset| line_column_at(source, *offset)),
+            };
+        }
+        i = value_end;
+    }
+    None
+}
+
+/// A JSON value for writing diagnostics. Numbers are kept as their JSON
+/// text and objects as ordered (key, value) pairs, so output is stable.
+#[derive(Debug, Clone, PartialEq)]
+enum JsonValue {
+    Null,
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+// Helper function: an optional string or number as a JSON value
+fn json_string_or_null(value: Option<&str>) -> JsonValue {
+    value.map(|s| JsonValue::String(s.to_string())).unwrap_or(JsonValue::Null)
+}
+
+fn json_number_or_null(value: Option<usize>) -> JsonValue {
+    value.map(|n| JsonValue::Number(n.to_string())).unwrap_or(JsonValue::Null)
+}
+
+/// Serializes a `JsonValue` to a pretty-printed JSON string (4-space indent),
+/// one object member or array element per line.
+fn write_json_value(value: &JsonValue) -> String {
+    let mut json_string = String::new();
+    write_json_value_indented(&mut json_string, value, Some(0));
+    json_string
+}
+
+/// Serializes a `JsonValue` on a single line, for JSON lines output.
+fn write_json_value_compact(value: &JsonValue) -> String {
+    let mut json_string = String::new();
+    write_json_value_indented(&mut json_string, value, None);
+    json_string
+}
+
+// Helper function to write one value at a given indent level (None: all on one line)
+fn write_json_value_indented(json_string: &mut String, value: &JsonValue, indent: Option<usize>) {
+    let inner = indent.map(|level| level + 1);
+    match value {
+        JsonValue::Null => json_string.push_str("null"),
+        JsonValue::Number(n) => json_string.push_str(n),
+        JsonValue::String(s) => write_json_string(json_string, s),
+        JsonValue::Array(items) => {
+            if items.is_empty() {
+                json_string.push_str("[]");
+                return;
+            }
+            json_string.push('[');
+            for (i, item) in items.iter().enumerate() {
+                push_newline_indent(json_string, inner);
+                write_json_value_indented(json_string, item, inner);
+                if i + 1 < items.len() {
+                    json_string.push(',');
+                }
+            }
+            push_newline_indent(json_string, indent);
+            json_string.push(']');
+        }
+        JsonValue::Object(members) => {
+            if members.is_empty() {
+                json_string.push_str("{}");
+                return;
+            }
+            json_string.push('{');
+            for (i, (key, member)) in members.iter().enumerate() {
+                push_newline_indent(json_string, inner);
+                write_json_string(json_string, key);
+                json_string.push_str(if indent.is_some() { ": " } else { ":" });
+                write_json_value_indented(json_string, member, inner);
+                if i + 1 < members.len() {
+                    json_string.push(',');
+                }
+            }
+            push_newline_indent(json_string, indent);
+            json_string.push('}');
+        }
+    }
+}
+
+// Helper function to start a new indented line (or nothing, on a single line)
+fn push_newline_indent(json_string: &mut String, indent: Option<usize>) {
+    if let Some(level) = indent {
+        json_string.push('\n');
+        for _ in 0..level {
+            json_string.push_str("    ");
+        }
+    }
+}
+
+// Helper function to write a quoted and escaped JSON string
+fn write_json_string(json_string: &mut String, s: &str) {
+    json_string.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json_string.push_str("\\\""),
+            '\\' => json_string.push_str("\\\\"),
+            '\n' => json_string.push_str("\\n"),
+            '\r' => json_string.push_str("\\r"),
+            '\t' => json_string.push_str("\\t"),
+            '\u{08}' => json_string.push_str("\\b"),
+            '\u{0C}' => json_string.push_str("\\f"),
+            c if (c as u32) < 0x20 => json_string.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json_string.push(c),
+        }
+    }
+    json_string.push('"');
+}
+
+/// How `format_diagnostics` writes diagnostics.
+///
+/// - `Text`: one `Display` line per diagnostic and a summary, for people.
+/// - `JsonLines`: one JSON object per line with `file`, `line`, `column`,
+///   `code`, `severity`, `key` and `message`; missing values are `null`.
+/// - `Sarif`: one SARIF 2.1.0 log with a result per diagnostic, for CI
+///   systems that annotate files.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DiagnosticFormat {
+    Text,
+    JsonLines,
+    Sarif,
+}
+
+impl FromStr for DiagnosticFormat {
+    type Err = ThisProjectError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(DiagnosticFormat::Text),
+            "jsonl" | "json-lines" => Ok(DiagnosticFormat::JsonLines),
+            "sarif" => Ok(DiagnosticFormat::Sarif),
+            other => Err(ThisProjectError::DiagnosticExportError(format!(
+                "Unknown diagnostic format \"{}\" (use text, jsonl or sarif)", other
+            ))),
+        }
+    }
+}
+
+/// The tool name in SARIF output.
+const SARIF_TOOL_NAME: &str = "collaborator-toml-check";
+
+// Helper function: a file path as a SARIF/JSON string with '/' separators
+fn file_path_string(file: &Path) -> String {
+    file.to_string_lossy().replace('\\', "/")
+}
+
+/// One diagnostic as a JSON lines record.
+fn diagnostic_to_json(diagnostic: &Diagnostic) -> JsonValue {
+    let file = diagnostic.file.as_deref().map(file_path_string);
+    JsonValue::Object(vec![
+        ("file".to_string(), json_string_or_null(file.as_deref())),
+        ("line".to_string(), json_number_or_null(diagnostic.line)),
+        ("column".to_string(), json_number_or_null(diagnostic.column)),
+        ("code".to_string(), JsonValue::String(diagnostic.code.to_string())),
+        ("severity".to_string(), JsonValue::String(diagnostic.severity.to_string())),
+        ("key".to_string(), json_string_or_null(diagnostic.key.as_deref())),
+        ("message".to_string(), JsonValue::String(diagnostic.message.clone())),
+    ])
+}
+
+/// One diagnostic as a SARIF `result`. SARIF levels are the same words as
+/// our severities (`error`, `warning`, `note`).
+fn diagnostic_to_sarif_result(diagnostic: &Diagnostic) -> JsonValue {
+    let mut result = vec![("ruleId".to_string(), JsonValue::String(diagnostic.code.to_string()))];
+    result.push(("level".to_string(), JsonValue::String(diagnostic.severity.to_string())));
+    let message = match &diagnostic.key {
+        Some(key) => format!("{}: {}", key, diagnostic.message),
+        None => diagnostic.message.clone(),
+    };
+    result.push(("message".to_string(), JsonValue::Object(vec![("text".to_string(), JsonValue::String(message))])));
+
+    if let Some(file) = &diagnostic.file {
+        let mut physical_location = vec![(
+            "artifactLocation".to_string(),
+            JsonValue::Object(vec![("uri".to_string(), JsonValue::String(file_path_string(file)))]),
+        )];
+        if let Some(line) = diagnostic.line {
+            let mut region = vec![("startLine".to_string(), JsonValue::Number(line.to_string()))];
+            if let Some(column) = diagnostic.column {
+                region.push(("startColumn".to_string(), JsonValue::Number(column.to_string())));
+            }
+            physical_location.push(("region".to_string(), JsonValue::Object(region)));
+        }
+        result.push((
+            "locations".to_string(),
+            JsonValue::Array(vec![JsonValue::Object(vec![(
+                "physicalLocation".to_string(),
+                JsonValue::Object(physical_location),
+            )])]),
+        ));
+    }
+    JsonValue::Object(result)
+}
+
+/// One diagnostic code as a SARIF `reportingDescriptor` (a rule).
+fn sarif_rule(code: DiagnosticCode) -> JsonValue {
+    let (description, fix) = code.description();
+    JsonValue::Object(vec![
+        ("id".to_string(), JsonValue::String(code.to_string())),
+        ("shortDescription".to_string(), JsonValue::Object(vec![("text".to_string(), JsonValue::String(code.title().to_string()))])),
+        ("fullDescription".to_string(), JsonValue::Object(vec![("text".to_string(), JsonValue::String(description.to_string()))])),
+        ("help".to_string(), JsonValue::Object(vec![("text".to_string(), JsonValue::String(fix.to_string()))])),
+    ])
+}
+
+/// Writes `diagnostics` in `format` (see `DiagnosticFormat`).
+fn format_diagnostics(diagnostics: &Diagnostics, format: DiagnosticFormat) -> String {
+    match format {
+        DiagnosticFormat::Text => format!("{}\n", diagnostics),
+        DiagnosticFormat::JsonLines => {
+            let mut json_lines = String::new();
+            for diagnostic in diagnostics.iter() {
+                json_lines.push_str(&write_json_value_compact(&diagnostic_to_json(diagnostic)));
+                json_lines.push('\n');
+            }
+            json_lines
+        }
+        DiagnosticFormat::Sarif => {
+            let sarif_log = JsonValue::Object(vec![
+                ("version".to_string(), JsonValue::String("2.1.0".to_string())),
+                (
+                    "$schema".to_string(),
+                    JsonValue::String("https://json.schemastore.org/sarif-2.1.0.json".to_string()),
+                ),
+                (
+                    "runs".to_string(),
+                    JsonValue::Array(vec![JsonValue::Object(vec![
+                        (
+                            "tool".to_string(),
+                            JsonValue::Object(vec![(
+                                "driver".to_string(),
+                                JsonValue::Object(vec![
+                                    ("name".to_string(), JsonValue::String(SARIF_TOOL_NAME.to_string())),
+                                    ("rules".to_string(), JsonValue::Array(DiagnosticCode::ALL.iter().map(|code| sarif_rule(*code)).collect())),
+                                ]),
+                            )]),
+                        ),
+                        (
+                            "results".to_string(),
+                            JsonValue::Array(diagnostics.iter().map(diagnostic_to_sarif_result).collect()),
+                        ),
+                    ])]),
+                ),
+            ]);
+            format!("{}\n", write_json_value(&sarif_log))
+        }
+    }
+}
+
+/// Reads one collaborator from TOML text, reporting everything found, and
+/// applies the address `policy` (see `apply_address_policy`).
+///
+/// Returns `None` (with at least one `Error`) if the text is not TOML, a
+/// required field is missing or invalid, or the policy rejects an address.
+/// Unknown keys, skipped addresses and addresses flagged or dropped by the
+/// policy are warnings; the collaborator is still returned. Legacy address
+/// keys and normalized or duplicate addresses are notes.
+/// Diagnostics about a key get the line and column of that key.
+fn collaborator_from_toml_with_diagnostics(
+    toml_string: &str,
+    policy: &AddressPolicy,
+) -> (Option<CollaboratorTomlData>, Vec<Diagnostic>) {
+    let mut diagnostics = Vec::new();
+
+    let mut table = match toml::from_str::<Value>(toml_string) {
+        Ok(Value::Table(table)) => table,
+        Ok(_) => {
+            let err = ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure: Expected a table".into());
+            diagnostics.push(Diagnostic::from_error(&err));
+            return (None, diagnostics);
+        }
+        Err(e) => {
+            let err = ThisProjectError::TomlVanillaDeserialStrError(e.message().to_string());
+            let mut diagnostic = Diagnostic::from_error(&err);
+            if let Some(span) = e.span() {
+                let (line, column) = line_column_at(toml_string, span.start);
+                diagnostic.line = Some(line);
+                diagnostic.column = Some(column);
+            }
+            diagnostics.push(diagnostic);
+            return (None, diagnostics);
+        }
+    };
+
+    // Unknown keys
+    for key in table.keys() {
+        if !KNOWN_KEYS.contains(&key.as_str()) {
+            diagnostics.push(Diagnostic::new(Severity::Warning, DiagnosticCode::UnknownKey, Some(key), "unknown key; ignored".into()));
+        }
+    }
+
+    // Legacy address keys
+    for (key, replacement) in LEGACY_KEYS.iter() {
+        if table.contains_key(*key) {
+            diagnostics.push(Diagnostic::new(
+                Severity::Note,
+                DiagnosticCode::LegacyKey,
+                Some(key),
+                format!("merged into {}", replacement),
+            ));
+        }
+    }
+
+    // Addresses: skip what cannot be used
+    let address_keys = merge_address_lists_with_diagnostics(&mut table, &mut diagnostics);
+
+    // Everything else must be valid, and the addresses allowed by the policy
+    let collaborator = match collaborator_from_toml_table(&table) {
+        Ok(mut collaborator) => {
+            if apply_address_policy(&mut collaborator, policy, &address_keys, &mut diagnostics) {
+                Some(collaborator)
+            } else {
+                None
+            }
+        }
+        Err(e) => {
+            diagnostics.push(Diagnostic::from_error(&e));
+            None
+        }
+    };
+
+    for diagnostic in diagnostics.iter_mut() {
+        if let Some((line, column)) = diagnostic.key.as_deref().and_then(|key| locate_key_path(toml_string, key)) {
+            diagnostic.line = Some(line);
+            diagnostic.column = Some(column);
+        }
+    }
+    (collaborator, diagnostics)
+}
+
+// Helper function: TOML table -> CollaboratorTomlData, failing on the first bad field
+fn collaborator_from_toml_table(table: &toml::map::Map<String, Value>) -> Result<CollaboratorTomlData, ThisProjectError> {
+
+    // Extract user_name
+    let user_name = extract_string(table, "user_name")?;
+
+    // Extract user_salt_list (required, so never None)
+    let user_salt_list = extract_parsed_list_with(table, "user_salt_list", &SALT_LIST_RULES, parse_hex_salt)?
+        .unwrap_or_default();
+
+    // Extract ip_addresses (after merge_address_lists, the only address list)
+    let ip_addresses = extract_parsed_list::<IpAddr>(table, "ip_addresses", &MERGED_IP_LIST_RULES)?;
+
+    // Extract gpg_key_public
+    let gpg_key_public = extract_string(table, "gpg_key_public")?;
+
+    // Extract sync_interval
+    let sync_interval = extract_u64(table, "sync_interval")?;
+
+    // Extract updated_at_timestamp
+    let updated_at_timestamp = extract_u64(table, "updated_at_timestamp")?;
+
+    Ok(CollaboratorTomlData {
+        user_name,
+        user_salt_list,
+        ip_addresses,
+        gpg_key_public,
+        sync_interval,
+        updated_at_timestamp,
+    })
+}
+
+// Helper function to extract a required string from a toml::Value::Table
+fn extract_string(table: &toml::map::Map<String, Value>, key: &str) -> Result<String, ThisProjectError> {
+    match table.get(key) {
+        Some(Value::String(s)) => Ok(s.clone()),
+        Some(_) => Err(reader_error(DiagnosticCode::WrongType, Some(key), "expected a string".into())),
+        None => Err(reader_error(DiagnosticCode::MissingField, None, format!("Missing {}", key))),
+    }
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str) -> Result<u64, ThisProjectError> {
+    match table.get(key) {
+        Some(Value::Integer(i)) => {
+            if let Ok(value) = u64::try_from(*i) {
+                Ok(value)
+            } else {
+                Err(reader_error(DiagnosticCode::U64OutOfRange, Some(key), format!("{} is out of range for u64", i)))
+            }
+        }
+        Some(_) => Err(reader_error(DiagnosticCode::WrongType, Some(key), "expected an integer".into())),
+        None => Err(reader_error(DiagnosticCode::MissingField, None, format!("Missing {}", key))),
+    }
+}
+
+/// Toml Deserialization: Reads collaborator setup data from TOML files in a
+/// specified directory, applying an address policy.
+///
+/// Every `*.toml` file in `project_graph_data/collaborator_files_address_book`
+/// is read with `collaborator_from_toml_with_diagnostics`, and its findings
+/// are added to one `Diagnostics`, each with the path of its file and a code
+/// (see `DiagnosticCode`). A file that cannot be read or is not UTF-8 is a
+/// `FileUnreadable` error, and the other files are still read. A file with
+/// an `Error` is skipped; with only warnings and notes its collaborator is
+/// still returned. A file not named `{user_name}__collaborator.toml` is a
+/// `NameMismatch` warning.
+///
+/// # Returns
+///
+/// Returns a `Result` containing:
+/// - `Ok`: A tuple with:
+///     - A vector of successfully parsed `CollaboratorTomlData` instances.
+///     - The `Diagnostics` for all files.
+/// - `Err`: A `ThisProjectError` if the directory itself could not be listed.
+fn read_a_collaborator_setup_toml(policy: &AddressPolicy) -> Result<(Vec<CollaboratorTomlData>, Diagnostics), ThisProjectError> {
+    let mut collaborators = Vec::new();
+    let mut diagnostics = Diagnostics::default();
+    let dir_path = Path::new("project_graph_data/collaborator_files_address_book");
+
+    for entry in fs::read_dir(dir_path)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
+            let toml_string = match fs::read_to_string(&path) {
+                Ok(toml_string) => toml_string,
+                Err(e) => {
+                    let mut diagnostic = Diagnostic::from_error(&ThisProjectError::IoError(e));
+                    diagnostic.file = Some(path.clone());
+                    diagnostics.push(diagnostic);
+                    continue;
+                }
+            };
+
+            let (collaborator, file_diagnostics) = collaborator_from_toml_with_diagnostics(&toml_string, policy);
+            for mut diagnostic in file_diagnostics {
+                diagnostic.file = Some(path.clone());
+                diagnostics.push(diagnostic);
+            }
+            if let Some(collaborator) = collaborator {
+                if let Some(mut diagnostic) = check_file_name(&path, &collaborator, &toml_string) {
+                    diagnostic.file = Some(path.clone());
+                    diagnostics.push(diagnostic);
+                }
+                collaborators.push(collaborator);
+            }
+        }
+    }
+
+    Ok((collaborators, diagnostics))
+}
+
+// Helper function: a `NameMismatch` warning if `path` is not named
+// `{user_name}__collaborator.toml`, ignoring case (so `alice__collaborator.toml`
+// may hold "Alice", as files are looked up by the lowercased name)
+fn check_file_name(path: &Path, collaborator: &CollaboratorTomlData, toml_string: &str) -> Option<Diagnostic> {
+    let expected = format!("{}__collaborator.toml", collaborator.user_name.to_lowercase());
+    let file_name = path.file_name().and_then(OsStr::to_str).unwrap_or("");
+    if file_name.to_lowercase() == expected {
+        return None;
+    }
+    let mut diagnostic = Diagnostic::new(
+        Severity::Warning,
+        DiagnosticCode::NameMismatch,
+        Some("user_name"),
+        format!("\"{}\" does not match the file name; expected {}", collaborator.user_name, expected),
+    );
+    if let Some((line, column)) = locate_key_path(toml_string, "user_name") {
+        diagnostic.line = Some(line);
+        diagnostic.column = Some(column);
+    }
+    Some(diagnostic)
+}
+
+/// Serializes a `CollaboratorTomlData` struct into a TOML-formatted string.
+///
+/// One `key = value` line per field, in struct order, with escaped strings
+/// and salts as `0x` hex strings. The addresses are normalized and written as
+/// `ipv4_addresses` and `ipv6_addresses`, grouped by family, so every reader
+/// sees them; an empty list is left out.
+///
+/// # Error Handling
+///
+/// Returns an error if either address list would have more entries than
+/// `IP_LIST_RULES` allows, since the file could not be read back.
+fn serialize_collaborator_to_toml(collaborator: &CollaboratorTomlData) -> Result<String, ThisProjectError> {
+    let mut toml_string = String::new();
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", escape_toml_basic_string(&collaborator.user_name)));
+
+    // Add user_salt_list
+    serialize_list_with(&mut toml_string, "user_salt_list", &collaborator.user_salt_list, format_hex_salt);
+
+    // Add ipv4_addresses and ipv6_addresses
+    if let Some(addresses) = &collaborator.ip_addresses {
+        let (ipv4, ipv6): (Vec<IpAddr>, Vec<IpAddr>) =
+            addresses.iter().map(|addr| normalize_ip_addr(*addr)).partition(IpAddr::is_ipv4);
+        serialize_address_list(&mut toml_string, "ipv4_addresses", &ipv4)?;
+        serialize_address_list(&mut toml_string, "ipv6_addresses", &ipv6)?;
+    }
+
+    // Add gpg_key_public
+    toml_string.push_str(&format!("gpg_key_public = \"{}\"\n", escape_toml_basic_string(&collaborator.gpg_key_public)));
+
+    // Add sync_interval
+    toml_string.push_str(&format!("sync_interval = {}\n", collaborator.sync_interval));
+
+    // Add updated_at_timestamp
+    toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp));
+
+    Ok(toml_string)
+}
+
+// Helper function to write one address list, or nothing if it is empty
+fn serialize_address_list(toml_string: &mut String, key: &str, addresses: &[IpAddr]) -> Result<(), ThisProjectError> {
+    if addresses.len() > IP_LIST_RULES.max_len {
+        return Err(reader_error(
+            DiagnosticCode::ListLength,
+            Some(key),
+            format!("{} entries, at most {} can be written", addresses.len(), IP_LIST_RULES.max_len),
+        ));
+    }
+    if !addresses.is_empty() {
+        serialize_list(toml_string, key, addresses);
+    }
+    Ok(())
+}
+
+/// Writes `items` as a multi-line TOML array of strings using `Display`.
+fn serialize_list<T: fmt::Display>(toml_string: &mut String, key: &str, items: &[T]) {
+    serialize_list_with(toml_string, key, items, |item| item.to_string());
+}
+
+/// Writes `items` as a multi-line TOML array of strings, formatting each
+/// with `format` (the counterpart of the `parse` in `extract_parsed_list_with`).
+fn serialize_list_with<T>(toml_string: &mut String, key: &str, items: &[T], format: impl Fn(&T) -> String) {
+    toml_string.push_str(&format!("{} = [\n", key));
+    for item in items {
+        toml_string.push_str(&format!("    \"{}\",\n", escape_toml_basic_string(&format(item))));
+    }
+    toml_string.push_str("]\n");
+}
+
+// Helper function to escape a value for a TOML basic string ("...")
+fn escape_toml_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Function to write a TOML string to a file
+fn write_toml_to_file(file_path: &str, toml_string: &str) -> Result<(), ThisProjectError> {
+    // Attempt to create the file.
+    let mut file = match File::create(file_path) {
+        Ok(file) => file,
+        Err(e) => return Err(ThisProjectError::IoError(e)),
+    };
+
+    // Attempt to write to the file.
+    if let Err(e) = file.write_all(toml_string.as_bytes()) {
+        return Err(ThisProjectError::IoError(e));
+    }
+
+    // Everything successful!
+    Ok(())
+}
+
+fn main() {
+    // `explain CT0003` prints the long description of a code
+    if env::args().nth(1).as_deref() == Some("explain") {
+        let code = env::args().nth(2).unwrap_or_default();
+        match explain(&code) {
+            Some(text) => println!("{}", text),
+            None => println!("Unknown diagnostic code \"{}\"", code),
+        }
+        return;
+    }
+
+    // Output format for the directory diagnostics: text (default), jsonl or sarif
+    let format = match env::args().nth(1).map(|arg| arg.parse::<DiagnosticFormat>()) {
+        Some(Ok(format)) => format,
+        Some(Err(e)) => {
+            println!("{}", Diagnostic::from_error(&e));
+            return;
+        }
+        None => DiagnosticFormat::Text,
+    };
+
+    // One file with something at every severity, read under the production policy
+    let messy_toml = "user_name = \"Carol\"\nuser_salt_list = [\"0x1\"]\n\
+        ipv4_addresses = [\"10.0.0.1\", \"bad\", \"127.0.0.1\"]\n\
+        ipv6_addresses = [\"::ffff:10.0.0.1\", \"fe80::1\"]\n\
+        gpg_key_public = \"\"\nsync_interval = 60\nupdated_at_timestamp = 0\nnickname = \"cj\"\n";
+    let (collaborator, diagnostics) = collaborator_from_toml_with_diagnostics(messy_toml, &AddressPolicy::production());
+    let messy_diagnostics = Diagnostics { items: diagnostics };
+    print!("{}", format_diagnostics(&messy_diagnostics, DiagnosticFormat::Text));
+    if let Some(collaborator) = collaborator {
+        println!("Loaded {} with {:?}", collaborator.user_name, collaborator.ip_addresses);
+    }
+
+    // The same file under a policy that rejects loopback outright
+    let strict_policy = AddressPolicy {
+        loopback: ReachabilityAction::Reject,
+        ..AddressPolicy::production()
+    };
+    let (_, diagnostics) = collaborator_from_toml_with_diagnostics(messy_toml, &strict_policy);
+    for diagnostic in diagnostics.iter().filter(|d| d.severity == Severity::Error) {
+        println!("{}", diagnostic);
+    }
+
+    // A file that is not TOML: the parser's position becomes the location
+    let (_, diagnostics) = collaborator_from_toml_with_diagnostics("user_name = \"Dan\"\nuser_salt_list = [\"0x1\"\n", &AddressPolicy::default());
+    print!("{}", format_diagnostics(&Diagnostics { items: diagnostics }, DiagnosticFormat::JsonLines));
+
+    // Errors from the fail-fast reader keep their code and key
+    let bad_interval_toml = "user_name = \"Eve\"\nuser_salt_list = [\"0x1\"]\n\
+        gpg_key_public = \"\"\nsync_interval = -5\nupdated_at_timestamp = 0\n";
+    let (_, diagnostics) = collaborator_from_toml_with_diagnostics(bad_interval_toml, &AddressPolicy::default());
+    for diagnostic in &diagnostics {
+        println!("{}", diagnostic);
+        if let Some(text) = explain(diagnostic.code.as_str()) {
+            println!("{}", text);
+        }
+    }
+
+    // Example CollaboratorTomlData instance
+    let collaborator = CollaboratorTomlData {
+        user_name: "Bob".to_string(),
+        user_salt_list: vec![0x123456789abcdef0, 0xabcdef0123456789],
+        ip_addresses: Some(vec![
+            IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)),
+            IpAddr::V6(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1)),
+        ]),
+        gpg_key_public: "-----BEGIN PGP PUBLIC KEY BLOCK----- ...".to_string(),
+        sync_interval: 300,
+        updated_at_timestamp: 1728308000,
+    };
+
+    match serialize_collaborator_to_toml(&collaborator) {
+        Ok(toml_string) => {
+            println!("Serialized TOML:\n{}", toml_string);
+
+            // Write the TOML string to a file (example file path)
+            match write_toml_to_file("collaborator_data.toml", &toml_string) {
+                Ok(_) => println!("TOML data written to file successfully."),
+                Err(e) => println!("Error writing to file: {}", e),
+            }
+        }
+        Err(e) => println!("Error serializing to TOML: {}", e),
+    }
+
+    match read_a_collaborator_setup_toml(&AddressPolicy::production()) {
+        Ok((collaborators, diagnostics)) => {
+            print!("{}", format_diagnostics(&diagnostics, format));
+            if diagnostics.has_errors() {
+                println!("Errors only:");
+                for diagnostic in diagnostics.at_least(Severity::Error) {
+                    println!("{}", diagnostic);
+                }
+            }
+            for diagnostic in diagnostics.iter().filter(|d| d.severity == Severity::Warning) {
+                println!("Warning for {}: {}", diagnostic.key.as_deref().unwrap_or("(file)"), diagnostic.message);
+            }
+
+            println!("Collaborators:");
+            for collaborator in collaborators {
+                println!("{:#?}", collaborator);
+            }
+        }
+        Err(e) => {
+            println!("Error reading TOML files: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collaborator_toml(address_lines: &str) -> String {
+        format!(
+            "user_name = \"Carol\"\nuser_salt_list = [\"0x1\"]\n{}gpg_key_public = \"\"\nsync_interval = 60\nupdated_at_timestamp = 0\n",
+            address_lines
+        )
+    }
+
+    // The code of the first error, or else of the first warning, for one file
+    fn first_finding(toml_string: &str) -> (Severity, DiagnosticCode) {
+        let (_, mut diagnostics) = collaborator_from_toml_with_diagnostics(toml_string, &AddressPolicy::default());
+        diagnostics.retain(|d| d.severity <= Severity::Warning);
+        diagnostics.sort_by_key(|d| d.severity);
+        let first = diagnostics.first().unwrap_or_else(|| panic!("no finding in {}", toml_string));
+        (first.severity, first.code)
+    }
+
+    #[test]
+    fn each_finding_has_its_code() {
+        let valid = collaborator_toml("");
+        let cases = [
+            (valid.replace("user_name = \"Carol\"\n", ""), Severity::Error, DiagnosticCode::MissingField),
+            (valid.replace("sync_interval = 60", "sync_interval = \"60\""), Severity::Error, DiagnosticCode::WrongType),
+            (valid.replace("\"0x1\"", "\"0xzz\""), Severity::Error, DiagnosticCode::InvalidSaltHex),
+            (collaborator_toml("ipv4_addresses = [\"10.0.0.256\"]\n"), Severity::Warning, DiagnosticCode::InvalidIpv4Address),
+            (valid.replace("updated_at_timestamp = 0", "updated_at_timestamp = -1"), Severity::Error, DiagnosticCode::U64OutOfRange),
+            (collaborator_toml("nickname = \"caz\"\n"), Severity::Warning, DiagnosticCode::UnknownKey),
+        ];
+        for (toml_string, severity, code) in cases {
+            assert_eq!(first_finding(&toml_string), (severity, code), "{}", toml_string);
+        }
+    }
+
+    #[test]
+    fn file_name_is_matched_ignoring_case() {
+        let toml_string = collaborator_toml("");
+        let (collaborator, _) = collaborator_from_toml_with_diagnostics(&toml_string, &AddressPolicy::default());
+        let collaborator = collaborator.unwrap();
+
+        assert_eq!(check_file_name(Path::new("dir/carol__collaborator.toml"), &collaborator, &toml_string), None);
+        assert_eq!(check_file_name(Path::new("dir/Carol__collaborator.toml"), &collaborator, &toml_string), None);
+        let mismatch = check_file_name(Path::new("dir/dave__collaborator.toml"), &collaborator, &toml_string).unwrap();
+        assert_eq!((mismatch.severity, mismatch.code), (Severity::Warning, DiagnosticCode::NameMismatch));
+        assert_eq!((mismatch.line, mismatch.column), (Some(1), Some(1)));
+    }
+
+    #[test]
+    fn every_error_kind_has_its_own_code() {
+        let parse_int_error = u128::from_str_radix("zz", 16).unwrap_err();
+        assert_eq!(Diagnostic::from_error(&ThisProjectError::ParseIntError(parse_int_error)).code, DiagnosticCode::InvalidSaltHex);
+        let format_error = "yaml".parse::<DiagnosticFormat>().unwrap_err();
+        assert_eq!(Diagnostic::from_error(&format_error).code, DiagnosticCode::InvalidArgument);
+    }
+
+    #[test]
+    fn every_code_is_explained_and_parses_back() {
+        for code in DiagnosticCode::ALL {
+            assert_eq!(code.as_str().parse::<DiagnosticCode>().unwrap(), code);
+            assert!(explain(code.as_str()).unwrap().starts_with(code.as_str()));
+        }
+    }
+
+    #[test]
+    fn unreadable_file_has_a_code() {
+        let path = std::env::temp_dir().join(format!("diagnostic_codes_test_{}.toml", std::process::id()));
+        fs::write(&path, [0xffu8, 0xfe, 0x00]).unwrap();
+        let err = fs::read_to_string(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        let diagnostic = Diagnostic::from_error(&ThisProjectError::IoError(err));
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.code, DiagnosticCode::FileUnreadable);
+        assert!(diagnostic.to_string().starts_with("error[CT0016]: "), "{}", diagnostic);
+    }
+
+    #[test]
+    fn policy_findings_have_their_code() {
+        let toml_string = collaborator_toml("ipv4_addresses = [\"10.0.0.1\", \"127.0.0.1\"]\n");
+        let (_, diagnostics) = collaborator_from_toml_with_diagnostics(&toml_string, &AddressPolicy::production());
+        let dropped = diagnostics.iter().find(|d| d.code == DiagnosticCode::AddressPolicy).unwrap();
+        assert_eq!(dropped.severity, Severity::Warning);
+        assert_eq!(dropped.key.as_deref(), Some("ipv4_addresses[1]"));
+
+        let strict_policy = AddressPolicy {
+            loopback: ReachabilityAction::Reject,
+            ..AddressPolicy::production()
+        };
+        let (collaborator, diagnostics) = collaborator_from_toml_with_diagnostics(&toml_string, &strict_policy);
+        assert!(collaborator.is_none());
+        assert!(diagnostics.iter().any(|d| d.severity == Severity::Error && d.code == DiagnosticCode::AddressPolicy));
+    }
+
+    #[test]
+    fn serializer_refuses_a_list_it_could_not_read_back() {
+        let collaborator = CollaboratorTomlData {
+            user_name: "Carol".to_string(),
+            user_salt_list: vec![1],
+            ip_addresses: Some((1..=65).map(|i| IpAddr::V4(Ipv4Addr::new(10, 0, 0, i))).collect()),
+            gpg_key_public: String::new(),
+            sync_interval: 60,
+            updated_at_timestamp: 0,
+        };
+        let err = serialize_collaborator_to_toml(&collaborator).unwrap_err();
+        assert!(err.to_string().contains("[CT0012] ipv4_addresses: 65 entries"), "{}", err);
+    }
+}
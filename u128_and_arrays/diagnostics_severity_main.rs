@@ -0,0 +1,1080 @@
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::ffi::OsStr;
+use std::str::FromStr;
+use toml::Value;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ip_addresses: Option<Vec<IpAddr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    ListError(String),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::ListError(err) => write!(f, "List Error: {}", err),
+        }
+    }
+}
+
+/// A check on one list element; returns why the element is not allowed.
+type ElementPolicy<T> = fn(&T) -> Result<(), String>;
+
+/// How `extract_parsed_list` reads one list field.
+///
+/// - `required`: a missing key is an error. Otherwise a missing key, or an
+///   empty list, reads as `None`.
+/// - `min_len`, `max_len`: allowed number of entries when the key is present.
+/// - `element_policies`: checked for every parsed element, in order.
+struct ListRules<T: 'static> {
+    required: bool,
+    min_len: usize,
+    max_len: usize,
+    element_policies: &'static [ElementPolicy<T>],
+}
+
+/// `user_salt_list`: at least one salt, no zero salts.
+const SALT_LIST_RULES: ListRules<u128> = ListRules {
+    required: true,
+    min_len: 1,
+    max_len: 64,
+    element_policies: &[reject_zero_salt],
+};
+
+fn reject_zero_salt(salt: &u128) -> Result<(), String> {
+    if *salt == 0 {
+        Err("a salt of zero is not allowed".into())
+    } else {
+        Ok(())
+    }
+}
+
+// Helper function: salts are written as "0x" + hex
+fn parse_hex_salt(s: &str) -> Result<u128, String> {
+    u128::from_str_radix(s.trim_start_matches("0x"), 16).map_err(|e| e.to_string())
+}
+
+// Helper function: the counterpart of `parse_hex_salt`
+fn format_hex_salt(salt: &u128) -> String {
+    format!("0x{:x}", salt)
+}
+
+/// Extracts a list of strings at `key` and parses each with `T::from_str`,
+/// under the same `rules` as `extract_parsed_list_with`.
+fn extract_parsed_list<T>(
+    table: &toml::map::Map<String, Value>,
+    key: &str,
+    rules: &ListRules<T>,
+) -> Result<Option<Vec<T>>, ThisProjectError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    extract_parsed_list_with(table, key, rules, |s| s.parse::<T>().map_err(|e| e.to_string()))
+}
+
+/// Extracts a list of strings at `key`, parsing each with `parse` and
+/// checking it against `rules`.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::ListError` naming the key, and for element
+/// errors the element index, e.g. `ipv4_addresses[2]: "bad": invalid IPv4
+/// address syntax`. Fails on the first error:
+/// - the key is missing and `rules.required`
+/// - the value is not an array
+/// - an element is not a string, does not parse, or fails a policy
+/// - the number of entries is outside `rules.min_len..=rules.max_len`
+fn extract_parsed_list_with<T>(
+    table: &toml::map::Map<String, Value>,
+    key: &str,
+    rules: &ListRules<T>,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<Option<Vec<T>>, ThisProjectError> {
+    let arr = match table.get(key) {
+        Some(Value::Array(arr)) => arr,
+        Some(_) => return Err(ThisProjectError::ListError(format!("{}: expected an array", key))),
+        None if rules.required => return Err(ThisProjectError::ListError(format!("Missing {}", key))),
+        None => return Ok(None),
+    };
+
+    if arr.len() < rules.min_len || arr.len() > rules.max_len {
+        return Err(ThisProjectError::ListError(format!(
+            "{}: {} entries, expected {} to {}",
+            key, arr.len(), rules.min_len, rules.max_len
+        )));
+    }
+
+    let mut items = Vec::with_capacity(arr.len());
+    for (index, val) in arr.iter().enumerate() {
+        let s = match val {
+            Value::String(s) => s,
+            _ => return Err(ThisProjectError::ListError(format!("{}[{}]: expected a string", key, index))),
+        };
+        let item = parse(s).map_err(|e| ThisProjectError::ListError(format!("{}[{}]: \"{}\": {}", key, index, s, e)))?;
+        for policy in rules.element_policies {
+            policy(&item).map_err(|e| ThisProjectError::ListError(format!("{}[{}]: \"{}\": {}", key, index, s, e)))?;
+        }
+        items.push(item);
+    }
+
+    if items.is_empty() && !rules.required {
+        Ok(None)
+    } else {
+        Ok(Some(items))
+    }
+}
+
+/*
+Unified address list
+
+`ip_addresses` holds IPv4 and IPv6 addresses together:
+
+    ip_addresses = [
+        "10.0.0.1",
+        "fe80::1",
+    ]
+
+The legacy `ipv4_addresses` and `ipv6_addresses` lists are still read, and
+either family is accepted in either list. On reading, all three lists are
+merged in the order ip_addresses, ipv4_addresses, ipv6_addresses; each
+entry is normalized (an IPv4-mapped address such as `::ffff:10.0.0.1`
+becomes `10.0.0.1`) and later duplicates are dropped. The 64-entry limit
+applies to each list in the file, not to the merged list.
+
+The serializer still writes `ipv4_addresses` and `ipv6_addresses`, split by
+family, so readers that predate `ip_addresses` see every address.
+*/
+
+/// `ip_addresses`, `ipv4_addresses`, `ipv6_addresses` as written in a file:
+/// optional, no unspecified address.
+const IP_LIST_RULES: ListRules<IpAddr> = ListRules {
+    required: false,
+    min_len: 0,
+    max_len: 64,
+    element_policies: &[reject_unspecified_ip],
+};
+
+/// The three source lists after they are merged into `ip_addresses`: each
+/// source is already limited by `IP_LIST_RULES`, so the merged list may hold
+/// up to all of their entries.
+const MERGED_IP_LIST_RULES: ListRules<IpAddr> = ListRules {
+    max_len: 3 * IP_LIST_RULES.max_len,
+    ..IP_LIST_RULES
+};
+
+fn reject_unspecified_ip(addr: &IpAddr) -> Result<(), String> {
+    if normalize_ip_addr(*addr).is_unspecified() {
+        Err(format!("the unspecified address {} is not a peer address", addr))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the canonical form of `addr`: IPv4-mapped IPv6 addresses
+/// (`::ffff:a.b.c.d`) become the IPv4 address; all others are unchanged.
+fn normalize_ip_addr(addr: IpAddr) -> IpAddr {
+    match addr {
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => IpAddr::V4(v4),
+            None => IpAddr::V6(v6),
+        },
+        IpAddr::V4(v4) => IpAddr::V4(v4),
+    }
+}
+
+/*
+Reachability classes
+
+    Loopback    127.0.0.0/8, ::1
+    LinkLocal   169.254.0.0/16, fe80::/10
+    Private     10.0.0.0/8, 172.16.0.0/12, 192.168.0.0/16,
+                100.64.0.0/10 (shared/CGNAT), fc00::/7 (unique local)
+    Special     not a usable peer address: unspecified, multicast,
+                broadcast, documentation, benchmarking and reserved ranges
+    Global      everything else
+
+Addresses are classified after `normalize_ip_addr`, so `::ffff:10.0.0.1`
+is Private.
+*/
+
+/// Where an address can be reached from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Reachability {
+    Loopback,
+    LinkLocal,
+    Private,
+    Global,
+    Special,
+}
+
+impl fmt::Display for Reachability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Reachability::Loopback => "loopback",
+            Reachability::LinkLocal => "link-local",
+            Reachability::Private => "private",
+            Reachability::Global => "global",
+            Reachability::Special => "special-purpose",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Classifies one address (see the table above).
+fn classify_ip_addr(addr: IpAddr) -> Reachability {
+    match normalize_ip_addr(addr) {
+        IpAddr::V4(v4) => {
+            let [a, b, c, _] = v4.octets();
+            if v4.is_loopback() {
+                Reachability::Loopback
+            } else if v4.is_link_local() {
+                Reachability::LinkLocal
+            } else if v4.is_private() || (a == 100 && (64..128).contains(&b)) {
+                Reachability::Private
+            } else if v4.is_unspecified()
+                || a == 0
+                || v4.is_multicast()
+                || a >= 240
+                || v4.is_documentation()
+                || (a == 198 && (b == 18 || b == 19))
+                || (a == 192 && b == 0 && c == 0)
+            {
+                Reachability::Special
+            } else {
+                Reachability::Global
+            }
+        }
+        IpAddr::V6(v6) => {
+            let segments = v6.segments();
+            if v6.is_loopback() {
+                Reachability::Loopback
+            } else if segments[0] & 0xffc0 == 0xfe80 {
+                Reachability::LinkLocal
+            } else if segments[0] & 0xfe00 == 0xfc00 {
+                Reachability::Private
+            } else if v6.is_unspecified()
+                || v6.is_multicast()
+                || (segments[0] == 0x2001 && segments[1] == 0x0db8)
+                || (segments[0] == 0x0100 && segments[1..4] == [0, 0, 0])
+            {
+                Reachability::Special
+            } else {
+                Reachability::Global
+            }
+        }
+    }
+}
+
+impl CollaboratorTomlData {
+    /// Every address in `ip_addresses` with its class, in file order.
+    fn classified_addresses(&self) -> Vec<(IpAddr, Reachability)> {
+        self.ip_addresses
+            .iter()
+            .flatten()
+            .map(|addr| (*addr, classify_ip_addr(*addr)))
+            .collect()
+    }
+}
+
+/// What `apply_address_policy` does with an address of a given class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReachabilityAction {
+    /// Keep it.
+    Allow,
+    /// Keep it and report a `Warning`.
+    Warn,
+    /// Remove it from the collaborator and report a `Warning`.
+    Drop,
+    /// Do not load the collaborator at all (an `Error`).
+    Reject,
+}
+
+/// Load-time address policy: one action per class, plus ordering.
+///
+/// - `prefer_global_ipv6`: move global IPv6 addresses to the front of
+///   `ip_addresses` (otherwise in file order), so they are tried first.
+#[derive(Debug, Clone)]
+struct AddressPolicy {
+    loopback: ReachabilityAction,
+    link_local: ReachabilityAction,
+    private: ReachabilityAction,
+    global: ReachabilityAction,
+    special: ReachabilityAction,
+    prefer_global_ipv6: bool,
+}
+
+impl AddressPolicy {
+    /// Everything is kept; special-purpose addresses are warned about.
+    fn development() -> Self {
+        AddressPolicy {
+            loopback: ReachabilityAction::Allow,
+            link_local: ReachabilityAction::Allow,
+            private: ReachabilityAction::Allow,
+            global: ReachabilityAction::Allow,
+            special: ReachabilityAction::Warn,
+            prefer_global_ipv6: false,
+        }
+    }
+
+    /// Loopback and special-purpose addresses are dropped, link-local ones
+    /// warned about, and global IPv6 is tried first.
+    fn production() -> Self {
+        AddressPolicy {
+            loopback: ReachabilityAction::Drop,
+            link_local: ReachabilityAction::Warn,
+            private: ReachabilityAction::Allow,
+            global: ReachabilityAction::Allow,
+            special: ReachabilityAction::Drop,
+            prefer_global_ipv6: true,
+        }
+    }
+
+    fn action_for(&self, reachability: Reachability) -> ReachabilityAction {
+        match reachability {
+            Reachability::Loopback => self.loopback,
+            Reachability::LinkLocal => self.link_local,
+            Reachability::Private => self.private,
+            Reachability::Global => self.global,
+            Reachability::Special => self.special,
+        }
+    }
+}
+
+impl Default for AddressPolicy {
+    fn default() -> Self {
+        AddressPolicy::development()
+    }
+}
+
+/// How serious a `Diagnostic` is.
+///
+/// - `Error`: the file could not be loaded; no collaborator is returned.
+/// - `Warning`: something was ignored; the collaborator is still returned.
+/// - `Note`: something was changed on reading (e.g. normalized); nothing
+///   was lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// One finding about one file.
+///
+/// - `file`: the file it is about, set by the directory loader.
+/// - `key`: the path of the value it is about, e.g. `ipv4_addresses[2]`,
+///   or `None` for the file as a whole.
+#[derive(Debug, Clone, PartialEq)]
+struct Diagnostic {
+    severity: Severity,
+    file: Option<PathBuf>,
+    key: Option<String>,
+    message: String,
+}
+
+impl Diagnostic {
+    fn new(severity: Severity, key: Option<&str>, message: String) -> Self {
+        Diagnostic {
+            severity,
+            file: None,
+            key: key.map(|k| k.to_string()),
+            message,
+        }
+    }
+
+    /// An `Error` for a `ThisProjectError` that stopped a file from loading.
+    fn from_error(err: &ThisProjectError) -> Self {
+        Diagnostic::new(Severity::Error, None, err.to_string())
+    }
+}
+
+/// e.g. `warning: alice__collaborator.toml: ipv4_addresses[2]: skipped "bad": invalid IP address syntax`
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: ", self.severity)?;
+        if let Some(file) = &self.file {
+            write!(f, "{}: ", file.display())?;
+        }
+        if let Some(key) = &self.key {
+            write!(f, "{}: ", key)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+/// All diagnostics of a load, in the order they were found.
+#[derive(Debug, Clone, Default)]
+struct Diagnostics {
+    items: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    fn push(&mut self, diagnostic: Diagnostic) {
+        self.items.push(diagnostic);
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.items.iter()
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.items.iter().filter(|d| d.severity == severity).count()
+    }
+
+    fn has_errors(&self) -> bool {
+        self.count(Severity::Error) > 0
+    }
+
+    /// Only the diagnostics at `severity` or more serious.
+    fn at_least(&self, severity: Severity) -> impl Iterator<Item = &Diagnostic> {
+        self.items.iter().filter(move |d| d.severity <= severity)
+    }
+}
+
+/// One line per diagnostic, then e.g. `1 error, 2 warnings, 0 notes`.
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for diagnostic in &self.items {
+            writeln!(f, "{}", diagnostic)?;
+        }
+        let plural = |n: usize, word: &str| if n == 1 { format!("{} {}", n, word) } else { format!("{} {}s", n, word) };
+        write!(
+            f,
+            "{}, {}, {}",
+            plural(self.count(Severity::Error), "error"),
+            plural(self.count(Severity::Warning), "warning"),
+            plural(self.count(Severity::Note), "note")
+        )
+    }
+}
+
+/// Keys a collaborator file may have; any other top-level key is a warning.
+const KNOWN_KEYS: [&str; 8] = [
+    "user_name",
+    "user_salt_list",
+    "ip_addresses",
+    "ipv4_addresses",
+    "ipv6_addresses",
+    "gpg_key_public",
+    "sync_interval",
+    "updated_at_timestamp",
+];
+
+/// The legacy address keys, with the key they are merged into on reading.
+/// The serializer still writes them (see `serialize_collaborator_to_toml`),
+/// so they are reported as notes, not as something to fix.
+const LEGACY_KEYS: [(&str, &str); 2] = [
+    ("ipv4_addresses", "ip_addresses"),
+    ("ipv6_addresses", "ip_addresses"),
+];
+
+/// Lenient counterpart of `extract_parsed_list_with`: an element that is
+/// not a string, does not parse or fails a policy is skipped with a
+/// `Warning`, as are entries beyond `rules.max_len`. A value that is not an
+/// array is ignored with a `Warning`. `rules.required` and `rules.min_len`
+/// are not checked; use the strict extractor for lists that must be complete.
+///
+/// Returns the kept elements with their index in the file's array.
+fn extract_parsed_list_skipping<T>(
+    table: &toml::map::Map<String, Value>,
+    key: &str,
+    rules: &ListRules<T>,
+    parse: impl Fn(&str) -> Result<T, String>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Vec<(usize, T)> {
+    let arr = match table.get(key) {
+        Some(Value::Array(arr)) => arr,
+        Some(_) => {
+            diagnostics.push(Diagnostic::new(Severity::Warning, Some(key), "expected an array; ignored".into()));
+            return Vec::new();
+        }
+        None => return Vec::new(),
+    };
+
+    let mut items = Vec::new();
+    for (index, val) in arr.iter().enumerate() {
+        let element_key = format!("{}[{}]", key, index);
+        if index >= rules.max_len {
+            diagnostics.push(Diagnostic::new(
+                Severity::Warning,
+                Some(&element_key),
+                format!("skipped: more than {} entries", rules.max_len),
+            ));
+            continue;
+        }
+        let s = match val {
+            Value::String(s) => s,
+            _ => {
+                diagnostics.push(Diagnostic::new(Severity::Warning, Some(&element_key), "skipped: expected a string".into()));
+                continue;
+            }
+        };
+        let checked = parse(s).and_then(|item| {
+            for policy in rules.element_policies {
+                policy(&item)?;
+            }
+            Ok(item)
+        });
+        match checked {
+            Ok(item) => items.push((index, item)),
+            Err(e) => diagnostics.push(Diagnostic::new(Severity::Warning, Some(&element_key), format!("skipped \"{}\": {}", s, e))),
+        }
+    }
+    items
+}
+
+/// Merges `ip_addresses`, `ipv4_addresses` and `ipv6_addresses` into
+/// `ip_addresses` in a TOML table, in that order, and removes the legacy
+/// keys. Entries that cannot be used are skipped with a `Warning` (see
+/// `extract_parsed_list_skipping`); IPv4-mapped entries are unwrapped and
+/// later duplicates dropped, each with a `Note`.
+///
+/// Returns the file key of each merged address (e.g. `ipv6_addresses[1]`),
+/// in the order of the new `ip_addresses`.
+fn merge_address_lists_with_diagnostics(table: &mut toml::map::Map<String, Value>, diagnostics: &mut Vec<Diagnostic>) -> Vec<String> {
+    let mut merged: Vec<IpAddr> = Vec::new();
+    let mut merged_keys = Vec::new();
+    for key in ["ip_addresses", "ipv4_addresses", "ipv6_addresses"] {
+        let entries = extract_parsed_list_skipping(table, key, &IP_LIST_RULES, |s| s.parse::<IpAddr>().map_err(|e| e.to_string()), diagnostics);
+        for (index, addr) in entries {
+            let normalized = normalize_ip_addr(addr);
+            let element_key = format!("{}[{}]", key, index);
+            if normalized != addr {
+                diagnostics.push(Diagnostic::new(Severity::Note, Some(&element_key), format!("{} read as {}", addr, normalized)));
+            }
+            if merged.contains(&normalized) {
+                diagnostics.push(Diagnostic::new(Severity::Note, Some(&element_key), format!("duplicate {} removed", normalized)));
+            } else {
+                merged.push(normalized);
+                merged_keys.push(element_key);
+            }
+        }
+    }
+
+    table.remove("ipv4_addresses");
+    table.remove("ipv6_addresses");
+    if merged.is_empty() {
+        table.remove("ip_addresses");
+    } else {
+        let values = merged.iter().map(|addr| Value::String(addr.to_string())).collect();
+        table.insert("ip_addresses".to_string(), Value::Array(values));
+    }
+    merged_keys
+}
+
+/// Applies `policy` to the addresses of `collaborator`: drops and reorders
+/// them as configured, with a `Warning` for each flagged or dropped address.
+/// `address_keys` holds the file key of each entry of `ip_addresses`, as
+/// returned by `merge_address_lists_with_diagnostics`, so the diagnostics
+/// point at the entry in the file.
+///
+/// Returns `false`, with an `Error` and `collaborator` unchanged, for the
+/// first address whose class is `ReachabilityAction::Reject`.
+fn apply_address_policy(
+    collaborator: &mut CollaboratorTomlData,
+    policy: &AddressPolicy,
+    address_keys: &[String],
+    diagnostics: &mut Vec<Diagnostic>,
+) -> bool {
+    let mut findings = Vec::new();
+    let mut kept = Vec::new();
+
+    for (index, (address, reachability)) in collaborator.classified_addresses().into_iter().enumerate() {
+        let key = address_keys.get(index).map(String::as_str);
+        let action = policy.action_for(reachability);
+        match action {
+            ReachabilityAction::Reject => {
+                diagnostics.push(Diagnostic::new(
+                    Severity::Error,
+                    key,
+                    format!("{} is {}, which the address policy rejects", address, reachability),
+                ));
+                return false;
+            }
+            ReachabilityAction::Warn => {
+                findings.push(Diagnostic::new(Severity::Warning, key, format!("{} is {}", address, reachability)));
+            }
+            ReachabilityAction::Drop => {
+                findings.push(Diagnostic::new(Severity::Warning, key, format!("{} is {}; dropped", address, reachability)));
+            }
+            ReachabilityAction::Allow => {}
+        }
+        if action != ReachabilityAction::Drop {
+            kept.push((address, reachability));
+        }
+    }
+
+    if policy.prefer_global_ipv6 {
+        // Stable: the order within each group is kept
+        kept.sort_by_key(|(address, reachability)| !(address.is_ipv6() && *reachability == Reachability::Global));
+    }
+
+    collaborator.ip_addresses = if kept.is_empty() {
+        None
+    } else {
+        Some(kept.into_iter().map(|(address, _)| address).collect())
+    };
+    diagnostics.extend(findings);
+    true
+}
+
+/// Reads one collaborator from TOML text, reporting everything found, and
+/// applies the address `policy` (see `apply_address_policy`).
+///
+/// Returns `None` (with at least one `Error`) if the text is not TOML, a
+/// required field is missing or invalid, or the policy rejects an address.
+/// Unknown keys, skipped addresses and addresses flagged or dropped by the
+/// policy are warnings; the collaborator is still returned. Legacy address
+/// keys and normalized or duplicate addresses are notes.
+fn collaborator_from_toml_with_diagnostics(
+    toml_string: &str,
+    policy: &AddressPolicy,
+) -> (Option<CollaboratorTomlData>, Vec<Diagnostic>) {
+    let mut diagnostics = Vec::new();
+
+    let mut table = match toml::from_str::<Value>(toml_string) {
+        Ok(Value::Table(table)) => table,
+        Ok(_) => {
+            let err = ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure: Expected a table".into());
+            diagnostics.push(Diagnostic::from_error(&err));
+            return (None, diagnostics);
+        }
+        Err(e) => {
+            let err = ThisProjectError::TomlVanillaDeserialStrError(e.to_string());
+            diagnostics.push(Diagnostic::from_error(&err));
+            return (None, diagnostics);
+        }
+    };
+
+    // Unknown keys
+    for key in table.keys() {
+        if !KNOWN_KEYS.contains(&key.as_str()) {
+            diagnostics.push(Diagnostic::new(Severity::Warning, Some(key), "unknown key; ignored".into()));
+        }
+    }
+
+    // Legacy address keys
+    for (key, replacement) in LEGACY_KEYS.iter() {
+        if table.contains_key(*key) {
+            diagnostics.push(Diagnostic::new(
+                Severity::Note,
+                Some(key),
+                format!("merged into {}", replacement),
+            ));
+        }
+    }
+
+    // Addresses: skip what cannot be used
+    let address_keys = merge_address_lists_with_diagnostics(&mut table, &mut diagnostics);
+
+    // Everything else must be valid
+    let mut collaborator = match collaborator_from_toml_table(&table) {
+        Ok(collaborator) => collaborator,
+        Err(e) => {
+            diagnostics.push(Diagnostic::from_error(&e));
+            return (None, diagnostics);
+        }
+    };
+
+    // Addresses the policy does not allow
+    if apply_address_policy(&mut collaborator, policy, &address_keys, &mut diagnostics) {
+        (Some(collaborator), diagnostics)
+    } else {
+        (None, diagnostics)
+    }
+}
+
+// Helper function: TOML table -> CollaboratorTomlData, failing on the first bad field
+fn collaborator_from_toml_table(table: &toml::map::Map<String, Value>) -> Result<CollaboratorTomlData, ThisProjectError> {
+
+    // Extract user_name
+    let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+    };
+
+    // Extract user_salt_list (required, so never None)
+    let user_salt_list = extract_parsed_list_with(table, "user_salt_list", &SALT_LIST_RULES, parse_hex_salt)?
+        .unwrap_or_default();
+
+    // Extract ip_addresses (after merge_address_lists, the only address list)
+    let ip_addresses = extract_parsed_list::<IpAddr>(table, "ip_addresses", &MERGED_IP_LIST_RULES)?;
+
+    // Extract gpg_key_public
+    let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+    };
+
+    // Extract sync_interval
+    let sync_interval = extract_u64(table, "sync_interval")?;
+
+    // Extract updated_at_timestamp
+    let updated_at_timestamp = extract_u64(table, "updated_at_timestamp")?;
+
+    Ok(CollaboratorTomlData {
+        user_name,
+        user_salt_list,
+        ip_addresses,
+        gpg_key_public,
+        sync_interval,
+        updated_at_timestamp,
+    })
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+/// Toml Deserialization: Reads collaborator setup data from TOML files in a
+/// specified directory, applying an address policy.
+///
+/// Every `*.toml` file in `project_graph_data/collaborator_files_address_book`
+/// is read with `collaborator_from_toml_with_diagnostics`, and its findings
+/// are added to one `Diagnostics`, each with the path of its file. A file
+/// with an `Error` is skipped; with only warnings and notes its collaborator
+/// is still returned.
+///
+/// # Returns
+///
+/// Returns a `Result` containing:
+/// - `Ok`: A tuple with:
+///     - A vector of successfully parsed `CollaboratorTomlData` instances.
+///     - The `Diagnostics` for all files.
+/// - `Err`: A `ThisProjectError` if there was an error reading the directory or any file.
+fn read_a_collaborator_setup_toml(policy: &AddressPolicy) -> Result<(Vec<CollaboratorTomlData>, Diagnostics), ThisProjectError> {
+    let mut collaborators = Vec::new();
+    let mut diagnostics = Diagnostics::default();
+    let dir_path = Path::new("project_graph_data/collaborator_files_address_book");
+
+    for entry in fs::read_dir(dir_path)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
+            let toml_string = fs::read_to_string(&path)?;
+
+            let (collaborator, file_diagnostics) = collaborator_from_toml_with_diagnostics(&toml_string, policy);
+            for mut diagnostic in file_diagnostics {
+                diagnostic.file = Some(path.clone());
+                diagnostics.push(diagnostic);
+            }
+            if let Some(collaborator) = collaborator {
+                collaborators.push(collaborator);
+            }
+        }
+    }
+
+    Ok((collaborators, diagnostics))
+}
+
+/// Serializes a `CollaboratorTomlData` struct into a TOML-formatted string.
+///
+/// One `key = value` line per field, in struct order, with escaped strings
+/// and salts as `0x` hex strings. The addresses are normalized and written as
+/// `ipv4_addresses` and `ipv6_addresses`, grouped by family, so every reader
+/// sees them; an empty list is left out.
+///
+/// # Error Handling
+///
+/// Returns an error if either address list would have more entries than
+/// `IP_LIST_RULES` allows, since the file could not be read back.
+fn serialize_collaborator_to_toml(collaborator: &CollaboratorTomlData) -> Result<String, ThisProjectError> {
+    let mut toml_string = String::new();
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", escape_toml_basic_string(&collaborator.user_name)));
+
+    // Add user_salt_list
+    serialize_list_with(&mut toml_string, "user_salt_list", &collaborator.user_salt_list, format_hex_salt);
+
+    // Add ipv4_addresses and ipv6_addresses
+    if let Some(addresses) = &collaborator.ip_addresses {
+        let (ipv4, ipv6): (Vec<IpAddr>, Vec<IpAddr>) =
+            addresses.iter().map(|addr| normalize_ip_addr(*addr)).partition(IpAddr::is_ipv4);
+        serialize_address_list(&mut toml_string, "ipv4_addresses", &ipv4)?;
+        serialize_address_list(&mut toml_string, "ipv6_addresses", &ipv6)?;
+    }
+
+    // Add gpg_key_public
+    toml_string.push_str(&format!("gpg_key_public = \"{}\"\n", escape_toml_basic_string(&collaborator.gpg_key_public)));
+
+    // Add sync_interval
+    toml_string.push_str(&format!("sync_interval = {}\n", collaborator.sync_interval));
+
+    // Add updated_at_timestamp
+    toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp));
+
+    Ok(toml_string)
+}
+
+// Helper function to write one address list, or nothing if it is empty
+fn serialize_address_list(toml_string: &mut String, key: &str, addresses: &[IpAddr]) -> Result<(), ThisProjectError> {
+    if addresses.len() > IP_LIST_RULES.max_len {
+        return Err(ThisProjectError::ListError(format!(
+            "{}: {} entries, at most {} can be written",
+            key, addresses.len(), IP_LIST_RULES.max_len
+        )));
+    }
+    if !addresses.is_empty() {
+        serialize_list(toml_string, key, addresses);
+    }
+    Ok(())
+}
+
+/// Writes `items` as a multi-line TOML array of strings using `Display`.
+fn serialize_list<T: fmt::Display>(toml_string: &mut String, key: &str, items: &[T]) {
+    serialize_list_with(toml_string, key, items, |item| item.to_string());
+}
+
+/// Writes `items` as a multi-line TOML array of strings, formatting each
+/// with `format` (the counterpart of the `parse` in `extract_parsed_list_with`).
+fn serialize_list_with<T>(toml_string: &mut String, key: &str, items: &[T], format: impl Fn(&T) -> String) {
+    toml_string.push_str(&format!("{} = [\n", key));
+    for item in items {
+        toml_string.push_str(&format!("    \"{}\",\n", escape_toml_basic_string(&format(item))));
+    }
+    toml_string.push_str("]\n");
+}
+
+// Helper function to escape a value for a TOML basic string ("...")
+fn escape_toml_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Function to write a TOML string to a file
+fn write_toml_to_file(file_path: &str, toml_string: &str) -> Result<(), ThisProjectError> {
+    // Attempt to create the file.
+    let mut file = match File::create(file_path) {
+        Ok(file) => file,
+        Err(e) => return Err(ThisProjectError::IoError(e)),
+    };
+
+    // Attempt to write to the file.
+    if let Err(e) = file.write_all(toml_string.as_bytes()) {
+        return Err(ThisProjectError::IoError(e));
+    }
+
+    // Everything successful!
+    Ok(())
+}
+
+fn main() {
+    // One file with something at every severity, read under the production policy
+    let messy_toml = "user_name = \"Carol\"\nuser_salt_list = [\"0x1\"]\n\
+        ipv4_addresses = [\"10.0.0.1\", \"bad\", \"127.0.0.1\"]\n\
+        ipv6_addresses = [\"::ffff:10.0.0.1\", \"fe80::1\"]\n\
+        gpg_key_public = \"\"\nsync_interval = 60\nupdated_at_timestamp = 0\nnickname = \"cj\"\n";
+    let (collaborator, diagnostics) = collaborator_from_toml_with_diagnostics(messy_toml, &AddressPolicy::production());
+    for diagnostic in &diagnostics {
+        println!("{}", diagnostic);
+    }
+    if let Some(collaborator) = collaborator {
+        println!("Loaded {} with {:?}", collaborator.user_name, collaborator.ip_addresses);
+    }
+
+    // The same file under a policy that rejects loopback outright
+    let strict_policy = AddressPolicy {
+        loopback: ReachabilityAction::Reject,
+        ..AddressPolicy::production()
+    };
+    let (collaborator, diagnostics) = collaborator_from_toml_with_diagnostics(messy_toml, &strict_policy);
+    for diagnostic in diagnostics.iter().filter(|d| d.severity == Severity::Error) {
+        println!("{}", diagnostic);
+    }
+    println!("Loaded under the strict policy: {}", collaborator.is_some());
+
+    // Example CollaboratorTomlData instance
+    let collaborator = CollaboratorTomlData {
+        user_name: "Bob".to_string(),
+        user_salt_list: vec![0x123456789abcdef0, 0xabcdef0123456789],
+        ip_addresses: Some(vec![
+            IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)),
+            IpAddr::V6(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1)),
+        ]),
+        gpg_key_public: "-----BEGIN PGP PUBLIC KEY BLOCK----- ...".to_string(),
+        sync_interval: 300,
+        updated_at_timestamp: 1728308000,
+    };
+
+    match serialize_collaborator_to_toml(&collaborator) {
+        Ok(toml_string) => {
+            println!("Serialized TOML:\n{}", toml_string);
+
+            // Write the TOML string to a file (example file path)
+            match write_toml_to_file("collaborator_data.toml", &toml_string) {
+                Ok(_) => println!("TOML data written to file successfully."),
+                Err(e) => println!("Error writing to file: {}", e),
+            }
+        }
+        Err(e) => println!("Error serializing to TOML: {}", e),
+    }
+
+    match read_a_collaborator_setup_toml(&AddressPolicy::production()) {
+        Ok((collaborators, diagnostics)) => {
+            println!("Diagnostics:\n{}", diagnostics);
+            if diagnostics.has_errors() {
+                println!("Errors only:");
+                for diagnostic in diagnostics.at_least(Severity::Error) {
+                    println!("{}", diagnostic);
+                }
+            }
+            for diagnostic in diagnostics.iter().filter(|d| d.severity == Severity::Warning) {
+                println!("Warning for {}: {}", diagnostic.key.as_deref().unwrap_or("(file)"), diagnostic.message);
+            }
+
+            println!("Collaborators:");
+            for collaborator in collaborators {
+                println!("{:#?}", collaborator);
+            }
+        }
+        Err(e) => {
+            println!("Error reading TOML files: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collaborator_toml(address_lines: &str) -> String {
+        format!(
+            "user_name = \"Carol\"\nuser_salt_list = [\"0x1\"]\n{}gpg_key_public = \"\"\nsync_interval = 60\nupdated_at_timestamp = 0\n",
+            address_lines
+        )
+    }
+
+    fn warnings(diagnostics: &[Diagnostic]) -> Vec<(Option<&str>, &str)> {
+        diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Warning)
+            .map(|d| (d.key.as_deref(), d.message.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn bad_entries_are_skipped_with_warnings() {
+        let toml_string = collaborator_toml("ipv4_addresses = [\"10.0.0.1\", \"bad\", 5]\nnickname = \"caz\"\n");
+        let (collaborator, diagnostics) = collaborator_from_toml_with_diagnostics(&toml_string, &AddressPolicy::default());
+        assert_eq!(
+            warnings(&diagnostics),
+            vec![
+                (Some("nickname"), "unknown key; ignored"),
+                (Some("ipv4_addresses[1]"), "skipped \"bad\": invalid IP address syntax"),
+                (Some("ipv4_addresses[2]"), "skipped: expected a string"),
+            ]
+        );
+        assert_eq!(collaborator.unwrap().ip_addresses.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn policy_findings_are_warnings_at_the_file_key() {
+        let toml_string = collaborator_toml("ipv4_addresses = [\"10.0.0.1\", \"fe80::1\"]\nipv6_addresses = [\"::1\"]\n");
+        let (collaborator, diagnostics) = collaborator_from_toml_with_diagnostics(&toml_string, &AddressPolicy::production());
+        assert_eq!(
+            warnings(&diagnostics),
+            vec![
+                (Some("ipv4_addresses[1]"), "fe80::1 is link-local"),
+                (Some("ipv6_addresses[0]"), "::1 is loopback; dropped"),
+            ]
+        );
+        assert_eq!(collaborator.unwrap().ip_addresses.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn rejected_address_is_an_error_and_no_collaborator() {
+        let toml_string = collaborator_toml("ip_addresses = [\"10.0.0.1\", \"127.0.0.1\"]\n");
+        let policy = AddressPolicy {
+            loopback: ReachabilityAction::Reject,
+            ..AddressPolicy::production()
+        };
+        let (collaborator, diagnostics) = collaborator_from_toml_with_diagnostics(&toml_string, &policy);
+        assert!(collaborator.is_none());
+        let error = diagnostics.iter().find(|d| d.severity == Severity::Error).unwrap();
+        assert_eq!(error.key.as_deref(), Some("ip_addresses[1]"));
+    }
+
+    #[test]
+    fn written_file_loads_without_warnings() {
+        let toml_string = collaborator_toml("ip_addresses = [\"fe80::1\", \"::ffff:10.0.0.2\"]\n");
+        let (collaborator, _) = collaborator_from_toml_with_diagnostics(&toml_string, &AddressPolicy::default());
+        let written = serialize_collaborator_to_toml(&collaborator.unwrap()).unwrap();
+        assert!(written.contains("ipv4_addresses = [\n    \"10.0.0.2\",\n]\n"), "{}", written);
+
+        let (reread, diagnostics) = collaborator_from_toml_with_diagnostics(&written, &AddressPolicy::default());
+        assert!(reread.is_some());
+        assert_eq!(warnings(&diagnostics), vec![]);
+        let notes: Vec<Option<&str>> = diagnostics.iter().map(|d| d.key.as_deref()).collect();
+        assert_eq!(notes, vec![Some("ipv4_addresses"), Some("ipv6_addresses")]);
+    }
+}
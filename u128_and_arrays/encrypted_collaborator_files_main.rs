@@ -0,0 +1,1128 @@
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use toml::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    EncryptionError(String),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::EncryptionError(err) => write!(f, "Encryption Error: {}", err),
+        }
+    }
+}
+
+/*
+Encrypted container format
+
+An encrypted `{name}__collaborator.toml` keeps its file name, but holds
+binary data instead of TOML text:
+
+    offset  size  field
+    0       4     magic "CTEN"
+    4       1     format version (2)
+    5       4     PBKDF2 iteration count (u32, big-endian)
+    9       16    PBKDF2 salt (random per write)
+    25      12    ChaCha20-Poly1305 nonce (random per write)
+    37      n     ciphertext of the TOML text (UTF-8)
+    37+n    16    Poly1305 tag
+
+The key is PBKDF2-HMAC-SHA256(passphrase, salt, iterations), 32 bytes. The
+AEAD associated data is the 37 header bytes followed by the collaborator
+name the file belongs to (the `{name}` of its file name, UTF-8). Changing
+any header byte (for example lowering the iteration count) fails
+authentication, and so does copying `alice__collaborator.toml` over
+`bob__collaborator.toml`: all files share one passphrase, so without the
+name any valid container would decrypt under any file name.
+
+Version 1 containers bound only the header; they are refused as an
+unsupported version and have to be re-encrypted.
+
+A TOML file never starts with "CTEN" followed by a byte from 0x01 to 0x08
+(control characters TOML does not allow there), so plaintext and encrypted
+files, including those of future format versions, can be told apart by
+their first five bytes.
+*/
+
+const ENCRYPTED_FILE_MAGIC: &[u8; 4] = b"CTEN";
+const ENCRYPTED_FILE_VERSION: u8 = 2;
+const ENCRYPTED_HEADER_LEN: usize = 4 + 1 + 4 + 16 + 12;
+const POLY1305_TAG_LEN: usize = 16;
+
+/// PBKDF2 iteration count for newly written files (OWASP's 2023
+/// recommendation for PBKDF2-HMAC-SHA256).
+const DEFAULT_KDF_ITERATIONS: u32 = 600_000;
+
+/// Files asking for more iterations than this are refused, so that a
+/// planted file cannot make a reader spin for hours.
+const MAX_KDF_ITERATIONS: u32 = 10_000_000;
+
+/// The OS entropy source for salts and nonces.
+const ENTROPY_SOURCE_PATH: &str = "/dev/urandom";
+
+/// Passphrase and settings for encrypting collaborator files at rest.
+///
+/// Pass `Some(&encryption)` to `read_one_collaborator_setup_toml` and
+/// `write_toml_to_file` to read and write encrypted files; pass `None` to
+/// keep using plaintext TOML.
+///
+/// - `kdf_iterations`: PBKDF2 iterations for files written with this
+///   configuration. Reading always uses the count stored in the file.
+///   Default `DEFAULT_KDF_ITERATIONS`.
+/// - `allow_plaintext_read`: whether a plaintext file may still be read
+///   while encryption is configured (useful while converting a directory).
+///   Default `false`: a plaintext file is an error, so that an encrypted
+///   file cannot silently be swapped for a plaintext one.
+///
+/// `Debug` does not print the passphrase, and the passphrase is overwritten
+/// with zeros on drop.
+struct AtRestEncryption {
+    passphrase: String,
+    kdf_iterations: u32,
+    allow_plaintext_read: bool,
+}
+
+impl AtRestEncryption {
+    fn new(passphrase: &str) -> AtRestEncryption {
+        AtRestEncryption {
+            passphrase: passphrase.to_string(),
+            kdf_iterations: DEFAULT_KDF_ITERATIONS,
+            allow_plaintext_read: false,
+        }
+    }
+}
+
+impl fmt::Debug for AtRestEncryption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AtRestEncryption")
+            .field("passphrase", &"<redacted>")
+            .field("kdf_iterations", &self.kdf_iterations)
+            .field("allow_plaintext_read", &self.allow_plaintext_read)
+            .finish()
+    }
+}
+
+impl Drop for AtRestEncryption {
+    fn drop(&mut self) {
+        // Safety: zero bytes are valid UTF-8, so the String stays valid
+        unsafe {
+            for byte in self.passphrase.as_bytes_mut() {
+                std::ptr::write_volatile(byte, 0);
+            }
+        }
+        std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+/// Returns `true` if `file_bytes` start like an encrypted container (of any
+/// format version).
+fn is_encrypted_collaborator_file(file_bytes: &[u8]) -> bool {
+    file_bytes.len() >= 5 && &file_bytes[..4] == ENCRYPTED_FILE_MAGIC && (0x01..=0x08).contains(&file_bytes[4])
+}
+
+/// Encrypts TOML text into the container format described at the top of
+/// this section, with a fresh random salt and nonce, bound to
+/// `collaborator_name` (see `encrypted_file_aad`).
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::IoError` if `/dev/urandom` cannot be read and
+/// `ThisProjectError::EncryptionError` for an iteration count of 0 or above
+/// `MAX_KDF_ITERATIONS`.
+fn encrypt_toml_string(
+    toml_string: &str,
+    collaborator_name: &str,
+    encryption: &AtRestEncryption,
+) -> Result<Vec<u8>, ThisProjectError> {
+    let iterations = encryption.kdf_iterations;
+    if iterations == 0 || iterations > MAX_KDF_ITERATIONS {
+        return Err(ThisProjectError::EncryptionError(format!(
+            "kdf_iterations must be between 1 and {}, got {}",
+            MAX_KDF_ITERATIONS, iterations
+        )));
+    }
+
+    let mut random = [0u8; 28];
+    File::open(ENTROPY_SOURCE_PATH)?.read_exact(&mut random)?;
+    let (kdf_salt, nonce) = random.split_at(16);
+
+    let mut header = Vec::with_capacity(ENCRYPTED_HEADER_LEN);
+    header.extend_from_slice(ENCRYPTED_FILE_MAGIC);
+    header.push(ENCRYPTED_FILE_VERSION);
+    header.extend_from_slice(&iterations.to_be_bytes());
+    header.extend_from_slice(kdf_salt);
+    header.extend_from_slice(nonce);
+
+    let key = pbkdf2_hmac_sha256(encryption.passphrase.as_bytes(), kdf_salt, iterations);
+    let mut nonce_array = [0u8; 12];
+    nonce_array.copy_from_slice(nonce);
+
+    let aad = encrypted_file_aad(&header, collaborator_name);
+    let mut file_bytes = header;
+    file_bytes.extend_from_slice(&chacha20_poly1305_seal(&key, &nonce_array, &aad, toml_string.as_bytes()));
+    Ok(file_bytes)
+}
+
+// Helper function: AEAD associated data, the container header followed by
+// the collaborator name
+fn encrypted_file_aad(header: &[u8], collaborator_name: &str) -> Vec<u8> {
+    let mut aad = header.to_vec();
+    aad.extend_from_slice(collaborator_name.as_bytes());
+    aad
+}
+
+/// Decrypts the encrypted container of `collaborator_name` back into TOML
+/// text.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::EncryptionError` if the data is not a
+/// container of a known version, is truncated, asks for too many KDF
+/// iterations, fails authentication (wrong passphrase, a changed file, or
+/// another collaborator's file: these cannot be told apart), or does not
+/// decrypt to UTF-8.
+fn decrypt_collaborator_file_bytes(
+    file_bytes: &[u8],
+    collaborator_name: &str,
+    encryption: &AtRestEncryption,
+) -> Result<String, ThisProjectError> {
+    if file_bytes.len() < 4 || &file_bytes[..4] != ENCRYPTED_FILE_MAGIC {
+        return Err(ThisProjectError::EncryptionError("Not an encrypted collaborator file".into()));
+    }
+    if file_bytes.len() < ENCRYPTED_HEADER_LEN + POLY1305_TAG_LEN {
+        return Err(ThisProjectError::EncryptionError("Encrypted file is truncated".into()));
+    }
+    if file_bytes[4] != ENCRYPTED_FILE_VERSION {
+        return Err(ThisProjectError::EncryptionError(format!(
+            "Unsupported encrypted file version {}",
+            file_bytes[4]
+        )));
+    }
+
+    let (header, sealed) = file_bytes.split_at(ENCRYPTED_HEADER_LEN);
+    let iterations = u32::from_be_bytes([header[5], header[6], header[7], header[8]]);
+    if iterations == 0 || iterations > MAX_KDF_ITERATIONS {
+        return Err(ThisProjectError::EncryptionError(format!(
+            "Encrypted file asks for {} KDF iterations (allowed: 1 to {})",
+            iterations, MAX_KDF_ITERATIONS
+        )));
+    }
+    let kdf_salt = &header[9..25];
+    let mut nonce = [0u8; 12];
+    nonce.copy_from_slice(&header[25..37]);
+
+    let key = pbkdf2_hmac_sha256(encryption.passphrase.as_bytes(), kdf_salt, iterations);
+    let aad = encrypted_file_aad(header, collaborator_name);
+    let plaintext = chacha20_poly1305_open(&key, &nonce, &aad, sealed).ok_or_else(|| {
+        ThisProjectError::EncryptionError(format!(
+            "Wrong passphrase, or the file has been tampered with or is not {}'s",
+            collaborator_name
+        ))
+    })?;
+
+    String::from_utf8(plaintext)
+        .map_err(|_| ThisProjectError::EncryptionError("Decrypted file is not valid UTF-8".into()))
+}
+
+// Helper function: file bytes -> TOML text, decrypting if needed
+fn collaborator_file_bytes_to_toml_string(
+    file_bytes: Vec<u8>,
+    collaborator_name: &str,
+    encryption: Option<&AtRestEncryption>,
+) -> Result<String, ThisProjectError> {
+    match (is_encrypted_collaborator_file(&file_bytes), encryption) {
+        (true, Some(encryption)) => decrypt_collaborator_file_bytes(&file_bytes, collaborator_name, encryption),
+        (true, None) => Err(ThisProjectError::EncryptionError(
+            "File is encrypted, but no passphrase is configured".into(),
+        )),
+        (false, Some(encryption)) if !encryption.allow_plaintext_read => Err(ThisProjectError::EncryptionError(
+            "File is not encrypted, but encryption is configured (set allow_plaintext_read to read it)".into(),
+        )),
+        (false, _) => String::from_utf8(file_bytes)
+            .map_err(|e| ThisProjectError::TomlVanillaDeserialStrError(format!("File is not valid UTF-8: {}", e))),
+    }
+}
+
+/// True if a file named `{collaborator_name}__collaborator.toml` may hold
+/// `user_name`. File names are the lowercased user name, as in
+/// `alice__collaborator.toml` holding `user_name = "Alice"`, so case is
+/// ignored.
+fn user_name_matches_file_name(user_name: &str, collaborator_name: &str) -> bool {
+    user_name.to_lowercase() == collaborator_name.to_lowercase()
+}
+
+/// Vanilla-Rust File Deserialization
+/// Reads collaborator setup data from a TOML file for a specific user.
+///
+/// Reads `project_graph_data/collaborator_files_address_book/{collaborator_name}__collaborator.toml`
+/// and stops at the first problem, which is returned as the error. The
+/// file's `user_name` must match `collaborator_name` (see
+/// `user_name_matches_file_name`), so a file copied over another
+/// collaborator's is refused whether or not it is encrypted.
+///
+/// Encryption at rest:
+///
+/// - `encryption: None`: the file must be plaintext TOML; an encrypted file
+///   gives `ThisProjectError::EncryptionError`.
+/// - `encryption: Some(..)`: an encrypted file is decrypted first; a wrong
+///   passphrase or a modified file gives `ThisProjectError::EncryptionError`.
+///   A plaintext file is only read if `allow_plaintext_read` is set.
+///
+/// # Use with:
+/// let encryption = AtRestEncryption::new(&passphrase);
+/// let collaborator = read_one_collaborator_setup_toml("alice", Some(&encryption))?;
+fn read_one_collaborator_setup_toml(
+    collaborator_name: &str,
+    encryption: Option<&AtRestEncryption>,
+) -> Result<CollaboratorTomlData, ThisProjectError> {
+
+    // 1. Construct File Path
+    let file_path = Path::new("project_graph_data/collaborator_files_address_book")
+        .join(format!("{}__collaborator.toml", collaborator_name));
+
+    // 2. Read TOML File (decrypting if needed)
+    let toml_string = collaborator_file_bytes_to_toml_string(fs::read(&file_path)?, collaborator_name, encryption)?;
+
+    // 3. Parse TOML Data (handle potential toml::de::Error)
+    let toml_value = match toml::from_str::<Value>(&toml_string) {
+        Ok(value) => value,
+        Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(e.to_string())),
+    };
+
+    // 4. Extract Data from TOML Value
+    if let Value::Table(table) = toml_value {
+
+        // Extract user_name
+        let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+            s.clone()
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+        };
+        if !user_name_matches_file_name(&user_name, collaborator_name) {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError(format!(
+                "{} holds user_name \"{}\", expected \"{}\"",
+                file_path.display(),
+                user_name,
+                collaborator_name
+            )));
+        }
+
+        // Extract user_salt_list
+        let user_salt_list = if let Some(Value::Array(arr)) = table.get("user_salt_list") {
+            arr.iter()
+                .map(|val| {
+                    if let Value::String(s) = val {
+                        u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                            .map_err(ThisProjectError::ParseIntError)
+                    } else {
+                        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                    }
+                })
+                .collect::<Result<Vec<u128>, ThisProjectError>>()?
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_salt_list".into()));
+        };
+
+        // Extract ipv4_addresses
+        let ipv4_addresses = extract_ipv4_addresses(&table, "ipv4_addresses")?;
+
+        // Extract ipv6_addresses
+        let ipv6_addresses = extract_ipv6_addresses(&table, "ipv6_addresses")?;
+
+        // Extract gpg_key_public
+        let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+            s.clone()
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+        };
+
+        // Extract sync_interval
+        let sync_interval = extract_u64(&table, "sync_interval")?;
+
+        // Extract updated_at_timestamp
+        let updated_at_timestamp = extract_u64(&table, "updated_at_timestamp")?;
+
+        // 5. Return CollaboratorTomlData
+        Ok(CollaboratorTomlData {
+            user_name,
+            user_salt_list,
+            ipv4_addresses,
+            ipv6_addresses,
+            gpg_key_public,
+            sync_interval,
+            updated_at_timestamp,
+        })
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure: Expected a table".into()))
+    }
+}
+
+fn extract_ipv4_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv4Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv4Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+fn extract_ipv6_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv6Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv6Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+/// Serializes a `CollaboratorTomlData` struct into a TOML-formatted string.
+///
+/// One `key = value` line per field, in struct order; salts are written as
+/// `0x` hex strings and an IP list that is `None` is left out. Encryption
+/// happens later, in `write_toml_to_file`.
+fn serialize_collaborator_to_toml(collaborator: &CollaboratorTomlData) -> Result<String, ThisProjectError> {
+    let mut toml_string = String::new();
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", collaborator.user_name));
+
+    // Add user_salt_list
+    toml_string.push_str("user_salt_list = [\n");
+    for salt in &collaborator.user_salt_list {
+        toml_string.push_str(&format!("    \"0x{:x}\",\n", salt));
+    }
+    toml_string.push_str("]\n");
+
+    // Add ipv4_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv4_addresses", &collaborator.ipv4_addresses)?;
+
+    // Add ipv6_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv6_addresses", &collaborator.ipv6_addresses)?;
+
+    // Add gpg_key_public
+    toml_string.push_str(&format!("gpg_key_public = \"{}\"\n", collaborator.gpg_key_public));
+
+    // Add sync_interval
+    toml_string.push_str(&format!("sync_interval = {}\n", collaborator.sync_interval));
+
+    // Add updated_at_timestamp
+    toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp));
+
+    Ok(toml_string)
+}
+
+// Helper function to serialize IP addresses to TOML array format
+fn serialize_ip_addresses<T: std::fmt::Display>(
+    toml_string: &mut String,
+    key: &str,
+    addresses: &Option<Vec<T>>
+) -> Result<(), ThisProjectError> {
+    if let Some(addr_vec) = addresses {
+        toml_string.push_str(&format!("{} = [\n", key));
+        for addr in addr_vec {
+            toml_string.push_str(&format!("    \"{}\",\n", addr));
+        }
+        toml_string.push_str("]\n");
+    }
+    Ok(()) // Return Ok(()) if the addresses field is None
+}
+
+// Function to write a TOML string to a file
+// With `Some(encryption)` the file is written as an encrypted container
+// bound to `collaborator_name` (see `encrypt_toml_string`), otherwise as
+// plaintext TOML.
+fn write_toml_to_file(
+    file_path: &str,
+    collaborator_name: &str,
+    toml_string: &str,
+    encryption: Option<&AtRestEncryption>,
+) -> Result<(), ThisProjectError> {
+    // Encrypt before creating the file, so a failure leaves any old file as it was
+    let file_bytes = match encryption {
+        Some(encryption) => encrypt_toml_string(toml_string, collaborator_name, encryption)?,
+        None => toml_string.as_bytes().to_vec(),
+    };
+
+    // Attempt to create the file.
+    let mut file = match File::create(file_path) {
+        Ok(file) => file,
+        Err(e) => return Err(ThisProjectError::IoError(e)),
+    };
+
+    // Attempt to write to the file.
+    if let Err(e) = file.write_all(&file_bytes) {
+        return Err(ThisProjectError::IoError(e));
+    }
+
+    // Everything successful!
+    Ok(())
+}
+
+/*
+ChaCha20-Poly1305 AEAD (RFC 8439), implemented here without a crypto
+dependency.
+*/
+
+// ChaCha20 quarter round on four words of the state
+fn chacha20_quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+/// One 64-byte ChaCha20 keystream block (RFC 8439, section 2.3).
+fn chacha20_block(key: &[u8; 32], counter: u32, nonce: &[u8; 12]) -> [u8; 64] {
+    let word = |bytes: &[u8], i: usize| u32::from_le_bytes([bytes[i * 4], bytes[i * 4 + 1], bytes[i * 4 + 2], bytes[i * 4 + 3]]);
+
+    let mut initial = [0u32; 16];
+    initial[0] = 0x61707865; // "expand 32-byte k"
+    initial[1] = 0x3320646e;
+    initial[2] = 0x79622d32;
+    initial[3] = 0x6b206574;
+    for i in 0..8 {
+        initial[4 + i] = word(key, i);
+    }
+    initial[12] = counter;
+    for i in 0..3 {
+        initial[13 + i] = word(nonce, i);
+    }
+
+    let mut state = initial;
+    for _ in 0..10 {
+        // Column rounds
+        chacha20_quarter_round(&mut state, 0, 4, 8, 12);
+        chacha20_quarter_round(&mut state, 1, 5, 9, 13);
+        chacha20_quarter_round(&mut state, 2, 6, 10, 14);
+        chacha20_quarter_round(&mut state, 3, 7, 11, 15);
+        // Diagonal rounds
+        chacha20_quarter_round(&mut state, 0, 5, 10, 15);
+        chacha20_quarter_round(&mut state, 1, 6, 11, 12);
+        chacha20_quarter_round(&mut state, 2, 7, 8, 13);
+        chacha20_quarter_round(&mut state, 3, 4, 9, 14);
+    }
+
+    let mut block = [0u8; 64];
+    for i in 0..16 {
+        block[i * 4..i * 4 + 4].copy_from_slice(&state[i].wrapping_add(initial[i]).to_le_bytes());
+    }
+    block
+}
+
+/// ChaCha20 encryption/decryption (the same operation), starting at block
+/// `initial_counter` (RFC 8439, section 2.4).
+fn chacha20_xor(key: &[u8; 32], initial_counter: u32, nonce: &[u8; 12], data: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(data.len());
+    for (i, chunk) in data.chunks(64).enumerate() {
+        let keystream = chacha20_block(key, initial_counter.wrapping_add(i as u32), nonce);
+        output.extend(chunk.iter().zip(keystream.iter()).map(|(byte, key_byte)| byte ^ key_byte));
+    }
+    output
+}
+
+/// Poly1305 one-time authenticator (RFC 8439, section 2.5), with the
+/// 130-bit accumulator in five 26-bit limbs.
+fn poly1305_mac(one_time_key: &[u8; 32], message: &[u8]) -> [u8; 16] {
+    let le32 = |bytes: &[u8], i: usize| u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+    const MASK_26: u32 = 0x3ffffff;
+
+    // r, clamped
+    let r0 = le32(one_time_key, 0) & 0x3ffffff;
+    let r1 = (le32(one_time_key, 3) >> 2) & 0x3ffff03;
+    let r2 = (le32(one_time_key, 6) >> 4) & 0x3ffc0ff;
+    let r3 = (le32(one_time_key, 9) >> 6) & 0x3f03fff;
+    let r4 = (le32(one_time_key, 12) >> 8) & 0x00fffff;
+    let (s1, s2, s3, s4) = (r1 * 5, r2 * 5, r3 * 5, r4 * 5);
+
+    let mut h = [0u32; 5];
+    for chunk in message.chunks(16) {
+        // Full blocks get a 2^128 bit; the last partial block is padded with 0x01 instead
+        let mut block = [0u8; 17];
+        block[..chunk.len()].copy_from_slice(chunk);
+        block[chunk.len()] = 1;
+        let high_bit = if chunk.len() == 16 { 1 << 24 } else { 0 };
+
+        h[0] += le32(&block, 0) & MASK_26;
+        h[1] += (le32(&block, 3) >> 2) & MASK_26;
+        h[2] += (le32(&block, 6) >> 4) & MASK_26;
+        h[3] += (le32(&block, 9) >> 6) & MASK_26;
+        h[4] += (le32(&block, 12) >> 8) | high_bit;
+
+        let m = |a: u32, b: u32| a as u64 * b as u64;
+        let d0 = m(h[0], r0) + m(h[1], s4) + m(h[2], s3) + m(h[3], s2) + m(h[4], s1);
+        let mut d1 = m(h[0], r1) + m(h[1], r0) + m(h[2], s4) + m(h[3], s3) + m(h[4], s2);
+        let mut d2 = m(h[0], r2) + m(h[1], r1) + m(h[2], r0) + m(h[3], s4) + m(h[4], s3);
+        let mut d3 = m(h[0], r3) + m(h[1], r2) + m(h[2], r1) + m(h[3], r0) + m(h[4], s4);
+        let mut d4 = m(h[0], r4) + m(h[1], r3) + m(h[2], r2) + m(h[3], r1) + m(h[4], r0);
+
+        // Partial carry back into 26-bit limbs (2^130 = 5 mod p)
+        d1 += d0 >> 26;
+        h[0] = (d0 as u32) & MASK_26;
+        d2 += d1 >> 26;
+        h[1] = (d1 as u32) & MASK_26;
+        d3 += d2 >> 26;
+        h[2] = (d2 as u32) & MASK_26;
+        d4 += d3 >> 26;
+        h[3] = (d3 as u32) & MASK_26;
+        h[4] = (d4 as u32) & MASK_26;
+        h[0] += ((d4 >> 26) as u32) * 5;
+        h[1] += h[0] >> 26;
+        h[0] &= MASK_26;
+    }
+
+    // Full carry
+    let mut carry;
+    carry = h[1] >> 26;
+    h[1] &= MASK_26;
+    h[2] += carry;
+    carry = h[2] >> 26;
+    h[2] &= MASK_26;
+    h[3] += carry;
+    carry = h[3] >> 26;
+    h[3] &= MASK_26;
+    h[4] += carry;
+    carry = h[4] >> 26;
+    h[4] &= MASK_26;
+    h[0] += carry * 5;
+    carry = h[0] >> 26;
+    h[0] &= MASK_26;
+    h[1] += carry;
+
+    // g = h + 5 - 2^130; use g if it did not go negative (h >= p)
+    let mut g = [0u32; 5];
+    g[0] = h[0] + 5;
+    carry = g[0] >> 26;
+    g[0] &= MASK_26;
+    for i in 1..4 {
+        g[i] = h[i] + carry;
+        carry = g[i] >> 26;
+        g[i] &= MASK_26;
+    }
+    g[4] = h[4].wrapping_add(carry).wrapping_sub(1 << 26);
+    let use_g = (g[4] >> 31).wrapping_sub(1); // all ones if g[4] did not wrap
+    for i in 0..5 {
+        h[i] = (h[i] & !use_g) | (g[i] & use_g);
+    }
+
+    // h mod 2^128, then add s (the second half of the key)
+    let h0 = h[0] | (h[1] << 26);
+    let h1 = (h[1] >> 6) | (h[2] << 20);
+    let h2 = (h[2] >> 12) | (h[3] << 14);
+    let h3 = (h[3] >> 18) | (h[4] << 8);
+
+    let mut tag = [0u8; 16];
+    let mut f: u64 = 0;
+    for (i, word) in [h0, h1, h2, h3].iter().enumerate() {
+        f = *word as u64 + le32(one_time_key, 16 + i * 4) as u64 + (f >> 32);
+        tag[i * 4..i * 4 + 4].copy_from_slice(&(f as u32).to_le_bytes());
+    }
+    tag
+}
+
+/// Encrypts and authenticates (RFC 8439, section 2.8); returns the
+/// ciphertext followed by the 16-byte tag.
+fn chacha20_poly1305_seal(key: &[u8; 32], nonce: &[u8; 12], aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+    let mut one_time_key = [0u8; 32];
+    one_time_key.copy_from_slice(&chacha20_block(key, 0, nonce)[..32]);
+
+    let mut sealed = chacha20_xor(key, 1, nonce, plaintext);
+    let tag = poly1305_mac(&one_time_key, &aead_mac_data(aad, &sealed));
+    sealed.extend_from_slice(&tag);
+    sealed
+}
+
+/// Checks the tag and decrypts; returns `None` if authentication fails.
+fn chacha20_poly1305_open(key: &[u8; 32], nonce: &[u8; 12], aad: &[u8], sealed: &[u8]) -> Option<Vec<u8>> {
+    if sealed.len() < POLY1305_TAG_LEN {
+        return None;
+    }
+    let (ciphertext, tag) = sealed.split_at(sealed.len() - POLY1305_TAG_LEN);
+
+    let mut one_time_key = [0u8; 32];
+    one_time_key.copy_from_slice(&chacha20_block(key, 0, nonce)[..32]);
+    let expected_tag = poly1305_mac(&one_time_key, &aead_mac_data(aad, ciphertext));
+
+    // Compare without an early exit
+    let difference = expected_tag.iter().zip(tag.iter()).fold(0u8, |acc, (a, b)| acc | (a ^ b));
+    if difference != 0 {
+        return None;
+    }
+    Some(chacha20_xor(key, 1, nonce, ciphertext))
+}
+
+// Helper function: aad || pad16 || ciphertext || pad16 || len(aad) || len(ciphertext)
+fn aead_mac_data(aad: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+    let mut mac_data = Vec::with_capacity(aad.len() + ciphertext.len() + 48);
+    mac_data.extend_from_slice(aad);
+    mac_data.resize(mac_data.len().div_ceil(16) * 16, 0);
+    mac_data.extend_from_slice(ciphertext);
+    mac_data.resize(mac_data.len().div_ceil(16) * 16, 0);
+    mac_data.extend_from_slice(&(aad.len() as u64).to_le_bytes());
+    mac_data.extend_from_slice(&(ciphertext.len() as u64).to_le_bytes());
+    mac_data
+}
+
+/// PBKDF2 (RFC 8018) with HMAC-SHA256, producing one 32-byte key.
+fn pbkdf2_hmac_sha256(passphrase: &[u8], salt: &[u8], iterations: u32) -> [u8; 32] {
+    // HMAC key: hashed if longer than a block, then zero-padded
+    let mut hmac_key = [0u8; 64];
+    if passphrase.len() > 64 {
+        hmac_key[..32].copy_from_slice(&sha256(passphrase));
+    } else {
+        hmac_key[..passphrase.len()].copy_from_slice(passphrase);
+    }
+
+    // Hash states after the inner and outer key blocks, reused for every HMAC
+    let mut inner = Sha256::new();
+    inner.update(&hmac_key.map(|b| b ^ 0x36));
+    let mut outer = Sha256::new();
+    outer.update(&hmac_key.map(|b| b ^ 0x5c));
+    let hmac = |message: &[u8]| {
+        let mut inner_hash = inner.clone();
+        inner_hash.update(message);
+        let mut outer_hash = outer.clone();
+        outer_hash.update(&inner_hash.finalize());
+        outer_hash.finalize()
+    };
+
+    // T1 = U1 ^ U2 ^ ... ^ Uc, U1 = HMAC(salt || INT(1)), Ui = HMAC(U(i-1))
+    let mut first_message = salt.to_vec();
+    first_message.extend_from_slice(&1u32.to_be_bytes());
+    let mut u = hmac(&first_message);
+    let mut derived = u;
+    for _ in 1..iterations {
+        u = hmac(&u);
+        for (d, x) in derived.iter_mut().zip(u.iter()) {
+            *d ^= x;
+        }
+    }
+    derived
+}
+
+/// SHA-256 round constants (FIPS 180-4, section 4.2.2).
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// SHA-256 initial hash value (FIPS 180-4, section 5.3.3).
+const SHA256_H0: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Incremental SHA-256 (FIPS 180-4), implemented here to avoid a crypto dependency.
+/// `Clone` lets HMAC reuse the state after the key block.
+///
+/// # use with
+/// let mut hasher = Sha256::new();
+/// hasher.update(b"part one");
+/// hasher.update(b"part two");
+/// let digest: [u8; 32] = hasher.finalize();
+#[derive(Clone)]
+struct Sha256 {
+    state: [u32; 8],
+    buffer: [u8; 64],
+    buffer_len: usize,
+    total_len: u64, // in bytes
+}
+
+impl Sha256 {
+    fn new() -> Sha256 {
+        Sha256 { state: SHA256_H0, buffer: [0; 64], buffer_len: 0, total_len: 0 }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        self.total_len = self.total_len.wrapping_add(data.len() as u64);
+
+        // Fill up a partial block first
+        if self.buffer_len > 0 {
+            let take = (64 - self.buffer_len).min(data.len());
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+            if self.buffer_len < 64 {
+                return;
+            }
+            let block = self.buffer;
+            self.compress(&block);
+            self.buffer_len = 0;
+        }
+
+        // Whole blocks straight from the input
+        while data.len() >= 64 {
+            let mut block = [0u8; 64];
+            block.copy_from_slice(&data[..64]);
+            self.compress(&block);
+            data = &data[64..];
+        }
+
+        // Keep the rest for later
+        self.buffer[..data.len()].copy_from_slice(data);
+        self.buffer_len = data.len();
+    }
+
+    fn finalize(mut self) -> [u8; 32] {
+        let bit_len = self.total_len.wrapping_mul(8);
+
+        // Padding: 0x80, zeros, then the message length in bits (big-endian u64)
+        let mut padding = vec![0x80u8];
+        let pad_zeros = (64 + 56 - (self.buffer_len + 1) % 64) % 64;
+        padding.resize(1 + pad_zeros, 0);
+        padding.extend_from_slice(&bit_len.to_be_bytes());
+        let total_len = self.total_len;
+        self.update(&padding);
+        self.total_len = total_len;
+
+        let mut digest = [0u8; 32];
+        for (i, word) in self.state.iter().enumerate() {
+            digest[i * 4..i * 4 + 4].copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+
+    fn compress(&mut self, block: &[u8; 64]) {
+        let mut w = [0u32; 64];
+        for i in 0..16 {
+            w[i] = u32::from_be_bytes([block[i * 4], block[i * 4 + 1], block[i * 4 + 2], block[i * 4 + 3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let temp1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(SHA256_K[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(maj);
+
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp1.wrapping_add(temp2);
+        }
+
+        for (word, value) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *word = word.wrapping_add(value);
+        }
+    }
+}
+
+/// SHA-256 of a byte slice in one call.
+fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+fn main() {
+    // A low iteration count keeps this demo quick in debug builds; real
+    // files should keep DEFAULT_KDF_ITERATIONS
+    let mut encryption = AtRestEncryption::new("correct horse battery staple");
+    encryption.kdf_iterations = 10_000;
+
+    // Example CollaboratorTomlData instance
+    let collaborator = CollaboratorTomlData {
+        user_name: "carol".to_string(),
+        user_salt_list: vec![0x123456789abcdef0, 0xabcdef0123456789],
+        ipv4_addresses: Some(vec![Ipv4Addr::new(192, 168, 1, 1), Ipv4Addr::new(10, 0, 0, 1)]),
+        ipv6_addresses: Some(vec![Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)]),
+        gpg_key_public: "-----BEGIN PGP PUBLIC KEY BLOCK----- ...".to_string(),
+        sync_interval: 300,
+        updated_at_timestamp: 1728308000,
+    };
+
+    // Write an encrypted file
+    let file_path = "project_graph_data/collaborator_files_address_book/carol__collaborator.toml";
+    match serialize_collaborator_to_toml(&collaborator)
+        .and_then(|toml_string| write_toml_to_file(file_path, "carol", &toml_string, Some(&encryption)))
+    {
+        Ok(_) => println!("Encrypted TOML data written to {}", file_path),
+        Err(e) => println!("Error writing encrypted file: {}", e),
+    }
+
+    // Read it back with the right passphrase, a wrong one, and none
+    match read_one_collaborator_setup_toml("carol", Some(&encryption)) {
+        Ok(collaborator) => println!("Decrypted: {:#?}", collaborator),
+        Err(e) => println!("Error reading encrypted file: {}", e),
+    }
+    let wrong = AtRestEncryption::new("wrong passphrase");
+    if let Err(e) = read_one_collaborator_setup_toml("carol", Some(&wrong)) {
+        println!("With a wrong passphrase: {}", e);
+    }
+    if let Err(e) = read_one_collaborator_setup_toml("carol", None) {
+        println!("Without a passphrase: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len()).step_by(2).map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap()).collect()
+    }
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn array<const N: usize>(bytes: &[u8]) -> [u8; N] {
+        bytes.try_into().unwrap()
+    }
+
+    const SUNSCREEN: &[u8] = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+
+    // ChaCha20 (RFC 8439, sections 2.3.2 and 2.4.2)
+
+    #[test]
+    fn chacha20_block_function() {
+        let key: [u8; 32] = array(&(0..32).collect::<Vec<u8>>());
+        let nonce: [u8; 12] = array(&hex("000000090000004a00000000"));
+        assert_eq!(
+            to_hex(&chacha20_block(&key, 1, &nonce)),
+            "10f1e7e4d13b5915500fdd1fa32071c4c7d1f4c733c068030422aa9ac3d46c4e\
+             d2826446079faa0914c2d705d98b02a2b5129cd1de164eb9cbd083e8a2503c4e"
+        );
+    }
+
+    #[test]
+    fn chacha20_encryption() {
+        let key: [u8; 32] = array(&(0..32).collect::<Vec<u8>>());
+        let nonce: [u8; 12] = array(&hex("000000000000004a00000000"));
+        assert_eq!(
+            to_hex(&chacha20_xor(&key, 1, &nonce, SUNSCREEN)),
+            "6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0b\
+             f91b65c5524733ab8f593dabcd62b3571639d624e65152ab8f530c359f0861d8\
+             07ca0dbf500d6a6156a38e088a22b65e52bc514d16ccf806818ce91ab7793736\
+             5af90bbf74a35be6b40b8eedf2785e42874d"
+        );
+    }
+
+    // Poly1305 (RFC 8439, section 2.5.2 and the appendix A.3 edge cases)
+
+    #[test]
+    fn poly1305_known_answer() {
+        let key: [u8; 32] = array(&hex("85d6be7857556d337f4452fe42d506a80103808afb0db2fd4abff6af4149f51b"));
+        assert_eq!(to_hex(&poly1305_mac(&key, b"Cryptographic Forum Research Group")), "a8061dc1305136c6c22b8baf0c0127a9");
+    }
+
+    #[test]
+    fn poly1305_edge_cases() {
+        let r1_s0 = "01000000000000000000000000000000".to_owned() + &"00".repeat(16);
+        let r2_s0 = "02000000000000000000000000000000".to_owned() + &"00".repeat(16);
+        let r2_s_ones = "02000000000000000000000000000000".to_owned() + &"ff".repeat(16);
+        let r_long = "01000000000000000400000000000000".to_owned() + &"00".repeat(16);
+        let cases = [
+            // Test vector #1: all zero
+            ("00".repeat(32), "00".repeat(64), "00000000000000000000000000000000"),
+            // #5: h reaches p exactly after the final reduction
+            (r2_s0.clone(), "ff".repeat(16), "03000000000000000000000000000000"),
+            // #6: the addition of s overflows 2^128
+            (r2_s_ones, "02000000000000000000000000000000".to_owned(), "03000000000000000000000000000000"),
+            // #7 and #8: carries through all limbs
+            (
+                r1_s0.clone(),
+                "ff".repeat(16) + "f0" + &"ff".repeat(15) + "11" + &"00".repeat(15),
+                "05000000000000000000000000000000",
+            ),
+            (
+                r1_s0,
+                "ff".repeat(16) + "fb" + &"fe".repeat(15) + &"01".repeat(16),
+                "00000000000000000000000000000000",
+            ),
+            // #9: h is just below 2^130 - 5
+            (r2_s0, "fd".to_owned() + &"ff".repeat(15), "faffffffffffffffffffffffffffffff"),
+            // #10 and #11: large intermediate products
+            (
+                r_long.clone(),
+                "e33594d7505e43b900000000000000003394d7505e4379cd0100000000000000\
+                 0000000000000000000000000000000001000000000000000000000000000000"
+                    .to_owned(),
+                "14000000000000005500000000000000",
+            ),
+            (
+                r_long,
+                "e33594d7505e43b900000000000000003394d7505e4379cd010000000000000000000000000000000000000000000000".to_owned(),
+                "13000000000000000000000000000000",
+            ),
+        ];
+        for (key, message, tag) in cases {
+            let key: [u8; 32] = array(&hex(&key));
+            assert_eq!(to_hex(&poly1305_mac(&key, &hex(&message))), tag, "message {}", message);
+        }
+    }
+
+    // AEAD (RFC 8439, section 2.8.2)
+
+    #[test]
+    fn chacha20_poly1305_known_answer() {
+        let key: [u8; 32] = array(&(0x80..0xa0).collect::<Vec<u8>>());
+        let nonce: [u8; 12] = array(&hex("070000004041424344454647"));
+        let aad = hex("50515253c0c1c2c3c4c5c6c7");
+
+        let sealed = chacha20_poly1305_seal(&key, &nonce, &aad, SUNSCREEN);
+        assert_eq!(
+            to_hex(&sealed),
+            "d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d6\
+             3dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b36\
+             92ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc\
+             3ff4def08e4b7a9de576d26586cec64b6116\
+             1ae10b594f09e26a7e902ecbd0600691"
+        );
+        assert_eq!(chacha20_poly1305_open(&key, &nonce, &aad, &sealed).as_deref(), Some(SUNSCREEN));
+
+        let mut tampered = sealed.clone();
+        tampered[0] ^= 1;
+        assert_eq!(chacha20_poly1305_open(&key, &nonce, &aad, &tampered), None);
+        assert_eq!(chacha20_poly1305_open(&key, &nonce, b"other aad", &sealed), None);
+    }
+
+    // PBKDF2-HMAC-SHA256 (RFC 7914, section 11; first 32 bytes of each)
+
+    #[test]
+    fn pbkdf2_hmac_sha256_known_answers() {
+        assert_eq!(
+            to_hex(&pbkdf2_hmac_sha256(b"passwd", b"salt", 1)),
+            "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc"
+        );
+        assert_eq!(
+            to_hex(&pbkdf2_hmac_sha256(b"Password", b"NaCl", 80000)),
+            "4ddcd8f60b98be21830cee5ef22701f9641a4418d04c0414aeff08876b34ab56"
+        );
+    }
+
+    // SHA-256 (FIPS 180-4 / NIST examples)
+
+    #[test]
+    fn sha256_known_answers() {
+        assert_eq!(to_hex(&sha256(b"")), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(to_hex(&sha256(b"abc")), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(
+            to_hex(&sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+    }
+
+    // Container
+
+    #[test]
+    fn file_name_check_ignores_case() {
+        assert!(user_name_matches_file_name("Alice", "alice"));
+        assert!(user_name_matches_file_name("alice", "alice"));
+        assert!(!user_name_matches_file_name("Bob", "alice"));
+    }
+
+    #[test]
+    fn container_is_bound_to_the_collaborator_name() {
+        let mut encryption = AtRestEncryption::new("passphrase");
+        encryption.kdf_iterations = 1;
+        let file_bytes = encrypt_toml_string("user_name = \"alice\"\n", "alice", &encryption).unwrap();
+
+        assert_eq!(
+            decrypt_collaborator_file_bytes(&file_bytes, "alice", &encryption).unwrap(),
+            "user_name = \"alice\"\n"
+        );
+        // alice's container copied over bob's file does not decrypt as bob's
+        assert!(decrypt_collaborator_file_bytes(&file_bytes, "bob", &encryption).is_err());
+    }
+}
@@ -0,0 +1,505 @@
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use toml::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+        }
+    }
+}
+
+/// A default value for a scalar field.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FieldDefault {
+    Integer(i64),
+    String(&'static str),
+}
+
+impl FieldDefault {
+    fn to_toml_value(self) -> Value {
+        match self {
+            FieldDefault::Integer(i) => Value::Integer(i),
+            FieldDefault::String(s) => Value::String(s.to_string()),
+        }
+    }
+}
+
+/// Whether a field must be in a collaborator file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FieldPresence {
+    /// Must be present; a missing field is an error.
+    Required,
+    /// May be absent; absent maps to `None` (and `None` is not written).
+    Optional,
+    /// May be absent; absent maps to this default.
+    Defaulted(FieldDefault),
+}
+
+/// One field of `CollaboratorTomlData` as it appears in a TOML file.
+#[derive(Debug, Clone, Copy)]
+struct FieldSpec {
+    key: &'static str,
+    presence: FieldPresence,
+}
+
+/// Presence rules for every field, in file order. Shared by the reader
+/// (`apply_field_defaults`) and the serializer (`serialize_collaborator_to_toml`),
+/// so the two cannot disagree about a default.
+///
+/// - `user_name`, `user_salt_list`: required, as in `u128_array_only.rs`.
+/// - `ipv4_addresses`, `ipv6_addresses`: optional.
+/// - `gpg_key_public`: defaults to `""` (no key yet).
+/// - `sync_interval`: defaults to 60 (seconds).
+/// - `updated_at_timestamp`: defaults to 0 (never updated / unknown).
+///
+/// With these rules the two-field files read by `u128_array_only.rs` are
+/// complete collaborator files.
+const COLLABORATOR_FIELDS: [FieldSpec; 7] = [
+    FieldSpec { key: "user_name", presence: FieldPresence::Required },
+    FieldSpec { key: "user_salt_list", presence: FieldPresence::Required },
+    FieldSpec { key: "ipv4_addresses", presence: FieldPresence::Optional },
+    FieldSpec { key: "ipv6_addresses", presence: FieldPresence::Optional },
+    FieldSpec { key: "gpg_key_public", presence: FieldPresence::Defaulted(FieldDefault::String("")) },
+    FieldSpec { key: "sync_interval", presence: FieldPresence::Defaulted(FieldDefault::Integer(60)) },
+    FieldSpec { key: "updated_at_timestamp", presence: FieldPresence::Defaulted(FieldDefault::Integer(0)) },
+];
+
+// Helper function to look up the presence rule of a field
+fn field_presence(key: &str) -> FieldPresence {
+    COLLABORATOR_FIELDS
+        .iter()
+        .find(|spec| spec.key == key)
+        .map(|spec| spec.presence)
+        .unwrap_or(FieldPresence::Optional)
+}
+
+/// Checks required fields and fills in defaulted fields of a TOML table,
+/// so that the extractors below see a complete table.
+///
+/// Only missing keys are filled in: a key that is present with the wrong
+/// type is left alone, so the extractor reports it as invalid instead of
+/// it being silently replaced by the default.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::TomlVanillaDeserialStrError("Missing {key}")`
+/// for the first missing required field.
+fn apply_field_defaults(table: &mut toml::map::Map<String, Value>) -> Result<(), ThisProjectError> {
+    for spec in COLLABORATOR_FIELDS.iter() {
+        if table.contains_key(spec.key) {
+            continue;
+        }
+        match spec.presence {
+            FieldPresence::Required => {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing {}", spec.key)));
+            }
+            FieldPresence::Optional => {}
+            FieldPresence::Defaulted(default) => {
+                table.insert(spec.key.to_string(), default.to_toml_value());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Vanilla-Rust File Deserialization
+/// Reads collaborator setup data from a TOML file for a specific user.
+///
+/// Reads `project_graph_data/collaborator_files_address_book/{collaborator_name}__collaborator.toml`
+/// and stops at the first problem, which is returned as the error. Missing
+/// fields follow `COLLABORATOR_FIELDS`: only `user_name` and
+/// `user_salt_list` are required, the others are optional or defaulted. A
+/// field that is present with the wrong type is an error, never defaulted.
+///
+/// # Example TOML File
+///
+/// A minimal file:
+///
+/// ```toml
+/// user_name = "Alice"
+/// user_salt_list = ["0x11111111111111111111111111111111", "0x11111111111111111111111111111112"]
+/// ```
+///
+/// reads as `sync_interval: 60`, `gpg_key_public: ""`, `updated_at_timestamp: 0`
+/// and no IP addresses.
+fn read_one_collaborator_setup_toml(collaborator_name: &str) -> Result<CollaboratorTomlData, ThisProjectError> {
+
+    // 1. Construct File Path
+    let file_path = Path::new("project_graph_data/collaborator_files_address_book")
+        .join(format!("{}__collaborator.toml", collaborator_name));
+
+    // 2. Read TOML File
+    let toml_string = fs::read_to_string(&file_path)?;
+
+    // 3.-5. Parse, apply defaults, extract
+    deserialize_collaborator_from_toml(&toml_string)
+}
+
+/// Parses one collaborator from TOML text, applying `COLLABORATOR_FIELDS`
+/// defaults; the parsing part of `read_one_collaborator_setup_toml`.
+fn deserialize_collaborator_from_toml(toml_string: &str) -> Result<CollaboratorTomlData, ThisProjectError> {
+
+    // 3. Parse TOML Data (handle potential toml::de::Error)
+    let toml_value = match toml::from_str::<Value>(toml_string) {
+        Ok(value) => value,
+        Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(e.to_string())),
+    };
+
+    // 4. Extract Data from TOML Value
+    if let Value::Table(mut table) = toml_value {
+
+        // Check required fields, fill in defaulted ones
+        apply_field_defaults(&mut table)?;
+
+        // Extract user_name
+        let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+            s.clone()
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid user_name: Expected string".into()));
+        };
+
+        // Extract user_salt_list
+        let user_salt_list = if let Some(Value::Array(arr)) = table.get("user_salt_list") {
+            arr.iter()
+                .map(|val| {
+                    if let Value::String(s) = val {
+                        u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                            .map_err(ThisProjectError::ParseIntError)
+                    } else {
+                        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                    }
+                })
+                .collect::<Result<Vec<u128>, ThisProjectError>>()?
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid user_salt_list: Expected array".into()));
+        };
+
+        // Extract ipv4_addresses
+        let ipv4_addresses = extract_ipv4_addresses(&table, "ipv4_addresses")?;
+
+        // Extract ipv6_addresses
+        let ipv6_addresses = extract_ipv6_addresses(&table, "ipv6_addresses")?;
+
+        // Extract gpg_key_public
+        let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+            s.clone()
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid gpg_key_public: Expected string".into()));
+        };
+
+        // Extract sync_interval
+        let sync_interval = extract_u64(&table, "sync_interval")?;
+
+        // Extract updated_at_timestamp
+        let updated_at_timestamp = extract_u64(&table, "updated_at_timestamp")?;
+
+        // 5. Return CollaboratorTomlData
+        Ok(CollaboratorTomlData {
+            user_name,
+            user_salt_list,
+            ipv4_addresses,
+            ipv6_addresses,
+            gpg_key_public,
+            sync_interval,
+            updated_at_timestamp,
+        })
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure: Expected a table".into()))
+    }
+}
+
+fn extract_ipv4_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv4Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv4Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else if table.contains_key(key) {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Expected array", key)))
+    } else {
+        Ok(None)
+    }
+}
+
+fn extract_ipv6_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv6Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv6Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else if table.contains_key(key) {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Expected array", key)))
+    } else {
+        Ok(None)
+    }
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+/// Settings for `serialize_collaborator_to_toml`.
+///
+/// - `omit_defaults`: leave out defaulted fields whose value equals their
+///   default (see `COLLABORATOR_FIELDS`), for shorter files. The reader
+///   fills them back in, so the data read back is the same. Default
+///   `false`: every field is written, as before.
+#[derive(Debug, Clone, Default)]
+struct SerializeOptions {
+    omit_defaults: bool,
+}
+
+// Helper function: should a scalar field be written?
+fn should_write_field(key: &str, value: &Value, options: &SerializeOptions) -> bool {
+    match field_presence(key) {
+        FieldPresence::Defaulted(default) => !(options.omit_defaults && default.to_toml_value() == *value),
+        FieldPresence::Required | FieldPresence::Optional => true,
+    }
+}
+
+/// Serialize struct to .toml file
+/// Serializes a `CollaboratorTomlData` struct into a TOML-formatted string.
+///
+/// Writes the fields in `COLLABORATOR_FIELDS` order, one `key = value` line
+/// each, with escaped strings and salts as `0x` hex strings. An IP list that
+/// is `None` is left out, and with `options.omit_defaults` so are the
+/// defaulted fields that hold their default value.
+///
+/// # use with
+/// let toml_string = serialize_collaborator_to_toml(&collaborator, &SerializeOptions { omit_defaults: true })?;
+fn serialize_collaborator_to_toml(collaborator: &CollaboratorTomlData, options: &SerializeOptions) -> Result<String, ThisProjectError> {
+    let mut toml_string = String::new();
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", escape_toml_basic_string(&collaborator.user_name)));
+
+    // Add user_salt_list
+    toml_string.push_str("user_salt_list = [\n");
+    for salt in &collaborator.user_salt_list {
+        toml_string.push_str(&format!("    \"0x{:x}\",\n", salt));
+    }
+    toml_string.push_str("]\n");
+
+    // Add ipv4_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv4_addresses", &collaborator.ipv4_addresses)?;
+
+    // Add ipv6_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv6_addresses", &collaborator.ipv6_addresses)?;
+
+    // Add gpg_key_public
+    if should_write_field("gpg_key_public", &Value::String(collaborator.gpg_key_public.clone()), options) {
+        toml_string.push_str(&format!("gpg_key_public = \"{}\"\n", escape_toml_basic_string(&collaborator.gpg_key_public)));
+    }
+
+    // Add sync_interval
+    if should_write_field("sync_interval", &Value::Integer(collaborator.sync_interval as i64), options) {
+        toml_string.push_str(&format!("sync_interval = {}\n", collaborator.sync_interval));
+    }
+
+    // Add updated_at_timestamp
+    if should_write_field("updated_at_timestamp", &Value::Integer(collaborator.updated_at_timestamp as i64), options) {
+        toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp));
+    }
+
+    Ok(toml_string)
+}
+
+// Helper function to escape a value for a TOML basic string ("...")
+fn escape_toml_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Helper function to serialize IP addresses to TOML array format
+fn serialize_ip_addresses<T: std::fmt::Display>(
+    toml_string: &mut String,
+    key: &str,
+    addresses: &Option<Vec<T>>
+) -> Result<(), ThisProjectError> {
+    if let Some(addr_vec) = addresses {
+        toml_string.push_str(&format!("{} = [\n", key));
+        for addr in addr_vec {
+            toml_string.push_str(&format!("    \"{}\",\n", addr));
+        }
+        toml_string.push_str("]\n");
+    }
+    Ok(()) // Return Ok(()) if the addresses field is None
+}
+
+// Function to write a TOML string to a file
+fn write_toml_to_file(file_path: &str, toml_string: &str) -> Result<(), ThisProjectError> {
+    // Attempt to create the file.
+    let mut file = match File::create(file_path) {
+        Ok(file) => file,
+        Err(e) => return Err(ThisProjectError::IoError(e)),
+    };
+
+    // Attempt to write to the file.
+    if let Err(e) = file.write_all(toml_string.as_bytes()) {
+        return Err(ThisProjectError::IoError(e));
+    }
+
+    // Everything successful!
+    Ok(())
+}
+
+fn main() {
+    // A minimal file, as read by u128_array_only.rs
+    let minimal_toml = "user_name = \"Carol\"\nuser_salt_list = [\"0x11111111111111111111111111111111\"]\n";
+    match deserialize_collaborator_from_toml(minimal_toml) {
+        Ok(collaborator) => {
+            println!("Minimal file with defaults applied:\n{:#?}", collaborator);
+
+            // Written back without the fields that are at their defaults
+            match serialize_collaborator_to_toml(&collaborator, &SerializeOptions { omit_defaults: true }) {
+                Ok(toml_string) => println!("Serialized without defaults:\n{}", toml_string),
+                Err(e) => println!("Error serializing to TOML: {}", e),
+            }
+        }
+        Err(e) => println!("Error reading minimal file: {}", e),
+    }
+
+    // A required field is still required
+    if let Err(e) = deserialize_collaborator_from_toml("user_name = \"Dave\"\n") {
+        println!("Without user_salt_list: {}", e);
+    }
+
+    // Example CollaboratorTomlData instance, written in full
+    let collaborator = CollaboratorTomlData {
+        user_name: "Bob".to_string(),
+        user_salt_list: vec![0x123456789abcdef0, 0xabcdef0123456789],
+        ipv4_addresses: Some(vec![Ipv4Addr::new(192, 168, 1, 1), Ipv4Addr::new(10, 0, 0, 1)]),
+        ipv6_addresses: Some(vec![Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)]),
+        gpg_key_public: "-----BEGIN PGP PUBLIC KEY BLOCK----- ...".to_string(),
+        sync_interval: 300,
+        updated_at_timestamp: 1728308000,
+    };
+    match serialize_collaborator_to_toml(&collaborator, &SerializeOptions::default()) {
+        Ok(toml_string) => {
+            println!("Serialized TOML:\n{}", toml_string);
+
+            // Write the TOML string to a file (example file path)
+            match write_toml_to_file("collaborator_data.toml", &toml_string) {
+                Ok(_) => println!("TOML data written to file successfully."),
+                Err(e) => println!("Error writing to file: {}", e),
+            }
+        }
+        Err(e) => println!("Error serializing to TOML: {}", e),
+    }
+
+    // Read one collaborator from the address book
+    match read_one_collaborator_setup_toml("alice") {
+        Ok(collaborator) => println!("Collaborator Data for alice:\n{:#?}", collaborator),
+        Err(e) => println!("Error reading collaborator data for alice: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINIMAL_FILE: &str = "user_name = \"alice\"\nuser_salt_list = [\"0x11\"]\n";
+
+    #[test]
+    fn missing_fields_get_their_defaults() {
+        let collaborator = deserialize_collaborator_from_toml(MINIMAL_FILE).unwrap();
+        assert_eq!(collaborator.ipv4_addresses, None);
+        assert_eq!(collaborator.gpg_key_public, "");
+        assert_eq!(collaborator.sync_interval, 60);
+        assert_eq!(collaborator.updated_at_timestamp, 0);
+    }
+
+    #[test]
+    fn wrong_type_is_an_error_not_a_default() {
+        for extra in ["ipv4_addresses = \"10.0.0.1\"", "ipv6_addresses = 6", "sync_interval = \"60\"", "gpg_key_public = 1"] {
+            let toml_string = format!("{}{}\n", MINIMAL_FILE, extra);
+            assert!(deserialize_collaborator_from_toml(&toml_string).is_err(), "{}", extra);
+        }
+    }
+}
@@ -0,0 +1,939 @@
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use toml::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    TomlEditError(String),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::TomlEditError(err) => write!(f, "TOML Edit Error: {}", err),
+        }
+    }
+}
+
+/// One `key = value` line (or lines, for multi-line values) of a TOML document,
+/// kept as the exact text it was read from.
+///
+/// Writing `indent + key_raw + separator_raw + value_raw + trailing_raw`
+/// gives back the original text byte for byte.
+///
+/// ```text
+///     sync_interval   =   60    # seconds\n
+/// ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+/// |   |              |     |  |
+/// |   key_raw        |     |  trailing_raw ("    # seconds\n")
+/// indent             |     value_raw ("60")
+///                    separator_raw ("   =   ")
+/// ```
+#[derive(Debug, Clone)]
+struct TomlKeyValue {
+    indent: String,
+    key_raw: String,
+    key: Vec<String>, // key_raw split on '.', with quotes removed
+    separator_raw: String,
+    value_raw: String,
+    trailing_raw: String,
+}
+
+/// One item of a `TomlDocument`.
+#[derive(Debug, Clone)]
+enum TomlDocumentItem {
+    /// A blank line or a comment line, including its line ending.
+    Trivia(String),
+    /// A `[table]` or `[[array.of.tables]]` header line, including any
+    /// comment and its line ending.
+    TableHeader { raw: String, name: Vec<String> },
+    KeyValue(TomlKeyValue),
+}
+
+/// A lossless concrete syntax tree for a TOML document.
+///
+/// `toml::Value` keeps only the data: comments, blank lines, key order,
+/// indentation and quoting style are all lost, so writing a `Value` (or a
+/// `CollaboratorTomlData` via `serialize_collaborator_to_toml`) back to a
+/// file wipes out everything a person wrote by hand.
+///
+/// `TomlDocument` keeps the text instead. The document is a list of lines
+/// (see `TomlDocumentItem`) and each `key = value` entry keeps its raw pieces
+/// (see `TomlKeyValue`). Editing replaces only the pieces that change, so:
+///
+/// - `set` on an existing key replaces only the value text; the indent, key
+///   spelling, spacing around `=` and trailing comment stay the same.
+/// - `remove` removes only that entry's lines; comments around it stay.
+/// - `insert_after` adds one new line, copying the indent of its neighbour.
+/// - Every entry that is not edited is written back exactly as it was read.
+///
+/// # Scope
+///
+/// The edit operations work on the keys of the root table (the keys before
+/// the first `[table]` header), which is where every field of a collaborator
+/// file lives. Table headers and their contents are preserved as-is.
+///
+/// # Parsing
+///
+/// `TomlDocument::parse` first validates the text with the `toml` crate, so
+/// the scanner here only ever sees valid TOML; it only needs to find where
+/// each piece starts and ends (including strings, multi-line strings,
+/// multi-line arrays, inline tables and comments inside arrays).
+///
+/// # Example
+///
+/// ```
+/// let toml_string = fs::read_to_string(file_path)?;
+/// let mut document = TomlDocument::parse(&toml_string)?;
+/// document.set("sync_interval", &Value::Integer(120))?;
+/// write_toml_to_file(file_path, &document.to_string())?;
+/// ```
+#[derive(Debug, Clone)]
+struct TomlDocument {
+    items: Vec<TomlDocumentItem>,
+    newline: &'static str, // line ending used for new lines: "\n" or "\r\n"
+}
+
+impl fmt::Display for TomlDocument {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for item in &self.items {
+            match item {
+                TomlDocumentItem::Trivia(raw) => f.write_str(raw)?,
+                TomlDocumentItem::TableHeader { raw, .. } => f.write_str(raw)?,
+                TomlDocumentItem::KeyValue(entry) => {
+                    f.write_str(&entry.indent)?;
+                    f.write_str(&entry.key_raw)?;
+                    f.write_str(&entry.separator_raw)?;
+                    f.write_str(&entry.value_raw)?;
+                    f.write_str(&entry.trailing_raw)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TomlDocument {
+    /// Parses TOML text into a `TomlDocument`.
+    ///
+    /// # Error Handling
+    ///
+    /// Returns `ThisProjectError::TomlVanillaDeserialStrError` if the text is
+    /// not valid TOML, and `ThisProjectError::TomlEditError` if the scanner
+    /// could not reproduce the input exactly (which would be a bug here, but
+    /// is checked so that an edit can never silently corrupt a file).
+    fn parse(toml_string: &str) -> Result<TomlDocument, ThisProjectError> {
+        if let Err(e) = toml::from_str::<Value>(toml_string) {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError(e.to_string()));
+        }
+
+        let scanner = TomlScanner { text: toml_string, bytes: toml_string.as_bytes() };
+        let mut items = Vec::new();
+        let mut pos = 0;
+
+        while pos < toml_string.len() {
+            let line_start = pos;
+            let content_start = scanner.skip_whitespace(pos);
+
+            match scanner.byte_at(content_start) {
+                // Blank line or comment line
+                None | Some(b'\n') | Some(b'\r') | Some(b'#') => {
+                    let end = scanner.line_end(content_start);
+                    items.push(TomlDocumentItem::Trivia(toml_string[line_start..end].to_string()));
+                    pos = end;
+                }
+                // [table] or [[array.of.tables]]
+                Some(b'[') => {
+                    let mut name_start = content_start + 1;
+                    if scanner.byte_at(name_start) == Some(b'[') {
+                        name_start += 1;
+                    }
+                    let (_, name) = scanner.scan_key(name_start)?;
+                    let end = scanner.line_end(content_start);
+                    items.push(TomlDocumentItem::TableHeader {
+                        raw: toml_string[line_start..end].to_string(),
+                        name,
+                    });
+                    pos = end;
+                }
+                // key = value
+                Some(_) => {
+                    let (key_end, key) = scanner.scan_key(content_start)?;
+                    let separator_start = key_end;
+                    let equals = scanner.skip_whitespace(separator_start);
+                    if scanner.byte_at(equals) != Some(b'=') {
+                        return Err(ThisProjectError::TomlEditError(format!("Expected '=' at byte {}", equals)));
+                    }
+                    let value_start = scanner.skip_whitespace(equals + 1);
+                    let value_end = scanner.scan_value(value_start);
+                    let end = scanner.line_end(value_end);
+
+                    items.push(TomlDocumentItem::KeyValue(TomlKeyValue {
+                        indent: toml_string[line_start..content_start].to_string(),
+                        key_raw: toml_string[content_start..key_end].to_string(),
+                        key,
+                        separator_raw: toml_string[separator_start..value_start].to_string(),
+                        value_raw: toml_string[value_start..value_end].to_string(),
+                        trailing_raw: toml_string[value_end..end].to_string(),
+                    }));
+                    pos = end;
+                }
+            }
+        }
+
+        let newline = if toml_string.contains("\r\n") { "\r\n" } else { "\n" };
+        let document = TomlDocument { items, newline };
+
+        // Lossless check
+        if document.to_string() != toml_string {
+            return Err(ThisProjectError::TomlEditError("Document could not be reproduced exactly".into()));
+        }
+        Ok(document)
+    }
+
+    // Helper function: position of a root-table key in self.items
+    fn find_root_key(&self, key: &str) -> Option<usize> {
+        for (i, item) in self.items.iter().enumerate() {
+            match item {
+                TomlDocumentItem::TableHeader { .. } => return None,
+                TomlDocumentItem::KeyValue(entry) if entry.key.len() == 1 && entry.key[0] == key => return Some(i),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Returns the value of a root-table key, parsed with the `toml` crate.
+    fn get(&self, key: &str) -> Option<Value> {
+        let position = self.find_root_key(key)?;
+        if let TomlDocumentItem::KeyValue(entry) = &self.items[position] {
+            parse_raw_value(&entry.value_raw).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Sets a root-table key to `value`.
+    ///
+    /// If the key exists, only its value text is replaced (see
+    /// `format_toml_value_like` for how the old quoting and array layout is
+    /// kept). If it does not exist, a new `key = value` line is added after
+    /// the last root-table entry.
+    fn set(&mut self, key: &str, value: &Value) -> Result<(), ThisProjectError> {
+        if let Some(position) = self.find_root_key(key) {
+            if let TomlDocumentItem::KeyValue(entry) = &mut self.items[position] {
+                entry.value_raw = format_toml_value_like(value, &entry.value_raw, &entry.indent, self.newline);
+            }
+            return Ok(());
+        }
+
+        // New key: after the last root entry, or else before the first table header
+        let mut position = None;
+        for (i, item) in self.items.iter().enumerate() {
+            match item {
+                TomlDocumentItem::TableHeader { .. } => break,
+                TomlDocumentItem::KeyValue(_) => position = Some(i + 1),
+                _ => {}
+            }
+        }
+        let position = match position {
+            Some(position) => position,
+            None => self.items.iter()
+                .position(|item| matches!(item, TomlDocumentItem::TableHeader { .. }))
+                .unwrap_or(self.items.len()),
+        };
+
+        self.insert_new_entry(position, String::new(), key, value);
+        Ok(())
+    }
+
+    /// Removes a root-table key. Returns `true` if the key was present.
+    ///
+    /// Comments above or below the entry are not removed, since there is no
+    /// reliable way to know which entry a comment belongs to.
+    fn remove(&mut self, key: &str) -> bool {
+        match self.find_root_key(key) {
+            Some(position) => {
+                self.items.remove(position);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Inserts a new root-table `key = value` line directly after the entry
+    /// for `after_key`, using the same indent as that entry.
+    ///
+    /// # Error Handling
+    ///
+    /// Returns `ThisProjectError::TomlEditError` if `after_key` does not exist
+    /// or `key` already exists (use `set` to change an existing key).
+    fn insert_after(&mut self, after_key: &str, key: &str, value: &Value) -> Result<(), ThisProjectError> {
+        if self.find_root_key(key).is_some() {
+            return Err(ThisProjectError::TomlEditError(format!("Key already exists: {}", key)));
+        }
+        let position = match self.find_root_key(after_key) {
+            Some(position) => position,
+            None => return Err(ThisProjectError::TomlEditError(format!("Key not found: {}", after_key))),
+        };
+        let indent = match &self.items[position] {
+            TomlDocumentItem::KeyValue(entry) => entry.indent.clone(),
+            _ => String::new(),
+        };
+        self.insert_new_entry(position + 1, indent, key, value);
+        Ok(())
+    }
+
+    // Helper function to insert a new entry, making sure the line before it ends
+    fn insert_new_entry(&mut self, position: usize, indent: String, key: &str, value: &Value) {
+        if position > 0 {
+            let newline = self.newline;
+            let previous_raw = match &mut self.items[position - 1] {
+                TomlDocumentItem::Trivia(raw) => raw,
+                TomlDocumentItem::TableHeader { raw, .. } => raw,
+                TomlDocumentItem::KeyValue(entry) => &mut entry.trailing_raw,
+            };
+            if !previous_raw.ends_with('\n') {
+                previous_raw.push_str(newline);
+            }
+        }
+
+        let value_raw = format_toml_value(value, &indent, self.newline, true, false);
+        self.items.insert(position, TomlDocumentItem::KeyValue(TomlKeyValue {
+            indent,
+            key_raw: format_toml_key(key),
+            key: vec![key.to_string()],
+            separator_raw: " = ".to_string(),
+            value_raw,
+            trailing_raw: self.newline.to_string(),
+        }));
+    }
+
+    /// Names of all `[table]` and `[[array.of.tables]]` headers, in document order.
+    fn table_names(&self) -> Vec<String> {
+        self.items.iter()
+            .filter_map(|item| match item {
+                TomlDocumentItem::TableHeader { name, .. } => Some(name.join(".")),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Root-table keys, in document order.
+    fn root_keys(&self) -> Vec<String> {
+        let mut keys = Vec::new();
+        for item in &self.items {
+            match item {
+                TomlDocumentItem::TableHeader { .. } => break,
+                TomlDocumentItem::KeyValue(entry) => keys.push(entry.key.join(".")),
+                _ => {}
+            }
+        }
+        keys
+    }
+}
+
+// Byte scanner over text already known to be valid TOML.
+// All delimiters it looks for are ASCII, so every position it returns is a
+// valid &str slice boundary.
+struct TomlScanner<'a> {
+    text: &'a str,
+    bytes: &'a [u8],
+}
+
+impl<'a> TomlScanner<'a> {
+    fn byte_at(&self, pos: usize) -> Option<u8> {
+        self.bytes.get(pos).copied()
+    }
+
+    fn skip_whitespace(&self, mut pos: usize) -> usize {
+        while let Some(b' ' | b'\t') = self.byte_at(pos) {
+            pos += 1;
+        }
+        pos
+    }
+
+    // Position just after the end of the current line (after "\n"), or the end of the text
+    fn line_end(&self, pos: usize) -> usize {
+        match self.text[pos..].find('\n') {
+            Some(offset) => pos + offset + 1,
+            None => self.text.len(),
+        }
+    }
+
+    // Scans a (possibly dotted, possibly quoted) key; returns its end and its segments
+    fn scan_key(&self, mut pos: usize) -> Result<(usize, Vec<String>), ThisProjectError> {
+        let mut segments = Vec::new();
+        loop {
+            pos = self.skip_whitespace(pos);
+            let start = pos;
+            match self.byte_at(pos) {
+                Some(b'"') | Some(b'\'') => {
+                    pos = self.scan_value(pos);
+                    match parse_raw_value(&self.text[start..pos]) {
+                        Ok(Value::String(s)) => segments.push(s),
+                        _ => return Err(ThisProjectError::TomlEditError(format!("Invalid quoted key at byte {}", start))),
+                    }
+                }
+                _ => {
+                    while let Some(b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-') = self.byte_at(pos) {
+                        pos += 1;
+                    }
+                    if pos == start {
+                        return Err(ThisProjectError::TomlEditError(format!("Expected a key at byte {}", start)));
+                    }
+                    segments.push(self.text[start..pos].to_string());
+                }
+            }
+            let after = self.skip_whitespace(pos);
+            if self.byte_at(after) == Some(b'.') {
+                pos = after + 1;
+            } else {
+                return Ok((pos, segments));
+            }
+        }
+    }
+
+    // Returns the position just after the value starting at `pos`
+    fn scan_value(&self, pos: usize) -> usize {
+        let rest = &self.text[pos..];
+        if rest.starts_with("\"\"\"") {
+            self.scan_multi_line_string(pos + 3, b'"')
+        } else if rest.starts_with("'''") {
+            self.scan_multi_line_string(pos + 3, b'\'')
+        } else {
+            match self.byte_at(pos) {
+                Some(b'"') => {
+                    let mut p = pos + 1;
+                    while let Some(b) = self.byte_at(p) {
+                        match b {
+                            b'\\' => p += 2,
+                            b'"' => return p + 1,
+                            _ => p += 1,
+                        }
+                    }
+                    self.text.len()
+                }
+                Some(b'\'') => match self.text[pos + 1..].find('\'') {
+                    Some(offset) => pos + 1 + offset + 1,
+                    None => self.text.len(),
+                },
+                Some(b'[') | Some(b'{') => self.scan_bracketed(pos),
+                _ => self.scan_bare(pos),
+            }
+        }
+    }
+
+    // Multi-line strings end at the delimiter, which may be followed by up to
+    // two more quotes that still belong to the content (e.g. """a"""" ).
+    fn scan_multi_line_string(&self, mut p: usize, quote: u8) -> usize {
+        while p < self.bytes.len() {
+            if quote == b'"' && self.bytes[p] == b'\\' {
+                p += 2;
+                continue;
+            }
+            if self.bytes[p] == quote && self.byte_at(p + 1) == Some(quote) && self.byte_at(p + 2) == Some(quote) {
+                let mut end = p + 3;
+                let mut extra = 0;
+                while extra < 2 && self.byte_at(end) == Some(quote) {
+                    end += 1;
+                    extra += 1;
+                }
+                return end;
+            }
+            p += 1;
+        }
+        self.text.len()
+    }
+
+    // Arrays and inline tables, which may nest and (for arrays) span lines with comments
+    fn scan_bracketed(&self, pos: usize) -> usize {
+        let mut depth = 0usize;
+        let mut p = pos;
+        while let Some(b) = self.byte_at(p) {
+            match b {
+                b'[' | b'{' => {
+                    depth += 1;
+                    p += 1;
+                }
+                b']' | b'}' => {
+                    depth -= 1;
+                    p += 1;
+                    if depth == 0 {
+                        return p;
+                    }
+                }
+                b'"' | b'\'' => p = self.scan_value(p),
+                b'#' => {
+                    while let Some(c) = self.byte_at(p) {
+                        if c == b'\n' {
+                            break;
+                        }
+                        p += 1;
+                    }
+                }
+                _ => p += 1,
+            }
+        }
+        self.text.len()
+    }
+
+    // Numbers, booleans, dates and times
+    fn scan_bare(&self, pos: usize) -> usize {
+        let mut p = pos;
+        while let Some(b) = self.byte_at(p) {
+            if matches!(b, b' ' | b'\t' | b'\r' | b'\n' | b'#' | b',' | b']' | b'}') {
+                break;
+            }
+            p += 1;
+        }
+        // A date-time may use a space instead of 'T': 1979-05-27 07:32:00Z
+        let token = &self.bytes[pos..p];
+        let is_date = token.len() == 10
+            && token.iter().enumerate().all(|(i, b)| if i == 4 || i == 7 { *b == b'-' } else { b.is_ascii_digit() });
+        if is_date && self.byte_at(p) == Some(b' ') && self.byte_at(p + 1).is_some_and(|b| b.is_ascii_digit()) {
+            return self.scan_bare(p + 1);
+        }
+        p
+    }
+}
+
+// Helper function to parse the raw text of one value with the toml crate
+fn parse_raw_value(value_raw: &str) -> Result<Value, ThisProjectError> {
+    match toml::from_str::<Value>(&format!("value = {}", value_raw)) {
+        Ok(Value::Table(mut table)) => table.remove("value")
+            .ok_or_else(|| ThisProjectError::TomlEditError("Missing value".into())),
+        Ok(_) => Err(ThisProjectError::TomlEditError("Invalid value".into())),
+        Err(e) => Err(ThisProjectError::TomlVanillaDeserialStrError(e.to_string())),
+    }
+}
+
+// Helper function to write a key bare if possible, else as a quoted key
+fn format_toml_key(key: &str) -> String {
+    if !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        key.to_string()
+    } else {
+        format!("\"{}\"", escape_toml_basic_string(key))
+    }
+}
+
+// Helper function to escape a value for a TOML basic string ("...")
+fn escape_toml_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Formats a new value so that it looks like the value it replaces.
+///
+/// - A string that replaces a `'literal string'` is written as a literal
+///   string too, when it contains nothing that a literal string cannot hold.
+/// - An array that replaces a single-line array is written on one line;
+///   otherwise it uses the one-item-per-line layout of `serialize_ip_addresses`.
+fn format_toml_value_like(value: &Value, old_value_raw: &str, indent: &str, newline: &str) -> String {
+    let prefer_literal = old_value_raw.starts_with('\'') && !old_value_raw.starts_with("'''");
+    let multi_line_arrays = !old_value_raw.starts_with('[') || old_value_raw.contains('\n');
+    format_toml_value(value, indent, newline, multi_line_arrays, prefer_literal)
+}
+
+/// Formats a `toml::Value` as TOML value text.
+///
+/// Multi-line arrays use the same layout as `serialize_collaborator_to_toml`:
+///
+/// ```toml
+/// [
+///     "item",
+///     "item",
+/// ]
+/// ```
+fn format_toml_value(value: &Value, indent: &str, newline: &str, multi_line_arrays: bool, prefer_literal: bool) -> String {
+    match value {
+        Value::String(s) => {
+            let literal_ok = !s.contains(|c: char| c == '\'' || (c.is_control() && c != '\t'));
+            if prefer_literal && literal_ok {
+                format!("'{}'", s)
+            } else {
+                format!("\"{}\"", escape_toml_basic_string(s))
+            }
+        }
+        Value::Integer(i) => i.to_string(),
+        Value::Float(x) => {
+            if x.is_nan() {
+                "nan".to_string()
+            } else {
+                // {:?} keeps the ".0" (1.0 rather than 1) and writes inf as "inf"
+                format!("{:?}", x)
+            }
+        }
+        Value::Boolean(b) => b.to_string(),
+        Value::Datetime(dt) => dt.to_string(),
+        Value::Array(items) => {
+            if items.is_empty() {
+                return "[]".to_string();
+            }
+            let inner_indent = format!("{}    ", indent);
+            if multi_line_arrays {
+                let mut out = format!("[{}", newline);
+                for item in items {
+                    out.push_str(&inner_indent);
+                    out.push_str(&format_toml_value(item, &inner_indent, newline, false, prefer_literal));
+                    out.push(',');
+                    out.push_str(newline);
+                }
+                out.push_str(indent);
+                out.push(']');
+                out
+            } else {
+                let parts: Vec<String> = items.iter()
+                    .map(|item| format_toml_value(item, indent, newline, false, prefer_literal))
+                    .collect();
+                format!("[{}]", parts.join(", "))
+            }
+        }
+        Value::Table(table) => {
+            if table.is_empty() {
+                return "{}".to_string();
+            }
+            let parts: Vec<String> = table.iter()
+                .map(|(k, v)| format!("{} = {}", format_toml_key(k), format_toml_value(v, indent, newline, false, prefer_literal)))
+                .collect();
+            format!("{{ {} }}", parts.join(", "))
+        }
+    }
+}
+
+/// The root-table value for each field of a `CollaboratorTomlData`, in the
+/// order used by `serialize_collaborator_to_toml`. `None` means the key
+/// should not be in the file.
+fn collaborator_field_values(collaborator: &CollaboratorTomlData) -> Vec<(&'static str, Option<Value>)> {
+    let strings = |items: Vec<String>| Value::Array(items.into_iter().map(Value::String).collect());
+    vec![
+        ("user_name", Some(Value::String(collaborator.user_name.clone()))),
+        ("user_salt_list", Some(strings(collaborator.user_salt_list.iter().map(|salt| format!("0x{:x}", salt)).collect()))),
+        ("ipv4_addresses", collaborator.ipv4_addresses.as_ref().map(|v| strings(v.iter().map(|ip| ip.to_string()).collect()))),
+        ("ipv6_addresses", collaborator.ipv6_addresses.as_ref().map(|v| strings(v.iter().map(|ip| ip.to_string()).collect()))),
+        ("gpg_key_public", Some(Value::String(collaborator.gpg_key_public.clone()))),
+        ("sync_interval", Some(Value::Integer(collaborator.sync_interval as i64))),
+        ("updated_at_timestamp", Some(Value::Integer(collaborator.updated_at_timestamp as i64))),
+    ]
+}
+
+// Helper function: do two values mean the same thing for this field?
+// (e.g. "0x0A" and "0xa" are the same salt, "::0001" and "::1" the same address,
+// so such entries are left exactly as the user wrote them)
+fn field_values_equivalent(key: &str, old: &Value, new: &Value) -> bool {
+    fn parsed_list<T>(value: &Value, parse: fn(&str) -> Option<T>) -> Option<Vec<T>> {
+        match value {
+            Value::Array(arr) => arr.iter()
+                .map(|v| if let Value::String(s) = v { parse(s) } else { None })
+                .collect(),
+            _ => None,
+        }
+    }
+    match key {
+        "user_salt_list" => {
+            let parse = |s: &str| u128::from_str_radix(s.trim_start_matches("0x"), 16).ok();
+            let old = parsed_list(old, parse);
+            old.is_some() && old == parsed_list(new, parse)
+        }
+        "ipv4_addresses" => {
+            let parse = |s: &str| s.parse::<Ipv4Addr>().ok();
+            let old = parsed_list(old, parse);
+            old.is_some() && old == parsed_list(new, parse)
+        }
+        "ipv6_addresses" => {
+            let parse = |s: &str| s.parse::<Ipv6Addr>().ok();
+            let old = parsed_list(old, parse);
+            old.is_some() && old == parsed_list(new, parse)
+        }
+        _ => old == new,
+    }
+}
+
+/// Updates a collaborator TOML document in place, keeping its formatting.
+///
+/// This is the format-preserving alternative to
+/// `serialize_collaborator_to_toml` + `write_toml_to_file`: only fields
+/// whose value actually changed are rewritten, so hand-written comments,
+/// blank lines, key order and quoting of every other field survive.
+///
+/// - A changed field is replaced in place (`TomlDocument::set`).
+/// - A field that is missing from the file is inserted after the nearest
+///   preceding field, in `serialize_collaborator_to_toml` order
+///   (`TomlDocument::insert_after`).
+/// - `ipv4_addresses` / `ipv6_addresses` set to `None` are removed.
+/// - Other keys in the file (unknown to `CollaboratorTomlData`) are kept.
+///
+/// # Returns
+///
+/// Returns a `Result` containing:
+/// - `Ok`: The updated TOML text.
+/// - `Err`: A `ThisProjectError` if the original text is not valid TOML.
+fn update_collaborator_toml_preserving_format(toml_string: &str, collaborator: &CollaboratorTomlData) -> Result<String, ThisProjectError> {
+    let mut document = TomlDocument::parse(toml_string)?;
+    let mut previous_key: Option<&str> = None;
+
+    for (key, new_value) in collaborator_field_values(collaborator) {
+        match new_value {
+            Some(new_value) => {
+                match document.get(key) {
+                    Some(old_value) => {
+                        if !field_values_equivalent(key, &old_value, &new_value) {
+                            document.set(key, &new_value)?;
+                        }
+                    }
+                    None => match previous_key {
+                        Some(after_key) => document.insert_after(after_key, key, &new_value)?,
+                        None => document.set(key, &new_value)?,
+                    },
+                }
+                previous_key = Some(key);
+            }
+            None => {
+                document.remove(key);
+            }
+        }
+    }
+
+    Ok(document.to_string())
+}
+
+// Function to write a TOML string to a file
+fn write_toml_to_file(file_path: &str, toml_string: &str) -> Result<(), ThisProjectError> {
+    // Attempt to create the file.
+    let mut file = match File::create(file_path) {
+        Ok(file) => file,
+        Err(e) => return Err(ThisProjectError::IoError(e)),
+    };
+
+    // Attempt to write to the file.
+    if let Err(e) = file.write_all(toml_string.as_bytes()) {
+        return Err(ThisProjectError::IoError(e));
+    }
+
+    // Everything successful!
+    Ok(())
+}
+
+/// Reads one collaborator from TOML text.
+///
+/// Same extraction rules as `read_one_collaborator_setup_toml` in
+/// `deserialize_one_file_main.rs`.
+fn deserialize_collaborator_from_toml(toml_string: &str) -> Result<CollaboratorTomlData, ThisProjectError> {
+    let toml_value = match toml::from_str::<Value>(toml_string) {
+        Ok(value) => value,
+        Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(e.to_string())),
+    };
+
+    if let Value::Table(table) = toml_value {
+
+        // Extract user_name
+        let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+            s.clone()
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+        };
+
+        // Extract user_salt_list
+        let user_salt_list = if let Some(Value::Array(arr)) = table.get("user_salt_list") {
+            arr.iter()
+                .map(|val| {
+                    if let Value::String(s) = val {
+                        u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                            .map_err(ThisProjectError::ParseIntError)
+                    } else {
+                        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                    }
+                })
+                .collect::<Result<Vec<u128>, ThisProjectError>>()?
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_salt_list".into()));
+        };
+
+        // Extract ipv4_addresses
+        let ipv4_addresses = extract_ipv4_addresses(&table, "ipv4_addresses")?;
+
+        // Extract ipv6_addresses
+        let ipv6_addresses = extract_ipv6_addresses(&table, "ipv6_addresses")?;
+
+        // Extract gpg_key_public
+        let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+            s.clone()
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+        };
+
+        // Extract sync_interval
+        let sync_interval = extract_u64(&table, "sync_interval")?;
+
+        // Extract updated_at_timestamp
+        let updated_at_timestamp = extract_u64(&table, "updated_at_timestamp")?;
+
+        Ok(CollaboratorTomlData {
+            user_name,
+            user_salt_list,
+            ipv4_addresses,
+            ipv6_addresses,
+            gpg_key_public,
+            sync_interval,
+            updated_at_timestamp,
+        })
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure: Expected a table".into()))
+    }
+}
+
+fn extract_ipv4_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv4Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv4Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+fn extract_ipv6_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv6Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv6Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+fn main() {
+    // Specify the username of the collaborator to update
+    let username = "alice";
+    let file_path = Path::new("project_graph_data/collaborator_files_address_book")
+        .join(format!("{}__collaborator.toml", username));
+
+    let toml_string = match fs::read_to_string(&file_path) {
+        Ok(toml_string) => toml_string,
+        Err(e) => {
+            println!("Error reading {}: {}", file_path.display(), e);
+            return;
+        }
+    };
+
+    // Show the document structure
+    match TomlDocument::parse(&toml_string) {
+        Ok(document) => {
+            println!("Root keys: {:?}", document.root_keys());
+            println!("Tables: {:?}", document.table_names());
+        }
+        Err(e) => {
+            println!("Error parsing {}: {}", file_path.display(), e);
+            return;
+        }
+    }
+
+    // Change one field, as an application would
+    let mut collaborator = match deserialize_collaborator_from_toml(&toml_string) {
+        Ok(collaborator) => collaborator,
+        Err(e) => {
+            println!("Error reading collaborator data for {}: {}", username, e);
+            return;
+        }
+    };
+    collaborator.sync_interval = 120;
+
+    // Write it back: only sync_interval changes, comments and layout stay
+    match update_collaborator_toml_preserving_format(&toml_string, &collaborator) {
+        Ok(updated) => {
+            println!("Updated TOML:\n{}", updated);
+            match write_toml_to_file(&file_path.to_string_lossy(), &updated) {
+                Ok(_) => println!("TOML data written to file successfully."),
+                Err(e) => println!("Error writing to file: {}", e),
+            }
+        }
+        Err(e) => println!("Error updating TOML: {}", e),
+    }
+}
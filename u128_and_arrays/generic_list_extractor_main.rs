@@ -0,0 +1,442 @@
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::ffi::OsStr;
+use std::str::FromStr;
+use toml::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    ListError(String),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::ListError(err) => write!(f, "List Error: {}", err),
+        }
+    }
+}
+
+/// A check on one list element; returns why the element is not allowed.
+type ElementPolicy<T> = fn(&T) -> Result<(), String>;
+
+/// How `extract_parsed_list` reads one list field.
+///
+/// - `required`: a missing key is an error. Otherwise a missing key, or an
+///   empty list, reads as `None`.
+/// - `min_len`, `max_len`: allowed number of entries when the key is present.
+/// - `element_policies`: checked for every parsed element, in order.
+struct ListRules<T: 'static> {
+    required: bool,
+    min_len: usize,
+    max_len: usize,
+    element_policies: &'static [ElementPolicy<T>],
+}
+
+/// `user_salt_list`: at least one salt, no zero salts.
+const SALT_LIST_RULES: ListRules<u128> = ListRules {
+    required: true,
+    min_len: 1,
+    max_len: 64,
+    element_policies: &[reject_zero_salt],
+};
+
+/// `ipv4_addresses`: optional, no `0.0.0.0`.
+const IPV4_LIST_RULES: ListRules<Ipv4Addr> = ListRules {
+    required: false,
+    min_len: 0,
+    max_len: 32,
+    element_policies: &[reject_unspecified_ipv4],
+};
+
+/// `ipv6_addresses`: optional, no `::`.
+const IPV6_LIST_RULES: ListRules<Ipv6Addr> = ListRules {
+    required: false,
+    min_len: 0,
+    max_len: 32,
+    element_policies: &[reject_unspecified_ipv6],
+};
+
+fn reject_zero_salt(salt: &u128) -> Result<(), String> {
+    if *salt == 0 {
+        Err("a salt of zero is not allowed".into())
+    } else {
+        Ok(())
+    }
+}
+
+fn reject_unspecified_ipv4(addr: &Ipv4Addr) -> Result<(), String> {
+    if addr.is_unspecified() {
+        Err("the unspecified address 0.0.0.0 is not a peer address".into())
+    } else {
+        Ok(())
+    }
+}
+
+fn reject_unspecified_ipv6(addr: &Ipv6Addr) -> Result<(), String> {
+    if addr.is_unspecified() {
+        Err("the unspecified address :: is not a peer address".into())
+    } else {
+        Ok(())
+    }
+}
+
+// Helper function: salts are written as "0x" + hex
+fn parse_hex_salt(s: &str) -> Result<u128, String> {
+    u128::from_str_radix(s.trim_start_matches("0x"), 16).map_err(|e| e.to_string())
+}
+
+// Helper function: the counterpart of `parse_hex_salt`
+fn format_hex_salt(salt: &u128) -> String {
+    format!("0x{:x}", salt)
+}
+
+/// Extracts a list of strings at `key` and parses each with `T::from_str`.
+///
+/// Works for any list whose entries have a `FromStr` impl (the IP address
+/// lists); see `extract_parsed_list_with` for the rules.
+fn extract_parsed_list<T>(
+    table: &toml::map::Map<String, Value>,
+    key: &str,
+    rules: &ListRules<T>,
+) -> Result<Option<Vec<T>>, ThisProjectError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    extract_parsed_list_with(table, key, rules, |s| s.parse::<T>().map_err(|e| e.to_string()))
+}
+
+/// Extracts a list of strings at `key`, parsing each with `parse` and
+/// checking it against `rules`.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::ListError` naming the key, and for element
+/// errors the element index, e.g. `ipv4_addresses[2]: "bad": invalid IPv4
+/// address syntax`. Fails on the first error:
+/// - the key is missing and `rules.required`
+/// - the value is not an array
+/// - an element is not a string, does not parse, or fails a policy
+/// - the number of entries is outside `rules.min_len..=rules.max_len`
+fn extract_parsed_list_with<T>(
+    table: &toml::map::Map<String, Value>,
+    key: &str,
+    rules: &ListRules<T>,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<Option<Vec<T>>, ThisProjectError> {
+    let arr = match table.get(key) {
+        Some(Value::Array(arr)) => arr,
+        Some(_) => return Err(ThisProjectError::ListError(format!("{}: expected an array", key))),
+        None if rules.required => return Err(ThisProjectError::ListError(format!("Missing {}", key))),
+        None => return Ok(None),
+    };
+
+    if arr.len() < rules.min_len || arr.len() > rules.max_len {
+        return Err(ThisProjectError::ListError(format!(
+            "{}: {} entries, expected {} to {}",
+            key, arr.len(), rules.min_len, rules.max_len
+        )));
+    }
+
+    let mut items = Vec::with_capacity(arr.len());
+    for (index, val) in arr.iter().enumerate() {
+        let s = match val {
+            Value::String(s) => s,
+            _ => return Err(ThisProjectError::ListError(format!("{}[{}]: expected a string", key, index))),
+        };
+        let item = parse(s).map_err(|e| ThisProjectError::ListError(format!("{}[{}]: \"{}\": {}", key, index, s, e)))?;
+        for policy in rules.element_policies {
+            policy(&item).map_err(|e| ThisProjectError::ListError(format!("{}[{}]: \"{}\": {}", key, index, s, e)))?;
+        }
+        items.push(item);
+    }
+
+    if items.is_empty() && !rules.required {
+        Ok(None)
+    } else {
+        Ok(Some(items))
+    }
+}
+
+// Helper function: TOML table -> CollaboratorTomlData, failing on the first bad field
+fn collaborator_from_toml_table(table: &toml::map::Map<String, Value>) -> Result<CollaboratorTomlData, ThisProjectError> {
+
+    // Extract user_name
+    let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+    };
+
+    // Extract user_salt_list (required, so never None)
+    let user_salt_list = extract_parsed_list_with(table, "user_salt_list", &SALT_LIST_RULES, parse_hex_salt)?
+        .unwrap_or_default();
+
+    // Extract ipv4_addresses
+    let ipv4_addresses = extract_parsed_list::<Ipv4Addr>(table, "ipv4_addresses", &IPV4_LIST_RULES)?;
+
+    // Extract ipv6_addresses
+    let ipv6_addresses = extract_parsed_list::<Ipv6Addr>(table, "ipv6_addresses", &IPV6_LIST_RULES)?;
+
+    // Extract gpg_key_public
+    let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+    };
+
+    // Extract sync_interval
+    let sync_interval = extract_u64(table, "sync_interval")?;
+
+    // Extract updated_at_timestamp
+    let updated_at_timestamp = extract_u64(table, "updated_at_timestamp")?;
+
+    Ok(CollaboratorTomlData {
+        user_name,
+        user_salt_list,
+        ipv4_addresses,
+        ipv6_addresses,
+        gpg_key_public,
+        sync_interval,
+        updated_at_timestamp,
+    })
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+// Helper function: TOML text -> CollaboratorTomlData
+fn deserialize_collaborator_from_toml(toml_string: &str) -> Result<CollaboratorTomlData, ThisProjectError> {
+    match toml::from_str::<Value>(toml_string) {
+        Ok(Value::Table(table)) => collaborator_from_toml_table(&table),
+        Ok(_) => Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure: Expected a table".into())),
+        Err(e) => Err(ThisProjectError::TomlVanillaDeserialStrError(e.to_string())),
+    }
+}
+
+/// Toml Deserialization: Reads collaborator setup data from TOML files in a
+/// specified directory.
+///
+/// Every `*.toml` file in `project_graph_data/collaborator_files_address_book`
+/// is parsed with `deserialize_collaborator_from_toml`; a file that does not
+/// parse is skipped and its error is added to the error vector. Every list
+/// field is read by `extract_parsed_list` with its `ListRules`, so list
+/// errors name the failing element and length limits are checked.
+///
+/// # Returns
+///
+/// Returns a `Result` containing:
+/// - `Ok`: A tuple with:
+///     - A vector of successfully parsed `CollaboratorTomlData` instances.
+///     - A vector of any `ThisProjectError` encountered during parsing.
+/// - `Err`: A `ThisProjectError` if there was an error reading the directory or any file.
+fn read_a_collaborator_setup_toml() -> Result<(Vec<CollaboratorTomlData>, Vec<ThisProjectError>), ThisProjectError> {
+    let mut collaborators = Vec::new();
+    let mut errors = Vec::new();
+    let dir_path = Path::new("project_graph_data/collaborator_files_address_book");
+
+    for entry in fs::read_dir(dir_path)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
+            let toml_string = fs::read_to_string(&path)?;
+
+            match deserialize_collaborator_from_toml(&toml_string) {
+                Ok(collaborator) => collaborators.push(collaborator),
+                Err(e) => errors.push(e),
+            }
+        }
+    }
+
+    Ok((collaborators, errors))
+}
+
+/// Serializes a `CollaboratorTomlData` struct into a TOML-formatted string.
+///
+/// One `key = value` line per field, in struct order, with escaped strings
+/// and `None` address lists left out; every list is written by
+/// `serialize_list` or `serialize_list_with`.
+fn serialize_collaborator_to_toml(collaborator: &CollaboratorTomlData) -> Result<String, ThisProjectError> {
+    let mut toml_string = String::new();
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", escape_toml_basic_string(&collaborator.user_name)));
+
+    // Add user_salt_list
+    serialize_list_with(&mut toml_string, "user_salt_list", &collaborator.user_salt_list, format_hex_salt);
+
+    // Add ipv4_addresses
+    if let Some(addresses) = &collaborator.ipv4_addresses {
+        serialize_list(&mut toml_string, "ipv4_addresses", addresses);
+    }
+
+    // Add ipv6_addresses
+    if let Some(addresses) = &collaborator.ipv6_addresses {
+        serialize_list(&mut toml_string, "ipv6_addresses", addresses);
+    }
+
+    // Add gpg_key_public
+    toml_string.push_str(&format!("gpg_key_public = \"{}\"\n", escape_toml_basic_string(&collaborator.gpg_key_public)));
+
+    // Add sync_interval
+    toml_string.push_str(&format!("sync_interval = {}\n", collaborator.sync_interval));
+
+    // Add updated_at_timestamp
+    toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp));
+
+    Ok(toml_string)
+}
+
+/// Writes `items` as a multi-line TOML array of strings using `Display`;
+/// generalized from `serialize_ip_addresses`.
+fn serialize_list<T: fmt::Display>(toml_string: &mut String, key: &str, items: &[T]) {
+    serialize_list_with(toml_string, key, items, |item| item.to_string());
+}
+
+/// Writes `items` as a multi-line TOML array of strings, formatting each
+/// with `format` (the counterpart of the `parse` in `extract_parsed_list_with`).
+fn serialize_list_with<T>(toml_string: &mut String, key: &str, items: &[T], format: impl Fn(&T) -> String) {
+    toml_string.push_str(&format!("{} = [\n", key));
+    for item in items {
+        toml_string.push_str(&format!("    \"{}\",\n", escape_toml_basic_string(&format(item))));
+    }
+    toml_string.push_str("]\n");
+}
+
+// Helper function to escape a value for a TOML basic string ("...")
+fn escape_toml_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Function to write a TOML string to a file
+fn write_toml_to_file(file_path: &str, toml_string: &str) -> Result<(), ThisProjectError> {
+    // Attempt to create the file.
+    let mut file = match File::create(file_path) {
+        Ok(file) => file,
+        Err(e) => return Err(ThisProjectError::IoError(e)),
+    };
+
+    // Attempt to write to the file.
+    if let Err(e) = file.write_all(toml_string.as_bytes()) {
+        return Err(ThisProjectError::IoError(e));
+    }
+
+    // Everything successful!
+    Ok(())
+}
+
+fn main() {
+    // Element and length errors, by key and index
+    for bad_toml in [
+        "user_name = \"Carol\"\nuser_salt_list = [\"0x1\", \"0x0\"]\ngpg_key_public = \"\"\nsync_interval = 60\nupdated_at_timestamp = 0\n",
+        "user_name = \"Carol\"\nuser_salt_list = []\ngpg_key_public = \"\"\nsync_interval = 60\nupdated_at_timestamp = 0\n",
+        "user_name = \"Carol\"\nuser_salt_list = [\"0x1\"]\nipv6_addresses = [\"::1\", 6]\ngpg_key_public = \"\"\nsync_interval = 60\nupdated_at_timestamp = 0\n",
+        "user_name = \"Carol\"\nuser_salt_list = [\"0x1\"]\nipv4_addresses = [\"10.0.0.1\", \"0.0.0.0\"]\ngpg_key_public = \"\"\nsync_interval = 60\nupdated_at_timestamp = 0\n",
+    ] {
+        if let Err(e) = deserialize_collaborator_from_toml(bad_toml) {
+            println!("{}", e);
+        }
+    }
+
+    // Example CollaboratorTomlData instance
+    let collaborator = CollaboratorTomlData {
+        user_name: "Bob".to_string(),
+        user_salt_list: vec![0x123456789abcdef0, 0xabcdef0123456789],
+        ipv4_addresses: Some(vec![Ipv4Addr::new(192, 168, 1, 1), Ipv4Addr::new(10, 0, 0, 1)]),
+        ipv6_addresses: Some(vec![Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)]),
+        gpg_key_public: "-----BEGIN PGP PUBLIC KEY BLOCK----- ...".to_string(),
+        sync_interval: 300,
+        updated_at_timestamp: 1728308000,
+    };
+
+    match serialize_collaborator_to_toml(&collaborator) {
+        Ok(toml_string) => {
+            println!("Serialized TOML:\n{}", toml_string);
+
+            // Write the TOML string to a file (example file path)
+            match write_toml_to_file("collaborator_data.toml", &toml_string) {
+                Ok(_) => println!("TOML data written to file successfully."),
+                Err(e) => println!("Error writing to file: {}", e),
+            }
+        }
+        Err(e) => println!("Error serializing to TOML: {}", e),
+    }
+
+    match read_a_collaborator_setup_toml() {
+        Ok((collaborators, errors)) => {
+            if !errors.is_empty() {
+                println!("Errors encountered:");
+                for err in errors {
+                    println!("{}", err);
+                }
+            }
+
+            println!("Collaborators:");
+            for collaborator in collaborators {
+                println!("{:#?}", collaborator);
+            }
+        }
+        Err(e) => {
+            println!("Error reading TOML files: {}", e);
+        }
+    }
+}
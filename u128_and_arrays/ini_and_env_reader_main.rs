@@ -0,0 +1,544 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use toml::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    KeyValueSyntaxError(String),
+    FieldError(String),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::KeyValueSyntaxError(err) => write!(f, "Key/Value Syntax Error: {}", err),
+            ThisProjectError::FieldError(err) => write!(f, "Field Error: {}", err),
+        }
+    }
+}
+
+/// Keys whose value is a comma-separated list in INI and `.env` files.
+const LIST_KEYS: [&str; 3] = ["user_salt_list", "ipv4_addresses", "ipv6_addresses"];
+
+/// Keys whose value is an integer in INI and `.env` files.
+const INTEGER_KEYS: [&str; 2] = ["sync_interval", "updated_at_timestamp"];
+
+/// One `key = value` line: (line number, key, value).
+type KeyValueLine = (usize, String, String);
+
+/// Builds a `CollaboratorTomlData` from a TOML table.
+///
+/// This is the extraction part of `read_one_collaborator_setup_toml`
+/// (see `deserialize_one_file_main.rs`), separated from file reading so
+/// that any format which can be mapped into a `toml::map::Map` -- TOML,
+/// INI sections, `.env` files -- goes through exactly the same extractors
+/// and produces exactly the same errors.
+///
+/// # Error Handling
+///
+/// Returns the first error found, as `read_one_collaborator_setup_toml` does.
+fn collaborator_from_toml_table(table: &toml::map::Map<String, Value>) -> Result<CollaboratorTomlData, ThisProjectError> {
+
+    // Extract user_name
+    let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+    };
+
+    // Extract user_salt_list
+    let user_salt_list = if let Some(Value::Array(arr)) = table.get("user_salt_list") {
+        arr.iter()
+            .map(|val| {
+                if let Value::String(s) = val {
+                    u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                        .map_err(ThisProjectError::ParseIntError)
+                } else {
+                    Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                }
+            })
+            .collect::<Result<Vec<u128>, ThisProjectError>>()?
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_salt_list".into()));
+    };
+
+    // Extract ipv4_addresses
+    let ipv4_addresses = extract_ipv4_addresses(table, "ipv4_addresses")?;
+
+    // Extract ipv6_addresses
+    let ipv6_addresses = extract_ipv6_addresses(table, "ipv6_addresses")?;
+
+    // Extract gpg_key_public
+    let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+    };
+
+    // Extract sync_interval
+    let sync_interval = extract_u64(table, "sync_interval")?;
+
+    // Extract updated_at_timestamp
+    let updated_at_timestamp = extract_u64(table, "updated_at_timestamp")?;
+
+    Ok(CollaboratorTomlData {
+        user_name,
+        user_salt_list,
+        ipv4_addresses,
+        ipv6_addresses,
+        gpg_key_public,
+        sync_interval,
+        updated_at_timestamp,
+    })
+}
+
+fn extract_ipv4_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv4Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv4Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+fn extract_ipv6_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv6Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv6Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+/// Maps one flat list of `key = value` pairs to a TOML table.
+///
+/// INI and `.env` values are untyped text, so the type is chosen by key,
+/// matching what the TOML files hold:
+///
+/// - `user_salt_list`, `ipv4_addresses`, `ipv6_addresses`: split on `,` into
+///   an array of strings (an empty value gives an empty array).
+/// - `sync_interval`, `updated_at_timestamp`: an integer if the text is one;
+///   otherwise left as a string so that `extract_u64` reports it as invalid.
+/// - Everything else: a string.
+///
+/// Keys are lower-cased, so `SYNC_INTERVAL` (the usual `.env` spelling) and
+/// `sync_interval` are the same key.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::KeyValueSyntaxError` if a key appears twice.
+fn key_value_pairs_to_toml_table(
+    pairs: &[KeyValueLine],
+    source_name: &str,
+) -> Result<toml::map::Map<String, Value>, ThisProjectError> {
+    let mut table = toml::map::Map::new();
+
+    for (line, key, raw_value) in pairs {
+        let key = key.to_lowercase();
+
+        let value = if LIST_KEYS.contains(&key.as_str()) {
+            let items = raw_value.split(',')
+                .map(|item| item.trim())
+                .filter(|item| !item.is_empty())
+                .map(|item| Value::String(item.to_string()))
+                .collect();
+            Value::Array(items)
+        } else if INTEGER_KEYS.contains(&key.as_str()) {
+            match raw_value.trim().parse::<i64>() {
+                Ok(i) => Value::Integer(i),
+                Err(_) => Value::String(raw_value.clone()),
+            }
+        } else {
+            Value::String(raw_value.clone())
+        };
+
+        if table.insert(key.clone(), value).is_some() {
+            return Err(ThisProjectError::KeyValueSyntaxError(format!(
+                "{} line {}: Duplicate key {}", source_name, line, key
+            )));
+        }
+    }
+
+    Ok(table)
+}
+
+// Helper function to strip an inline comment from an unquoted value
+fn strip_inline_comment(value: &str) -> &str {
+    // A comment marker must follow whitespace, so "a#b" keeps its '#'
+    let bytes = value.as_bytes();
+    for i in 1..bytes.len() {
+        if (bytes[i] == b'#' || bytes[i] == b';') && (bytes[i - 1] == b' ' || bytes[i - 1] == b'\t') {
+            return value[..i].trim_end();
+        }
+    }
+    value
+}
+
+// Helper function to parse the value part of a key/value line
+// (quoted with "..." (escapes allowed), quoted with '...' (literal), or bare)
+fn parse_key_value_value(raw: &str, source_name: &str, line: usize) -> Result<String, ThisProjectError> {
+    let raw = raw.trim();
+
+    if let Some(rest) = raw.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = rest.chars();
+        loop {
+            match chars.next() {
+                Some('"') => break,
+                Some('\\') => match chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some('t') => value.push('\t'),
+                    Some('"') => value.push('"'),
+                    Some('\\') => value.push('\\'),
+                    Some(c) => {
+                        return Err(ThisProjectError::KeyValueSyntaxError(format!(
+                            "{} line {}: Invalid escape \\{}", source_name, line, c
+                        )));
+                    }
+                    None => {
+                        return Err(ThisProjectError::KeyValueSyntaxError(format!(
+                            "{} line {}: Unterminated double-quoted value", source_name, line
+                        )));
+                    }
+                },
+                Some(c) => value.push(c),
+                None => {
+                    return Err(ThisProjectError::KeyValueSyntaxError(format!(
+                        "{} line {}: Unterminated double-quoted value", source_name, line
+                    )));
+                }
+            }
+        }
+        check_after_quote(chars.as_str(), source_name, line)?;
+        Ok(value)
+    } else if let Some(rest) = raw.strip_prefix('\'') {
+        match rest.find('\'') {
+            Some(end) => {
+                check_after_quote(&rest[end + 1..], source_name, line)?;
+                Ok(rest[..end].to_string())
+            }
+            None => Err(ThisProjectError::KeyValueSyntaxError(format!(
+                "{} line {}: Unterminated single-quoted value", source_name, line
+            ))),
+        }
+    } else {
+        Ok(strip_inline_comment(raw).to_string())
+    }
+}
+
+// Helper function to allow only whitespace or a comment after a closing quote
+fn check_after_quote(rest: &str, source_name: &str, line: usize) -> Result<(), ThisProjectError> {
+    let rest = rest.trim();
+    if rest.is_empty() || rest.starts_with('#') || rest.starts_with(';') {
+        Ok(())
+    } else {
+        Err(ThisProjectError::KeyValueSyntaxError(format!(
+            "{} line {}: Unexpected text after quoted value: {}", source_name, line, rest
+        )))
+    }
+}
+
+// Helper function to split "key = value" and check the key
+fn split_key_value<'a>(line_text: &'a str, source_name: &str, line: usize) -> Result<(&'a str, &'a str), ThisProjectError> {
+    match line_text.split_once('=') {
+        Some((key, value)) => {
+            let key = key.trim();
+            if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+                return Err(ThisProjectError::KeyValueSyntaxError(format!(
+                    "{} line {}: Invalid key \"{}\"", source_name, line, key
+                )));
+            }
+            Ok((key, value))
+        }
+        None => Err(ThisProjectError::KeyValueSyntaxError(format!(
+            "{} line {}: Expected key = value", source_name, line
+        ))),
+    }
+}
+
+/// Reads collaborators from INI text, one collaborator per `[section]`.
+///
+/// # INI Format
+///
+/// ```ini
+/// ; Collaborators for host-7
+/// [alice]
+/// user_salt_list = 0x11111111111111111111111111111111, 0x11111111111111111111111111111112
+/// ipv4_addresses = 192.168.1.1, 10.0.0.1
+/// ipv6_addresses = fe80::1, ::1
+/// gpg_key_public = "-----BEGIN PGP PUBLIC KEY BLOCK----- ..."
+/// sync_interval = 60
+/// updated_at_timestamp = 1728307160
+/// ```
+///
+/// - Lines starting with `;` or `#` are comments; blank lines are ignored.
+/// - Values may be bare, `"double-quoted"` (with `\n`, `\t`, `\"`, `\\`
+///   escapes) or `'single-quoted'` (literal).
+/// - List fields are comma-separated (see `key_value_pairs_to_toml_table`).
+/// - If a section has no `user_name` key, the section name is used.
+///
+/// Each section is then mapped by `collaborator_from_toml_table`, so the
+/// field rules and error messages are the same as for TOML files.
+///
+/// # Error Handling
+///
+/// As with `read_a_collaborator_setup_toml`:
+/// - A syntax error (unterminated quote, key outside a section, line
+///   without `=`, duplicate key, duplicate section) returns `Err`, since
+///   the file as a whole cannot be trusted.
+/// - A section with bad field data is skipped, and its error is added to
+///   the returned error vector as a `FieldError` naming the source and
+///   section, e.g. `collaborators.ini [bob]: TOML Error: Missing user_salt_list`.
+///
+/// # Returns
+///
+/// Returns a `Result` containing:
+/// - `Ok`: A tuple with:
+///     - A vector of successfully parsed `CollaboratorTomlData` instances.
+///     - A vector of any `ThisProjectError` encountered while mapping sections.
+/// - `Err`: A `ThisProjectError` if the INI text itself is malformed.
+fn read_collaborators_from_ini_str(
+    ini_string: &str,
+    source_name: &str,
+) -> Result<(Vec<CollaboratorTomlData>, Vec<ThisProjectError>), ThisProjectError> {
+    // (section name, its key/value lines)
+    let mut sections: Vec<(String, Vec<KeyValueLine>)> = Vec::new();
+
+    for (i, line_text) in ini_string.lines().enumerate() {
+        let line = i + 1;
+        let trimmed = line_text.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with(';') || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(header) = trimmed.strip_prefix('[') {
+            match header.strip_suffix(']') {
+                Some(name) if !name.trim().is_empty() => {
+                    let name = name.trim();
+                    if sections.iter().any(|(section_name, _)| section_name == name) {
+                        return Err(ThisProjectError::KeyValueSyntaxError(format!(
+                            "{} line {}: Duplicate section [{}]", source_name, line, name
+                        )));
+                    }
+                    sections.push((name.to_string(), Vec::new()));
+                }
+                _ => {
+                    return Err(ThisProjectError::KeyValueSyntaxError(format!(
+                        "{} line {}: Invalid section header", source_name, line
+                    )));
+                }
+            }
+            continue;
+        }
+
+        let (key, raw_value) = split_key_value(trimmed, source_name, line)?;
+        let value = parse_key_value_value(raw_value, source_name, line)?;
+        match sections.last_mut() {
+            Some((_, pairs)) => pairs.push((line, key.to_string(), value)),
+            None => {
+                return Err(ThisProjectError::KeyValueSyntaxError(format!(
+                    "{} line {}: Key {} is outside of any [section]", source_name, line, key
+                )));
+            }
+        }
+    }
+
+    let mut collaborators = Vec::new();
+    let mut errors = Vec::new();
+
+    for (section_name, pairs) in sections {
+        let mut table = key_value_pairs_to_toml_table(&pairs, source_name)?;
+        if !table.contains_key("user_name") {
+            table.insert("user_name".to_string(), Value::String(section_name.clone()));
+        }
+
+        // Field errors say which section they came from
+        match collaborator_from_toml_table(&table) {
+            Ok(collaborator) => collaborators.push(collaborator),
+            Err(e) => errors.push(ThisProjectError::FieldError(format!("{} [{}]: {}", source_name, section_name, e))),
+        }
+    }
+
+    Ok((collaborators, errors))
+}
+
+/// Reads one collaborator from `.env`-style `KEY=value` text.
+///
+/// # `.env` Format
+///
+/// ```text
+/// # alice on host-7
+/// USER_NAME=alice
+/// USER_SALT_LIST=0x11111111111111111111111111111111,0x11111111111111111111111111111112
+/// IPV4_ADDRESSES=192.168.1.1,10.0.0.1
+/// IPV6_ADDRESSES=fe80::1,::1
+/// GPG_KEY_PUBLIC="-----BEGIN PGP PUBLIC KEY BLOCK----- ..."
+/// SYNC_INTERVAL=60
+/// export UPDATED_AT_TIMESTAMP=1728307160
+/// ```
+///
+/// - Keys are case-insensitive (`USER_NAME` and `user_name` are the same).
+/// - An optional leading `export ` is ignored, so the file can also be
+///   sourced by a shell.
+/// - Comments, quoting and list rules are the same as for INI files.
+///
+/// # Error Handling
+///
+/// Like `read_one_collaborator_setup_toml`, the first error is returned:
+/// either a `KeyValueSyntaxError` for malformed lines, or a `FieldError`
+/// naming the source, e.g. `alice.env: TOML Error: Missing user_salt_list`.
+fn read_collaborator_from_env_str(env_string: &str, source_name: &str) -> Result<CollaboratorTomlData, ThisProjectError> {
+    let mut pairs = Vec::new();
+
+    for (i, line_text) in env_string.lines().enumerate() {
+        let line = i + 1;
+        let trimmed = line_text.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let trimmed = trimmed.strip_prefix("export ").map(str::trim_start).unwrap_or(trimmed);
+        let (key, raw_value) = split_key_value(trimmed, source_name, line)?;
+        let value = parse_key_value_value(raw_value, source_name, line)?;
+        pairs.push((line, key.to_string(), value));
+    }
+
+    let table = key_value_pairs_to_toml_table(&pairs, source_name)?;
+    collaborator_from_toml_table(&table)
+        .map_err(|e| ThisProjectError::FieldError(format!("{}: {}", source_name, e)))
+}
+
+/// Reads collaborators from an INI file. See `read_collaborators_from_ini_str`.
+fn read_collaborators_from_ini_file(file_path: &Path) -> Result<(Vec<CollaboratorTomlData>, Vec<ThisProjectError>), ThisProjectError> {
+    let ini_string = fs::read_to_string(file_path)?;
+    read_collaborators_from_ini_str(&ini_string, &file_path.display().to_string())
+}
+
+/// Reads one collaborator from a `.env` file. See `read_collaborator_from_env_str`.
+fn read_collaborator_from_env_file(file_path: &Path) -> Result<CollaboratorTomlData, ThisProjectError> {
+    let env_string = fs::read_to_string(file_path)?;
+    read_collaborator_from_env_str(&env_string, &file_path.display().to_string())
+}
+
+fn main() {
+    // Legacy INI file with one section per collaborator
+    let ini_path = Path::new("project_graph_data/legacy/collaborators.ini");
+    match read_collaborators_from_ini_file(ini_path) {
+        Ok((collaborators, errors)) => {
+            if !errors.is_empty() {
+                println!("Errors encountered:");
+                for err in errors {
+                    println!("{}", err);
+                }
+            }
+
+            println!("Collaborators:");
+            for collaborator in collaborators {
+                println!(
+                    "{}: {} salts, IPv4 {:?}, IPv6 {:?}, sync every {}s, updated at {}, key {}",
+                    collaborator.user_name,
+                    collaborator.user_salt_list.len(),
+                    collaborator.ipv4_addresses,
+                    collaborator.ipv6_addresses,
+                    collaborator.sync_interval,
+                    collaborator.updated_at_timestamp,
+                    collaborator.gpg_key_public.lines().next().unwrap_or("")
+                );
+            }
+        }
+        Err(e) => {
+            println!("Error reading INI file: {}", e);
+        }
+    }
+
+    // Legacy .env file for one collaborator
+    let env_path = Path::new("project_graph_data/legacy/alice.env");
+    match read_collaborator_from_env_file(env_path) {
+        Ok(collaborator) => {
+            println!("Collaborator Data from {}:", env_path.display());
+            println!("{:#?}", collaborator);
+        }
+        Err(e) => {
+            println!("Error reading collaborator data from {}: {}", env_path.display(), e);
+        }
+    }
+}
@@ -0,0 +1,882 @@
+use std::fmt;
+use toml::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug, PartialEq)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    JsonVanillaDeserialStrError(String), // use without serede crate (good)
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::JsonVanillaDeserialStrError(err) => write!(f, "JSON Error: {}", err),
+        }
+    }
+}
+
+/// A JSON value, parsed and written without the `serde` crate.
+///
+/// # Numbers
+///
+/// Numbers keep their original text (e.g. `"1728307160"`) instead of being
+/// converted to `f64`. This way no precision is lost when a number is read
+/// and written again, and each field extractor decides how to parse it
+/// (see `extract_json_u64`).
+///
+/// # Objects
+///
+/// Objects are stored as a list of `(key, value)` pairs so that the key order
+/// of the input is kept when writing the value back out.
+#[derive(Debug, Clone, PartialEq)]
+enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    // Helper function to look up a key in a JSON object
+    fn get(&self, key: &str) -> Option<&JsonValue> {
+        if let JsonValue::Object(members) = self {
+            members.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+        } else {
+            None
+        }
+    }
+}
+
+/// Serializes a `JsonValue` to a pretty-printed JSON string (4-space indent).
+///
+/// Strings are escaped per RFC 8259: `"` `\` and control characters are
+/// escaped, everything else (including non-ASCII) is written as-is in UTF-8.
+fn write_json_value(value: &JsonValue) -> String {
+    let mut json_string = String::new();
+    write_json_value_indented(&mut json_string, value, 0);
+    json_string
+}
+
+// Helper function to write one value at a given indent level
+fn write_json_value_indented(json_string: &mut String, value: &JsonValue, indent: usize) {
+    match value {
+        JsonValue::Null => json_string.push_str("null"),
+        JsonValue::Bool(b) => json_string.push_str(if *b { "true" } else { "false" }),
+        JsonValue::Number(n) => json_string.push_str(n),
+        JsonValue::String(s) => write_json_string(json_string, s),
+        JsonValue::Array(items) => {
+            if items.is_empty() {
+                json_string.push_str("[]");
+                return;
+            }
+            json_string.push_str("[\n");
+            for (i, item) in items.iter().enumerate() {
+                push_indent(json_string, indent + 1);
+                write_json_value_indented(json_string, item, indent + 1);
+                if i + 1 < items.len() {
+                    json_string.push(',');
+                }
+                json_string.push('\n');
+            }
+            push_indent(json_string, indent);
+            json_string.push(']');
+        }
+        JsonValue::Object(members) => {
+            if members.is_empty() {
+                json_string.push_str("{}");
+                return;
+            }
+            json_string.push_str("{\n");
+            for (i, (key, member)) in members.iter().enumerate() {
+                push_indent(json_string, indent + 1);
+                write_json_string(json_string, key);
+                json_string.push_str(": ");
+                write_json_value_indented(json_string, member, indent + 1);
+                if i + 1 < members.len() {
+                    json_string.push(',');
+                }
+                json_string.push('\n');
+            }
+            push_indent(json_string, indent);
+            json_string.push('}');
+        }
+    }
+}
+
+// Helper function to add indentation
+fn push_indent(json_string: &mut String, indent: usize) {
+    for _ in 0..indent {
+        json_string.push_str("    ");
+    }
+}
+
+// Helper function to write a quoted and escaped JSON string
+fn write_json_string(json_string: &mut String, s: &str) {
+    json_string.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json_string.push_str("\\\""),
+            '\\' => json_string.push_str("\\\\"),
+            '\n' => json_string.push_str("\\n"),
+            '\r' => json_string.push_str("\\r"),
+            '\t' => json_string.push_str("\\t"),
+            '\u{08}' => json_string.push_str("\\b"),
+            '\u{0C}' => json_string.push_str("\\f"),
+            c if (c as u32) < 0x20 => json_string.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json_string.push(c),
+        }
+    }
+    json_string.push('"');
+}
+
+/// Parses a JSON document into a `JsonValue`.
+///
+/// This is a small recursive-descent parser following RFC 8259:
+///
+/// - The whole input must be exactly one value (surrounding whitespace allowed).
+/// - Strings support all escapes, including `\uXXXX` surrogate pairs.
+/// - Numbers are checked against the JSON number grammar and kept as text.
+/// - Nesting is limited to `MAX_JSON_DEPTH` levels so that hostile input
+///   cannot overflow the stack.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::JsonVanillaDeserialStrError` with the byte
+/// offset of the problem, e.g. `"Expected ':' at byte 17"`.
+fn parse_json(json_string: &str) -> Result<JsonValue, ThisProjectError> {
+    let mut parser = JsonParser { bytes: json_string.as_bytes(), pos: 0 };
+    parser.skip_whitespace();
+    let value = parser.parse_value(0)?;
+    parser.skip_whitespace();
+    if parser.pos != parser.bytes.len() {
+        return Err(parser.error("Unexpected trailing characters"));
+    }
+    Ok(value)
+}
+
+const MAX_JSON_DEPTH: usize = 128;
+
+struct JsonParser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn error(&self, message: &str) -> ThisProjectError {
+        ThisProjectError::JsonVanillaDeserialStrError(format!("{} at byte {}", message, self.pos))
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), ThisProjectError> {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("Expected '{}'", byte as char)))
+        }
+    }
+
+    fn expect_literal(&mut self, literal: &str) -> Result<(), ThisProjectError> {
+        if self.bytes[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("Expected '{}'", literal)))
+        }
+    }
+
+    fn parse_value(&mut self, depth: usize) -> Result<JsonValue, ThisProjectError> {
+        if depth > MAX_JSON_DEPTH {
+            return Err(self.error("Nesting too deep"));
+        }
+        match self.peek() {
+            Some(b'{') => self.parse_object(depth),
+            Some(b'[') => self.parse_array(depth),
+            Some(b'"') => Ok(JsonValue::String(self.parse_string()?)),
+            Some(b't') => {
+                self.expect_literal("true")?;
+                Ok(JsonValue::Bool(true))
+            }
+            Some(b'f') => {
+                self.expect_literal("false")?;
+                Ok(JsonValue::Bool(false))
+            }
+            Some(b'n') => {
+                self.expect_literal("null")?;
+                Ok(JsonValue::Null)
+            }
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            Some(_) => Err(self.error("Unexpected character")),
+            None => Err(self.error("Unexpected end of input")),
+        }
+    }
+
+    fn parse_object(&mut self, depth: usize) -> Result<JsonValue, ThisProjectError> {
+        self.expect(b'{')?;
+        let mut members: Vec<(String, JsonValue)> = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(JsonValue::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(b'"') {
+                return Err(self.error("Expected object key string"));
+            }
+            let key_pos = self.pos;
+            let key = self.parse_string()?;
+            if members.iter().any(|(k, _)| *k == key) {
+                self.pos = key_pos;
+                return Err(self.error(&format!("Duplicate key \"{}\"", key)));
+            }
+            self.skip_whitespace();
+            self.expect(b':')?;
+            self.skip_whitespace();
+            let value = self.parse_value(depth + 1)?;
+            members.push((key, value));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Object(members));
+                }
+                _ => return Err(self.error("Expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn parse_array(&mut self, depth: usize) -> Result<JsonValue, ThisProjectError> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(JsonValue::Array(items));
+        }
+        loop {
+            self.skip_whitespace();
+            items.push(self.parse_value(depth + 1)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Array(items));
+                }
+                _ => return Err(self.error("Expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, ThisProjectError> {
+        self.expect(b'"')?;
+        let mut bytes = Vec::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("Unterminated string")),
+                Some(b'"') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let escape = self.peek();
+                    self.pos += 1;
+                    let escaped = match escape {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{08}',
+                        Some(b'f') => '\u{0C}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => self.parse_unicode_escape()?,
+                        _ => {
+                            self.pos -= 1;
+                            return Err(self.error("Invalid escape sequence"));
+                        }
+                    };
+                    let mut buf = [0u8; 4];
+                    bytes.extend_from_slice(escaped.encode_utf8(&mut buf).as_bytes());
+                }
+                Some(b) if b < 0x20 => return Err(self.error("Control character in string")),
+                Some(b) => {
+                    bytes.push(b);
+                    self.pos += 1;
+                }
+            }
+        }
+        // Input is a &str, and escapes are pushed as UTF-8, so this cannot fail
+        String::from_utf8(bytes).map_err(|_| self.error("Invalid UTF-8 in string"))
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, ThisProjectError> {
+        let digits = self.bytes.get(self.pos..self.pos + 4)
+            .ok_or_else(|| self.error("Truncated \\u escape"))?;
+        let text = std::str::from_utf8(digits).map_err(|_| self.error("Invalid \\u escape"))?;
+        let code = u32::from_str_radix(text, 16).map_err(|_| self.error("Invalid \\u escape"))?;
+        self.pos += 4;
+        Ok(code)
+    }
+
+    fn parse_unicode_escape(&mut self) -> Result<char, ThisProjectError> {
+        let first = self.parse_hex4()?;
+        let code = if (0xD800..0xDC00).contains(&first) {
+            // High surrogate: must be followed by \uDC00..\uDFFF
+            self.expect_literal("\\u")?;
+            let second = self.parse_hex4()?;
+            if !(0xDC00..0xE000).contains(&second) {
+                return Err(self.error("Invalid low surrogate in \\u escape"));
+            }
+            0x10000 + ((first - 0xD800) << 10) + (second - 0xDC00)
+        } else {
+            first
+        };
+        char::from_u32(code).ok_or_else(|| self.error("Invalid code point in \\u escape"))
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, ThisProjectError> {
+        let start = self.pos;
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        // Integer part: "0" or [1-9][0-9]*
+        match self.peek() {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => self.skip_digits(),
+            _ => return Err(self.error("Invalid number")),
+        }
+        // Fraction part
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.error("Invalid number: expected digit after '.'"));
+            }
+            self.skip_digits();
+        }
+        // Exponent part
+        if let Some(b'e' | b'E') = self.peek() {
+            self.pos += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.pos += 1;
+            }
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.error("Invalid number: expected digit in exponent"));
+            }
+            self.skip_digits();
+        }
+        // Only ASCII was consumed, so this slice is valid UTF-8
+        let text = String::from_utf8_lossy(&self.bytes[start..self.pos]).into_owned();
+        Ok(JsonValue::Number(text))
+    }
+
+    fn skip_digits(&mut self) {
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+    }
+}
+
+/// Serializes a `CollaboratorTomlData` struct into a JSON string.
+///
+/// The JSON object uses the same keys, in the same order, as
+/// `serialize_collaborator_to_toml`, so a collaborator can be converted
+/// TOML -> JSON -> TOML without losing anything.
+///
+/// # `u128` Salts
+///
+/// JSON numbers are read as 64-bit floats by most consumers (including
+/// JavaScript), which cannot hold a `u128`. Salts are therefore written as
+/// hexadecimal strings, exactly as in the TOML files: `"0x123456789abcdef0"`.
+///
+/// # JSON Format
+///
+/// ```json
+/// {
+///     "user_name": "Bob",
+///     "user_salt_list": [
+///         "0x123456789abcdef0",
+///         "0xabcdef0123456789"
+///     ],
+///     "ipv4_addresses": [
+///         "192.168.1.1",
+///         "10.0.0.1"
+///     ],
+///     "ipv6_addresses": [
+///         "fe80::1",
+///         "::1"
+///     ],
+///     "gpg_key_public": "-----BEGIN PGP PUBLIC KEY BLOCK----- ...",
+///     "sync_interval": 300,
+///     "updated_at_timestamp": 1728308000
+/// }
+/// ```
+///
+/// As in the TOML output, `ipv4_addresses` and `ipv6_addresses` are left out
+/// when they are `None`.
+fn serialize_collaborator_to_json(collaborator: &CollaboratorTomlData) -> Result<String, ThisProjectError> {
+    let mut members = Vec::new();
+
+    // Add user_name
+    members.push(("user_name".to_string(), JsonValue::String(collaborator.user_name.clone())));
+
+    // Add user_salt_list
+    let salts = collaborator.user_salt_list.iter()
+        .map(|salt| JsonValue::String(format!("0x{:x}", salt)))
+        .collect();
+    members.push(("user_salt_list".to_string(), JsonValue::Array(salts)));
+
+    // Add ipv4_addresses
+    if let Some(addresses) = &collaborator.ipv4_addresses {
+        let addresses = addresses.iter().map(|ip| JsonValue::String(ip.to_string())).collect();
+        members.push(("ipv4_addresses".to_string(), JsonValue::Array(addresses)));
+    }
+
+    // Add ipv6_addresses
+    if let Some(addresses) = &collaborator.ipv6_addresses {
+        let addresses = addresses.iter().map(|ip| JsonValue::String(ip.to_string())).collect();
+        members.push(("ipv6_addresses".to_string(), JsonValue::Array(addresses)));
+    }
+
+    // Add gpg_key_public
+    members.push(("gpg_key_public".to_string(), JsonValue::String(collaborator.gpg_key_public.clone())));
+
+    // Add sync_interval
+    members.push(("sync_interval".to_string(), JsonValue::Number(collaborator.sync_interval.to_string())));
+
+    // Add updated_at_timestamp
+    members.push(("updated_at_timestamp".to_string(), JsonValue::Number(collaborator.updated_at_timestamp.to_string())));
+
+    let mut json_string = write_json_value(&JsonValue::Object(members));
+    json_string.push('\n');
+    Ok(json_string)
+}
+
+/// Deserializes a `CollaboratorTomlData` struct from a JSON string.
+///
+/// This is the JSON counterpart of `read_one_collaborator_setup_toml`: the
+/// same fields are required, salts are read from hexadecimal strings, and
+/// the first problem found is returned as an error.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::JsonVanillaDeserialStrError` for invalid JSON,
+/// missing fields and wrong value types, and `ThisProjectError::ParseIntError`
+/// for salts that are not valid hexadecimal.
+fn deserialize_collaborator_from_json(json_string: &str) -> Result<CollaboratorTomlData, ThisProjectError> {
+    let json_value = parse_json(json_string)?;
+
+    if !matches!(json_value, JsonValue::Object(_)) {
+        return Err(ThisProjectError::JsonVanillaDeserialStrError("Invalid JSON structure: Expected an object".into()));
+    }
+
+    // Extract user_name
+    let user_name = if let Some(JsonValue::String(s)) = json_value.get("user_name") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::JsonVanillaDeserialStrError("Missing user_name".into()));
+    };
+
+    // Extract user_salt_list
+    let user_salt_list = if let Some(JsonValue::Array(arr)) = json_value.get("user_salt_list") {
+        arr.iter()
+            .map(|val| {
+                if let JsonValue::String(s) = val {
+                    u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                        .map_err(ThisProjectError::ParseIntError)
+                } else {
+                    Err(ThisProjectError::JsonVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                }
+            })
+            .collect::<Result<Vec<u128>, ThisProjectError>>()?
+    } else {
+        return Err(ThisProjectError::JsonVanillaDeserialStrError("Missing user_salt_list".into()));
+    };
+
+    // Extract ipv4_addresses
+    let ipv4_addresses = extract_json_ip_addresses::<Ipv4Addr>(&json_value, "ipv4_addresses")?;
+
+    // Extract ipv6_addresses
+    let ipv6_addresses = extract_json_ip_addresses::<Ipv6Addr>(&json_value, "ipv6_addresses")?;
+
+    // Extract gpg_key_public
+    let gpg_key_public = if let Some(JsonValue::String(s)) = json_value.get("gpg_key_public") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::JsonVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+    };
+
+    // Extract sync_interval
+    let sync_interval = extract_json_u64(&json_value, "sync_interval")?;
+
+    // Extract updated_at_timestamp
+    let updated_at_timestamp = extract_json_u64(&json_value, "updated_at_timestamp")?;
+
+    Ok(CollaboratorTomlData {
+        user_name,
+        user_salt_list,
+        ipv4_addresses,
+        ipv6_addresses,
+        gpg_key_public,
+        sync_interval,
+        updated_at_timestamp,
+    })
+}
+
+// Helper function to extract and parse IP addresses from a JSON object
+// (same rules as extract_ipv4_addresses / extract_ipv6_addresses: an absent
+// key or an empty list gives None)
+fn extract_json_ip_addresses<T>(json_value: &JsonValue, key: &str) -> Result<Option<Vec<T>>, ThisProjectError>
+where
+    T: std::str::FromStr,
+    T::Err: fmt::Display,
+{
+    match json_value.get(key) {
+        Some(JsonValue::Array(arr)) => {
+            let mut addresses = Vec::new();
+            for val in arr {
+                if let JsonValue::String(s) = val {
+                    match s.parse::<T>() {
+                        Ok(ip) => addresses.push(ip),
+                        Err(e) => return Err(ThisProjectError::JsonVanillaDeserialStrError(format!("Invalid {} format: {}", key, e))),
+                    }
+                } else {
+                    return Err(ThisProjectError::JsonVanillaDeserialStrError(format!("Invalid {} format: Expected string", key)));
+                }
+            }
+
+            if addresses.is_empty() {
+                Ok(None)
+            } else {
+                Ok(Some(addresses))
+            }
+        }
+        Some(JsonValue::Null) | None => Ok(None),
+        Some(_) => Err(ThisProjectError::JsonVanillaDeserialStrError(format!("Invalid {} format: Expected array", key))),
+    }
+}
+
+// Helper function to extract a u64 from a JSON object.
+// Values are limited to i64::MAX like extract_u64, so that anything read
+// from JSON can also be written to (and read back from) TOML.
+fn extract_json_u64(json_value: &JsonValue, key: &str) -> Result<u64, ThisProjectError> {
+    if let Some(JsonValue::Number(n)) = json_value.get(key) {
+        match n.parse::<u64>() {
+            Ok(i) if i <= i64::MAX as u64 => Ok(i),
+            Ok(_) => Err(ThisProjectError::JsonVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key))),
+            Err(_) => Err(ThisProjectError::JsonVanillaDeserialStrError(format!("Invalid {}: Expected a non-negative integer", key))),
+        }
+    } else {
+        Err(ThisProjectError::JsonVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+/// Parses one collaborator from a TOML string.
+///
+/// Same extraction rules as `read_one_collaborator_setup_toml` in
+/// `deserialize_one_file_main.rs`, but working on a string instead of a file,
+/// so that it can be used for TOML <-> JSON conversion.
+fn deserialize_collaborator_from_toml(toml_string: &str) -> Result<CollaboratorTomlData, ThisProjectError> {
+    let toml_value = match toml::from_str::<Value>(toml_string) {
+        Ok(value) => value,
+        Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(e.to_string())),
+    };
+
+    if let Value::Table(table) = toml_value {
+
+        // Extract user_name
+        let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+            s.clone()
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+        };
+
+        // Extract user_salt_list
+        let user_salt_list = if let Some(Value::Array(arr)) = table.get("user_salt_list") {
+            arr.iter()
+                .map(|val| {
+                    if let Value::String(s) = val {
+                        u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                            .map_err(ThisProjectError::ParseIntError)
+                    } else {
+                        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                    }
+                })
+                .collect::<Result<Vec<u128>, ThisProjectError>>()?
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_salt_list".into()));
+        };
+
+        // Extract ipv4_addresses
+        let ipv4_addresses = extract_ipv4_addresses(&table, "ipv4_addresses")?;
+
+        // Extract ipv6_addresses
+        let ipv6_addresses = extract_ipv6_addresses(&table, "ipv6_addresses")?;
+
+        // Extract gpg_key_public
+        let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+            s.clone()
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+        };
+
+        // Extract sync_interval
+        let sync_interval = extract_u64(&table, "sync_interval")?;
+
+        // Extract updated_at_timestamp
+        let updated_at_timestamp = extract_u64(&table, "updated_at_timestamp")?;
+
+        Ok(CollaboratorTomlData {
+            user_name,
+            user_salt_list,
+            ipv4_addresses,
+            ipv6_addresses,
+            gpg_key_public,
+            sync_interval,
+            updated_at_timestamp,
+        })
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure: Expected a table".into()))
+    }
+}
+
+fn extract_ipv4_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv4Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv4Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+fn extract_ipv6_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv6Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv6Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+/// Serializes a `CollaboratorTomlData` struct into a TOML-formatted string.
+///
+/// Strings are escaped for TOML basic strings, so a `user_name` or a
+/// multi-line `gpg_key_public` read from JSON is written back unchanged.
+fn serialize_collaborator_to_toml(collaborator: &CollaboratorTomlData) -> Result<String, ThisProjectError> {
+    let mut toml_string = String::new();
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", escape_toml_basic_string(&collaborator.user_name)));
+
+    // Add user_salt_list
+    toml_string.push_str("user_salt_list = [\n");
+    for salt in &collaborator.user_salt_list {
+        toml_string.push_str(&format!("    \"0x{:x}\",\n", salt));
+    }
+    toml_string.push_str("]\n");
+
+    // Add ipv4_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv4_addresses", &collaborator.ipv4_addresses)?;
+
+    // Add ipv6_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv6_addresses", &collaborator.ipv6_addresses)?;
+
+    // Add gpg_key_public
+    toml_string.push_str(&format!("gpg_key_public = \"{}\"\n", escape_toml_basic_string(&collaborator.gpg_key_public)));
+
+    // Add sync_interval
+    toml_string.push_str(&format!("sync_interval = {}\n", collaborator.sync_interval));
+
+    // Add updated_at_timestamp
+    toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp));
+
+    Ok(toml_string)
+}
+
+// Helper function to serialize IP addresses to TOML array format
+fn serialize_ip_addresses<T: std::fmt::Display>(
+    toml_string: &mut String,
+    key: &str,
+    addresses: &Option<Vec<T>>
+) -> Result<(), ThisProjectError> {
+    if let Some(addr_vec) = addresses {
+        toml_string.push_str(&format!("{} = [\n", key));
+        for addr in addr_vec {
+            toml_string.push_str(&format!("    \"{}\",\n", addr));
+        }
+        toml_string.push_str("]\n");
+    }
+    Ok(()) // Return Ok(()) if the addresses field is None
+}
+
+// Helper function to escape a value for a TOML basic string ("...")
+fn escape_toml_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Converts a collaborator TOML document into the equivalent JSON document.
+///
+/// # use with
+/// let toml_string = fs::read_to_string("project_graph_data/collaborator_files_address_book/alice__collaborator.toml")?;
+/// let json_string = convert_collaborator_toml_to_json(&toml_string)?;
+fn convert_collaborator_toml_to_json(toml_string: &str) -> Result<String, ThisProjectError> {
+    let collaborator = deserialize_collaborator_from_toml(toml_string)?;
+    serialize_collaborator_to_json(&collaborator)
+}
+
+/// Converts a collaborator JSON document into the equivalent TOML document.
+///
+/// # use with
+/// let toml_string = convert_collaborator_json_to_toml(&json_string)?;
+/// write_toml_to_file("alice__collaborator.toml", &toml_string)?;
+fn convert_collaborator_json_to_toml(json_string: &str) -> Result<String, ThisProjectError> {
+    let collaborator = deserialize_collaborator_from_json(json_string)?;
+    serialize_collaborator_to_toml(&collaborator)
+}
+
+fn main() {
+    // Example CollaboratorTomlData instance
+    let collaborator = CollaboratorTomlData {
+        user_name: "Bob".to_string(),
+        user_salt_list: vec![0x123456789abcdef0, 0xabcdef0123456789, u128::MAX],
+        ipv4_addresses: Some(vec![Ipv4Addr::new(192, 168, 1, 1), Ipv4Addr::new(10, 0, 0, 1)]),
+        ipv6_addresses: Some(vec![Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)]),
+        gpg_key_public: "-----BEGIN PGP PUBLIC KEY BLOCK-----\n\nmDMEZ...\n-----END PGP PUBLIC KEY BLOCK-----".to_string(),
+        sync_interval: 300,
+        updated_at_timestamp: 1728308000,
+    };
+
+    // Serialize the collaborator data to a JSON string
+    let json_string = match serialize_collaborator_to_json(&collaborator) {
+        Ok(json_string) => json_string,
+        Err(e) => {
+            println!("Error serializing to JSON: {}", e);
+            return;
+        }
+    };
+    println!("Serialized JSON:\n{}", json_string);
+
+    // JSON -> TOML
+    let toml_string = match convert_collaborator_json_to_toml(&json_string) {
+        Ok(toml_string) => toml_string,
+        Err(e) => {
+            println!("Error converting JSON to TOML: {}", e);
+            return;
+        }
+    };
+    println!("Converted TOML:\n{}", toml_string);
+
+    // TOML -> JSON, which should give back the same JSON
+    match convert_collaborator_toml_to_json(&toml_string) {
+        Ok(json_again) => {
+            println!("Round trip lossless: {}", json_again == json_string);
+        }
+        Err(e) => println!("Error converting TOML to JSON: {}", e),
+    }
+
+    // JSON -> struct, which should give back the same struct
+    match deserialize_collaborator_from_json(&json_string) {
+        Ok(parsed) => println!("Struct round trip lossless: {}", parsed == collaborator),
+        Err(e) => println!("Error deserializing JSON: {}", e),
+    }
+}
@@ -0,0 +1,630 @@
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::ffi::OsStr;
+use toml::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+    network: Option<NetworkSettings>,
+    sync: SyncSettings,
+    devices: Vec<DeviceEntry>,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    NestedTableError(String),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::NestedTableError(err) => write!(f, "Nested Table Error: {}", err),
+        }
+    }
+}
+
+/*
+Nested sections of a collaborator file
+
+The seven top-level keys are unchanged. After them, a file may have:
+
+    [network]
+    listen_port = 40000          # optional, 1 to 65535
+    prefer_ipv6 = true           # optional, default false
+
+    [sync]
+    enabled = true               # optional, default true
+    max_retries = 3              # optional, default 3
+
+    [[devices]]                  # zero or more
+    name = "laptop"              # required
+    ipv4_addresses = ["192.168.1.20"]
+    ipv6_addresses = ["fe80::20"]
+    ports = [40000, 40001]       # optional, 1 to 65535 each
+
+Errors name the failing value by its path from the top of the file,
+e.g. `devices[2].ipv6_addresses[0]` or `network.listen_port`.
+*/
+
+/// The `[network]` section.
+#[derive(Debug, Clone, PartialEq)]
+struct NetworkSettings {
+    listen_port: Option<u16>,
+    prefer_ipv6: bool,
+}
+
+/// The `[sync]` section. Absent sections and keys take these defaults.
+#[derive(Debug, Clone, PartialEq)]
+struct SyncSettings {
+    enabled: bool,
+    max_retries: u32,
+}
+
+impl Default for SyncSettings {
+    fn default() -> Self {
+        SyncSettings {
+            enabled: true,
+            max_retries: 3,
+        }
+    }
+}
+
+/// One `[[devices]]` entry.
+#[derive(Debug, Clone, PartialEq)]
+struct DeviceEntry {
+    name: String,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    ports: Vec<u16>,
+}
+
+// Helper function: path of `key` inside the table at `parent_path` ("" is the top level)
+fn child_path(parent_path: &str, key: &str) -> String {
+    if parent_path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent_path, key)
+    }
+}
+
+// Helper function: path of element `index` of the array at `path`
+fn index_path(path: &str, index: usize) -> String {
+    format!("{}[{}]", path, index)
+}
+
+// Helper function: an error for the value at `path`
+fn path_error(path: &str, reason: &str) -> ThisProjectError {
+    ThisProjectError::NestedTableError(format!("{}: {}", path, reason))
+}
+
+// Helper function to extract an optional sub-table such as `[network]`
+fn extract_optional_table<'a>(
+    table: &'a toml::map::Map<String, Value>,
+    parent_path: &str,
+    key: &str,
+) -> Result<Option<&'a toml::map::Map<String, Value>>, ThisProjectError> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::Table(sub_table)) => Ok(Some(sub_table)),
+        Some(_) => Err(path_error(&child_path(parent_path, key), "expected a table")),
+    }
+}
+
+// Helper function to extract an optional boolean with a default
+fn extract_bool_or(
+    table: &toml::map::Map<String, Value>,
+    parent_path: &str,
+    key: &str,
+    default: bool,
+) -> Result<bool, ThisProjectError> {
+    match table.get(key) {
+        None => Ok(default),
+        Some(Value::Boolean(b)) => Ok(*b),
+        Some(_) => Err(path_error(&child_path(parent_path, key), "expected true or false")),
+    }
+}
+
+// Helper function: a TOML integer as a port number (1 to 65535)
+fn value_to_port(value: &Value, path: &str) -> Result<u16, ThisProjectError> {
+    match value {
+        Value::Integer(i) if (1..=65535).contains(i) => Ok(*i as u16),
+        Value::Integer(i) => Err(path_error(path, &format!("{} is not a port number (1 to 65535)", i))),
+        _ => Err(path_error(path, "expected an integer port number")),
+    }
+}
+
+// Helper function to extract the `[network]` section
+fn extract_network_settings(
+    table: &toml::map::Map<String, Value>,
+    key: &str,
+) -> Result<Option<NetworkSettings>, ThisProjectError> {
+    let network_table = match extract_optional_table(table, "", key)? {
+        Some(network_table) => network_table,
+        None => return Ok(None),
+    };
+    let path = child_path("", key);
+
+    let listen_port = match network_table.get("listen_port") {
+        Some(value) => Some(value_to_port(value, &child_path(&path, "listen_port"))?),
+        None => None,
+    };
+    let prefer_ipv6 = extract_bool_or(network_table, &path, "prefer_ipv6", false)?;
+
+    Ok(Some(NetworkSettings { listen_port, prefer_ipv6 }))
+}
+
+// Helper function to extract the `[sync]` section, with defaults when absent
+fn extract_sync_settings(table: &toml::map::Map<String, Value>, key: &str) -> Result<SyncSettings, ThisProjectError> {
+    let defaults = SyncSettings::default();
+    let sync_table = match extract_optional_table(table, "", key)? {
+        Some(sync_table) => sync_table,
+        None => return Ok(defaults),
+    };
+    let path = child_path("", key);
+
+    let enabled = extract_bool_or(sync_table, &path, "enabled", defaults.enabled)?;
+    let max_retries = match sync_table.get("max_retries") {
+        None => defaults.max_retries,
+        Some(Value::Integer(i)) if *i >= 0 && *i <= u32::MAX as i64 => *i as u32,
+        Some(_) => return Err(path_error(&child_path(&path, "max_retries"), "expected an integer from 0 to 4294967295")),
+    };
+
+    Ok(SyncSettings { enabled, max_retries })
+}
+
+// Helper function to extract the `[[devices]]` array of tables
+fn extract_devices(table: &toml::map::Map<String, Value>, key: &str) -> Result<Vec<DeviceEntry>, ThisProjectError> {
+    let path = child_path("", key);
+    let entries = match table.get(key) {
+        None => return Ok(Vec::new()),
+        Some(Value::Array(entries)) => entries,
+        Some(_) => return Err(path_error(&path, "expected an array of tables ([[devices]])")),
+    };
+
+    let mut devices = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let device_path = index_path(&path, index);
+        let device_table = match entry {
+            Value::Table(device_table) => device_table,
+            _ => return Err(path_error(&device_path, "expected a table")),
+        };
+
+        let name = match device_table.get("name") {
+            Some(Value::String(s)) => s.clone(),
+            Some(_) => return Err(path_error(&child_path(&device_path, "name"), "expected a string")),
+            None => return Err(path_error(&child_path(&device_path, "name"), "missing")),
+        };
+        let ipv4_addresses = extract_ipv4_addresses(device_table, &device_path, "ipv4_addresses")?;
+        let ipv6_addresses = extract_ipv6_addresses(device_table, &device_path, "ipv6_addresses")?;
+
+        let ports_path = child_path(&device_path, "ports");
+        let ports = match device_table.get("ports") {
+            None => Vec::new(),
+            Some(Value::Array(arr)) => arr
+                .iter()
+                .enumerate()
+                .map(|(port_index, value)| value_to_port(value, &index_path(&ports_path, port_index)))
+                .collect::<Result<Vec<u16>, ThisProjectError>>()?,
+            Some(_) => return Err(path_error(&ports_path, "expected an array")),
+        };
+
+        devices.push(DeviceEntry { name, ipv4_addresses, ipv6_addresses, ports });
+    }
+    Ok(devices)
+}
+
+// Helper function: TOML table -> CollaboratorTomlData, failing on the first bad field
+fn collaborator_from_toml_table(table: &toml::map::Map<String, Value>) -> Result<CollaboratorTomlData, ThisProjectError> {
+
+    // Extract user_name
+    let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+    };
+
+    // Extract user_salt_list
+    let user_salt_list = if let Some(Value::Array(arr)) = table.get("user_salt_list") {
+        arr.iter()
+            .map(|val| {
+                if let Value::String(s) = val {
+                    u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                        .map_err(ThisProjectError::ParseIntError)
+                } else {
+                    Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                }
+            })
+            .collect::<Result<Vec<u128>, ThisProjectError>>()?
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_salt_list".into()));
+    };
+
+    // Extract ipv4_addresses
+    let ipv4_addresses = extract_ipv4_addresses(table, "", "ipv4_addresses")?;
+
+    // Extract ipv6_addresses
+    let ipv6_addresses = extract_ipv6_addresses(table, "", "ipv6_addresses")?;
+
+    // Extract gpg_key_public
+    let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+    };
+
+    // Extract sync_interval
+    let sync_interval = extract_u64(table, "sync_interval")?;
+
+    // Extract updated_at_timestamp
+    let updated_at_timestamp = extract_u64(table, "updated_at_timestamp")?;
+
+    // Extract [network], [sync] and [[devices]]
+    let network = extract_network_settings(table, "network")?;
+    let sync = extract_sync_settings(table, "sync")?;
+    let devices = extract_devices(table, "devices")?;
+
+    Ok(CollaboratorTomlData {
+        user_name,
+        user_salt_list,
+        ipv4_addresses,
+        ipv6_addresses,
+        gpg_key_public,
+        sync_interval,
+        updated_at_timestamp,
+        network,
+        sync,
+        devices,
+    })
+}
+
+/// Reads the optional IPv4 address list `key` of a table found at
+/// `parent_path` (`""` for the top level). An absent or empty list is
+/// `None`; the first bad element is the error, named by its path, e.g.
+/// `devices[2].ipv4_addresses[0]`.
+fn extract_ipv4_addresses(
+    table: &toml::map::Map<String, Value>,
+    parent_path: &str,
+    key: &str,
+) -> Result<Option<Vec<Ipv4Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let path = child_path(parent_path, key);
+        let mut addresses = Vec::new();
+        for (index, val) in arr.iter().enumerate() {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv4Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(path_error(&index_path(&path, index), &format!("\"{}\": {}", s, e))),
+                }
+            } else {
+                return Err(path_error(&index_path(&path, index), "expected a string"));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+/// Same as `extract_ipv4_addresses` above, for IPv6 addresses.
+fn extract_ipv6_addresses(
+    table: &toml::map::Map<String, Value>,
+    parent_path: &str,
+    key: &str,
+) -> Result<Option<Vec<Ipv6Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let path = child_path(parent_path, key);
+        let mut addresses = Vec::new();
+        for (index, val) in arr.iter().enumerate() {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv6Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(path_error(&index_path(&path, index), &format!("\"{}\": {}", s, e))),
+                }
+            } else {
+                return Err(path_error(&index_path(&path, index), "expected a string"));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+// Helper function: TOML text -> CollaboratorTomlData
+fn deserialize_collaborator_from_toml(toml_string: &str) -> Result<CollaboratorTomlData, ThisProjectError> {
+    match toml::from_str::<Value>(toml_string) {
+        Ok(Value::Table(table)) => collaborator_from_toml_table(&table),
+        Ok(_) => Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure: Expected a table".into())),
+        Err(e) => Err(ThisProjectError::TomlVanillaDeserialStrError(e.to_string())),
+    }
+}
+
+/// Toml Deserialization: Reads collaborator setup data from TOML files in a
+/// specified directory.
+///
+/// Every `*.toml` file in `project_graph_data/collaborator_files_address_book`
+/// is parsed with `deserialize_collaborator_from_toml`; a file that does not
+/// parse is skipped and its error is added to the error vector.
+///
+/// The optional `[network]`, `[sync]` and `[[devices]]` sections are read
+/// into `CollaboratorTomlData` as well, and errors inside them carry the
+/// path of the failing value.
+///
+/// # Returns
+///
+/// Returns a `Result` containing:
+/// - `Ok`: A tuple with:
+///     - A vector of successfully parsed `CollaboratorTomlData` instances.
+///     - A vector of any `ThisProjectError` encountered during parsing.
+/// - `Err`: A `ThisProjectError` if there was an error reading the directory or any file.
+fn read_a_collaborator_setup_toml() -> Result<(Vec<CollaboratorTomlData>, Vec<ThisProjectError>), ThisProjectError> {
+    let mut collaborators = Vec::new();
+    let mut errors = Vec::new();
+    let dir_path = Path::new("project_graph_data/collaborator_files_address_book");
+
+    for entry in fs::read_dir(dir_path)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
+            let toml_string = fs::read_to_string(&path)?;
+
+            match deserialize_collaborator_from_toml(&toml_string) {
+                Ok(collaborator) => collaborators.push(collaborator),
+                Err(e) => errors.push(e),
+            }
+        }
+    }
+
+    Ok((collaborators, errors))
+}
+
+/// Serializes a `CollaboratorTomlData` struct into a TOML-formatted string.
+///
+/// The top-level fields first, one `key = value` line each in struct order
+/// (escaped strings, `0x` hex salts, `None` IP lists left out), then the
+/// nested sections: `[network]` if
+/// present, `[sync]` if it differs from `SyncSettings::default()`, and one
+/// `[[devices]]` header per device. The sections come after all top-level
+/// keys, since in TOML every key after a header belongs to that header.
+fn serialize_collaborator_to_toml(collaborator: &CollaboratorTomlData) -> Result<String, ThisProjectError> {
+    let mut toml_string = String::new();
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", escape_toml_basic_string(&collaborator.user_name)));
+
+    // Add user_salt_list
+    toml_string.push_str("user_salt_list = [\n");
+    for salt in &collaborator.user_salt_list {
+        toml_string.push_str(&format!("    \"0x{:x}\",\n", salt));
+    }
+    toml_string.push_str("]\n");
+
+    // Add ipv4_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv4_addresses", &collaborator.ipv4_addresses)?;
+
+    // Add ipv6_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv6_addresses", &collaborator.ipv6_addresses)?;
+
+    // Add gpg_key_public
+    toml_string.push_str(&format!("gpg_key_public = \"{}\"\n", escape_toml_basic_string(&collaborator.gpg_key_public)));
+
+    // Add sync_interval
+    toml_string.push_str(&format!("sync_interval = {}\n", collaborator.sync_interval));
+
+    // Add updated_at_timestamp
+    toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp));
+
+    // Add [network]
+    if let Some(network) = &collaborator.network {
+        toml_string.push_str("\n[network]\n");
+        if let Some(listen_port) = network.listen_port {
+            toml_string.push_str(&format!("listen_port = {}\n", listen_port));
+        }
+        toml_string.push_str(&format!("prefer_ipv6 = {}\n", network.prefer_ipv6));
+    }
+
+    // Add [sync]
+    if collaborator.sync != SyncSettings::default() {
+        toml_string.push_str("\n[sync]\n");
+        toml_string.push_str(&format!("enabled = {}\n", collaborator.sync.enabled));
+        toml_string.push_str(&format!("max_retries = {}\n", collaborator.sync.max_retries));
+    }
+
+    // Add [[devices]]
+    for device in &collaborator.devices {
+        toml_string.push_str("\n[[devices]]\n");
+        toml_string.push_str(&format!("name = \"{}\"\n", escape_toml_basic_string(&device.name)));
+        serialize_ip_addresses(&mut toml_string, "ipv4_addresses", &device.ipv4_addresses)?;
+        serialize_ip_addresses(&mut toml_string, "ipv6_addresses", &device.ipv6_addresses)?;
+        if !device.ports.is_empty() {
+            let ports: Vec<String> = device.ports.iter().map(|port| port.to_string()).collect();
+            toml_string.push_str(&format!("ports = [{}]\n", ports.join(", ")));
+        }
+    }
+
+    Ok(toml_string)
+}
+
+// Helper function to escape a value for a TOML basic string ("...")
+fn escape_toml_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Helper function to serialize IP addresses to TOML array format
+fn serialize_ip_addresses<T: std::fmt::Display>(
+    toml_string: &mut String,
+    key: &str,
+    addresses: &Option<Vec<T>>
+) -> Result<(), ThisProjectError> {
+    if let Some(addr_vec) = addresses {
+        toml_string.push_str(&format!("{} = [\n", key));
+        for addr in addr_vec {
+            toml_string.push_str(&format!("    \"{}\",\n", addr));
+        }
+        toml_string.push_str("]\n");
+    }
+    Ok(()) // Return Ok(()) if the addresses field is None
+}
+
+// Function to write a TOML string to a file
+fn write_toml_to_file(file_path: &str, toml_string: &str) -> Result<(), ThisProjectError> {
+    // Attempt to create the file.
+    let mut file = match File::create(file_path) {
+        Ok(file) => file,
+        Err(e) => return Err(ThisProjectError::IoError(e)),
+    };
+
+    // Attempt to write to the file.
+    if let Err(e) = file.write_all(toml_string.as_bytes()) {
+        return Err(ThisProjectError::IoError(e));
+    }
+
+    // Everything successful!
+    Ok(())
+}
+
+fn main() {
+    // Example CollaboratorTomlData instance with nested sections
+    let collaborator = CollaboratorTomlData {
+        user_name: "Bob".to_string(),
+        user_salt_list: vec![0x123456789abcdef0, 0xabcdef0123456789],
+        ipv4_addresses: Some(vec![Ipv4Addr::new(192, 168, 1, 1), Ipv4Addr::new(10, 0, 0, 1)]),
+        ipv6_addresses: Some(vec![Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)]),
+        gpg_key_public: "-----BEGIN PGP PUBLIC KEY BLOCK----- ...".to_string(),
+        sync_interval: 300,
+        updated_at_timestamp: 1728308000,
+        network: Some(NetworkSettings { listen_port: Some(40000), prefer_ipv6: true }),
+        sync: SyncSettings { enabled: true, max_retries: 5 },
+        devices: vec![
+            DeviceEntry {
+                name: "laptop".to_string(),
+                ipv4_addresses: Some(vec![Ipv4Addr::new(192, 168, 1, 20)]),
+                ipv6_addresses: None,
+                ports: vec![40000, 40001],
+            },
+            DeviceEntry {
+                name: "phone".to_string(),
+                ipv4_addresses: None,
+                ipv6_addresses: Some(vec![Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 0x21)]),
+                ports: Vec::new(),
+            },
+        ],
+    };
+
+    match serialize_collaborator_to_toml(&collaborator) {
+        Ok(toml_string) => {
+            println!("Serialized TOML:\n{}", toml_string);
+
+            match deserialize_collaborator_from_toml(&toml_string) {
+                Ok(read_back) => println!(
+                    "Read back {} device(s), sync {:?}, network {:?}",
+                    read_back.devices.len(), read_back.sync, read_back.network
+                ),
+                Err(e) => println!("Error reading back: {}", e),
+            }
+
+            // A bad address in the third device is reported by its path
+            let bad_toml = format!(
+                "{}\n[[devices]]\nname = \"tablet\"\nipv6_addresses = [\"fe80::zz\"]\n",
+                toml_string
+            );
+            if let Err(e) = deserialize_collaborator_from_toml(&bad_toml) {
+                println!("{}", e);
+            }
+
+            // Write the TOML string to a file (example file path)
+            match write_toml_to_file("collaborator_data.toml", &toml_string) {
+                Ok(_) => println!("TOML data written to file successfully."),
+                Err(e) => println!("Error writing to file: {}", e),
+            }
+        }
+        Err(e) => println!("Error serializing to TOML: {}", e),
+    }
+
+    // Flat files without sections still load
+    match read_a_collaborator_setup_toml() {
+        Ok((collaborators, errors)) => {
+            if !errors.is_empty() {
+                println!("Errors encountered:");
+                for err in errors {
+                    println!("{}", err);
+                }
+            }
+
+            println!("Collaborators:");
+            for collaborator in collaborators {
+                println!("{:#?}", collaborator);
+            }
+        }
+        Err(e) => {
+            println!("Error reading TOML files: {}", e);
+        }
+    }
+}
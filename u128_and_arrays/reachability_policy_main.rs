@@ -0,0 +1,897 @@
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::ffi::OsStr;
+use std::str::FromStr;
+use toml::Value;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ip_addresses: Option<Vec<IpAddr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    ListError(String),
+    AddressPolicyError(String),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::ListError(err) => write!(f, "List Error: {}", err),
+            ThisProjectError::AddressPolicyError(err) => write!(f, "Address Policy Error: {}", err),
+        }
+    }
+}
+
+/// A check on one list element; returns why the element is not allowed.
+type ElementPolicy<T> = fn(&T) -> Result<(), String>;
+
+/// How `extract_parsed_list` reads one list field.
+///
+/// - `required`: a missing key is an error. Otherwise a missing key, or an
+///   empty list, reads as `None`.
+/// - `min_len`, `max_len`: allowed number of entries when the key is present.
+/// - `element_policies`: checked for every parsed element, in order.
+struct ListRules<T: 'static> {
+    required: bool,
+    min_len: usize,
+    max_len: usize,
+    element_policies: &'static [ElementPolicy<T>],
+}
+
+/// `user_salt_list`: at least one salt, no zero salts.
+const SALT_LIST_RULES: ListRules<u128> = ListRules {
+    required: true,
+    min_len: 1,
+    max_len: 64,
+    element_policies: &[reject_zero_salt],
+};
+
+fn reject_zero_salt(salt: &u128) -> Result<(), String> {
+    if *salt == 0 {
+        Err("a salt of zero is not allowed".into())
+    } else {
+        Ok(())
+    }
+}
+
+// Helper function: salts are written as "0x" + hex
+fn parse_hex_salt(s: &str) -> Result<u128, String> {
+    u128::from_str_radix(s.trim_start_matches("0x"), 16).map_err(|e| e.to_string())
+}
+
+// Helper function: the counterpart of `parse_hex_salt`
+fn format_hex_salt(salt: &u128) -> String {
+    format!("0x{:x}", salt)
+}
+
+/// Extracts a list of strings at `key` and parses each with `T::from_str`,
+/// under the same `rules` as `extract_parsed_list_with`.
+fn extract_parsed_list<T>(
+    table: &toml::map::Map<String, Value>,
+    key: &str,
+    rules: &ListRules<T>,
+) -> Result<Option<Vec<T>>, ThisProjectError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    extract_parsed_list_with(table, key, rules, |s| s.parse::<T>().map_err(|e| e.to_string()))
+}
+
+/// Extracts a list of strings at `key`, parsing each with `parse` and
+/// checking it against `rules`.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::ListError` naming the key, and for element
+/// errors the element index, e.g. `ipv4_addresses[2]: "bad": invalid IPv4
+/// address syntax`. Fails on the first error:
+/// - the key is missing and `rules.required`
+/// - the value is not an array
+/// - an element is not a string, does not parse, or fails a policy
+/// - the number of entries is outside `rules.min_len..=rules.max_len`
+fn extract_parsed_list_with<T>(
+    table: &toml::map::Map<String, Value>,
+    key: &str,
+    rules: &ListRules<T>,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<Option<Vec<T>>, ThisProjectError> {
+    let arr = match table.get(key) {
+        Some(Value::Array(arr)) => arr,
+        Some(_) => return Err(ThisProjectError::ListError(format!("{}: expected an array", key))),
+        None if rules.required => return Err(ThisProjectError::ListError(format!("Missing {}", key))),
+        None => return Ok(None),
+    };
+
+    if arr.len() < rules.min_len || arr.len() > rules.max_len {
+        return Err(ThisProjectError::ListError(format!(
+            "{}: {} entries, expected {} to {}",
+            key, arr.len(), rules.min_len, rules.max_len
+        )));
+    }
+
+    let mut items = Vec::with_capacity(arr.len());
+    for (index, val) in arr.iter().enumerate() {
+        let s = match val {
+            Value::String(s) => s,
+            _ => return Err(ThisProjectError::ListError(format!("{}[{}]: expected a string", key, index))),
+        };
+        let item = parse(s).map_err(|e| ThisProjectError::ListError(format!("{}[{}]: \"{}\": {}", key, index, s, e)))?;
+        for policy in rules.element_policies {
+            policy(&item).map_err(|e| ThisProjectError::ListError(format!("{}[{}]: \"{}\": {}", key, index, s, e)))?;
+        }
+        items.push(item);
+    }
+
+    if items.is_empty() && !rules.required {
+        Ok(None)
+    } else {
+        Ok(Some(items))
+    }
+}
+
+/*
+Unified address list
+
+`ip_addresses` holds IPv4 and IPv6 addresses together:
+
+    ip_addresses = [
+        "10.0.0.1",
+        "fe80::1",
+    ]
+
+The legacy `ipv4_addresses` and `ipv6_addresses` lists are still read, and
+either family is accepted in either list. On reading, all three lists are
+merged in the order ip_addresses, ipv4_addresses, ipv6_addresses; each
+entry is normalized (an IPv4-mapped address such as `::ffff:10.0.0.1`
+becomes `10.0.0.1`) and later duplicates are dropped. The 64-entry limit
+applies to each list in the file, not to the merged list.
+
+The serializer still writes `ipv4_addresses` and `ipv6_addresses`, split by
+family, so readers that predate `ip_addresses` see every address.
+*/
+
+/// `ip_addresses`, `ipv4_addresses`, `ipv6_addresses` as written in a file:
+/// optional, no unspecified address.
+const IP_LIST_RULES: ListRules<IpAddr> = ListRules {
+    required: false,
+    min_len: 0,
+    max_len: 64,
+    element_policies: &[reject_unspecified_ip],
+};
+
+/// The three source lists after they are merged into `ip_addresses`: each
+/// source is already limited by `IP_LIST_RULES`, so the merged list may hold
+/// up to all of their entries.
+const MERGED_IP_LIST_RULES: ListRules<IpAddr> = ListRules {
+    max_len: 3 * IP_LIST_RULES.max_len,
+    ..IP_LIST_RULES
+};
+
+fn reject_unspecified_ip(addr: &IpAddr) -> Result<(), String> {
+    if normalize_ip_addr(*addr).is_unspecified() {
+        Err(format!("the unspecified address {} is not a peer address", addr))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the canonical form of `addr`: IPv4-mapped IPv6 addresses
+/// (`::ffff:a.b.c.d`) become the IPv4 address; all others are unchanged.
+fn normalize_ip_addr(addr: IpAddr) -> IpAddr {
+    match addr {
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => IpAddr::V4(v4),
+            None => IpAddr::V6(v6),
+        },
+        IpAddr::V4(v4) => IpAddr::V4(v4),
+    }
+}
+
+/// Merges `ipv4_addresses` and `ipv6_addresses` into `ip_addresses` in a
+/// TOML table, in that order, normalizing each entry and dropping later
+/// duplicates; the legacy keys are removed. Each source list is checked
+/// against `IP_LIST_RULES`, so an error names the key as written in the file.
+fn merge_address_lists(table: &mut toml::map::Map<String, Value>) -> Result<(), ThisProjectError> {
+    let mut merged: Vec<IpAddr> = Vec::new();
+    for key in ["ip_addresses", "ipv4_addresses", "ipv6_addresses"] {
+        for addr in extract_parsed_list::<IpAddr>(table, key, &IP_LIST_RULES)?.unwrap_or_default() {
+            let normalized = normalize_ip_addr(addr);
+            if !merged.contains(&normalized) {
+                merged.push(normalized);
+            }
+        }
+    }
+
+    table.remove("ipv4_addresses");
+    table.remove("ipv6_addresses");
+    if merged.is_empty() {
+        table.remove("ip_addresses");
+    } else {
+        let values = merged.iter().map(|addr| Value::String(addr.to_string())).collect();
+        table.insert("ip_addresses".to_string(), Value::Array(values));
+    }
+    Ok(())
+}
+
+/*
+Reachability classes
+
+    Loopback    127.0.0.0/8, ::1
+    LinkLocal   169.254.0.0/16, fe80::/10
+    Private     10.0.0.0/8, 172.16.0.0/12, 192.168.0.0/16,
+                100.64.0.0/10 (shared/CGNAT), fc00::/7 (unique local)
+    Special     not a usable peer address: unspecified, multicast,
+                broadcast, documentation, benchmarking and reserved ranges
+    Global      everything else
+
+Addresses are classified after `normalize_ip_addr`, so `::ffff:10.0.0.1`
+is Private.
+*/
+
+/// Where an address can be reached from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Reachability {
+    Loopback,
+    LinkLocal,
+    Private,
+    Global,
+    Special,
+}
+
+impl fmt::Display for Reachability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Reachability::Loopback => "loopback",
+            Reachability::LinkLocal => "link-local",
+            Reachability::Private => "private",
+            Reachability::Global => "global",
+            Reachability::Special => "special-purpose",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Classifies one address (see the table above).
+fn classify_ip_addr(addr: IpAddr) -> Reachability {
+    match normalize_ip_addr(addr) {
+        IpAddr::V4(v4) => {
+            let [a, b, c, _] = v4.octets();
+            if v4.is_loopback() {
+                Reachability::Loopback
+            } else if v4.is_link_local() {
+                Reachability::LinkLocal
+            } else if v4.is_private() || (a == 100 && (64..128).contains(&b)) {
+                Reachability::Private
+            } else if v4.is_unspecified()
+                || a == 0
+                || v4.is_multicast()
+                || a >= 240
+                || v4.is_documentation()
+                || (a == 198 && (b == 18 || b == 19))
+                || (a == 192 && b == 0 && c == 0)
+            {
+                Reachability::Special
+            } else {
+                Reachability::Global
+            }
+        }
+        IpAddr::V6(v6) => {
+            let segments = v6.segments();
+            if v6.is_loopback() {
+                Reachability::Loopback
+            } else if segments[0] & 0xffc0 == 0xfe80 {
+                Reachability::LinkLocal
+            } else if segments[0] & 0xfe00 == 0xfc00 {
+                Reachability::Private
+            } else if v6.is_unspecified()
+                || v6.is_multicast()
+                || (segments[0] == 0x2001 && segments[1] == 0x0db8)
+                || (segments[0] == 0x0100 && segments[1..4] == [0, 0, 0])
+            {
+                Reachability::Special
+            } else {
+                Reachability::Global
+            }
+        }
+    }
+}
+
+impl CollaboratorTomlData {
+    /// Every address in `ip_addresses` with its class, in file order.
+    fn classified_addresses(&self) -> Vec<(IpAddr, Reachability)> {
+        self.ip_addresses
+            .iter()
+            .flatten()
+            .map(|addr| (*addr, classify_ip_addr(*addr)))
+            .collect()
+    }
+
+    /// The addresses of one class, in file order.
+    fn addresses_with_reachability(&self, reachability: Reachability) -> Vec<IpAddr> {
+        self.classified_addresses()
+            .into_iter()
+            .filter(|(_, class)| *class == reachability)
+            .map(|(addr, _)| addr)
+            .collect()
+    }
+}
+
+/// What `apply_address_policy` does with an address of a given class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReachabilityAction {
+    /// Keep it.
+    Allow,
+    /// Keep it and report a warning.
+    Warn,
+    /// Remove it from the collaborator and report a warning.
+    Drop,
+    /// Do not load the collaborator at all (an error).
+    Reject,
+}
+
+/// Load-time address policy: one action per class, plus ordering.
+///
+/// - `prefer_global_ipv6`: move global IPv6 addresses to the front of
+///   `ip_addresses` (otherwise in file order), so they are tried first.
+#[derive(Debug, Clone)]
+struct AddressPolicy {
+    loopback: ReachabilityAction,
+    link_local: ReachabilityAction,
+    private: ReachabilityAction,
+    global: ReachabilityAction,
+    special: ReachabilityAction,
+    prefer_global_ipv6: bool,
+}
+
+impl AddressPolicy {
+    /// Everything is kept; special-purpose addresses are warned about.
+    fn development() -> Self {
+        AddressPolicy {
+            loopback: ReachabilityAction::Allow,
+            link_local: ReachabilityAction::Allow,
+            private: ReachabilityAction::Allow,
+            global: ReachabilityAction::Allow,
+            special: ReachabilityAction::Warn,
+            prefer_global_ipv6: false,
+        }
+    }
+
+    /// Loopback and special-purpose addresses are dropped, link-local ones
+    /// warned about, and global IPv6 is tried first.
+    fn production() -> Self {
+        AddressPolicy {
+            loopback: ReachabilityAction::Drop,
+            link_local: ReachabilityAction::Warn,
+            private: ReachabilityAction::Allow,
+            global: ReachabilityAction::Allow,
+            special: ReachabilityAction::Drop,
+            prefer_global_ipv6: true,
+        }
+    }
+
+    fn action_for(&self, reachability: Reachability) -> ReachabilityAction {
+        match reachability {
+            Reachability::Loopback => self.loopback,
+            Reachability::LinkLocal => self.link_local,
+            Reachability::Private => self.private,
+            Reachability::Global => self.global,
+            Reachability::Special => self.special,
+        }
+    }
+}
+
+impl Default for AddressPolicy {
+    fn default() -> Self {
+        AddressPolicy::development()
+    }
+}
+
+/// A non-fatal finding of `apply_address_policy`; the collaborator is
+/// still loaded.
+#[derive(Debug, Clone, PartialEq)]
+struct PolicyWarning {
+    user_name: String,
+    address: IpAddr,
+    reachability: Reachability,
+    dropped: bool,
+}
+
+impl fmt::Display for PolicyWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Address Policy Warning: {}: {} is {}{}",
+            self.user_name,
+            self.address,
+            self.reachability,
+            if self.dropped { "; dropped" } else { "" }
+        )
+    }
+}
+
+/// Applies `policy` to the addresses of `collaborator`: drops, reorders and
+/// returns warnings as configured.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::AddressPolicyError` for the first address whose
+/// class is `ReachabilityAction::Reject`; `collaborator` is then unchanged.
+fn apply_address_policy(
+    collaborator: &mut CollaboratorTomlData,
+    policy: &AddressPolicy,
+) -> Result<Vec<PolicyWarning>, ThisProjectError> {
+    let mut warnings = Vec::new();
+    let mut kept = Vec::new();
+
+    for (address, reachability) in collaborator.classified_addresses() {
+        let action = policy.action_for(reachability);
+        if action == ReachabilityAction::Reject {
+            return Err(ThisProjectError::AddressPolicyError(format!(
+                "{}: {} is {}, which this policy rejects",
+                collaborator.user_name, address, reachability
+            )));
+        }
+        if action == ReachabilityAction::Warn || action == ReachabilityAction::Drop {
+            warnings.push(PolicyWarning {
+                user_name: collaborator.user_name.clone(),
+                address,
+                reachability,
+                dropped: action == ReachabilityAction::Drop,
+            });
+        }
+        if action != ReachabilityAction::Drop {
+            kept.push((address, reachability));
+        }
+    }
+
+    if policy.prefer_global_ipv6 {
+        // Stable: the order within each group is kept
+        kept.sort_by_key(|(address, reachability)| !(address.is_ipv6() && *reachability == Reachability::Global));
+    }
+
+    collaborator.ip_addresses = if kept.is_empty() {
+        None
+    } else {
+        Some(kept.into_iter().map(|(address, _)| address).collect())
+    };
+    Ok(warnings)
+}
+
+// Helper function: TOML table -> CollaboratorTomlData, failing on the first bad field
+fn collaborator_from_toml_table(table: &toml::map::Map<String, Value>) -> Result<CollaboratorTomlData, ThisProjectError> {
+
+    // Extract user_name
+    let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+    };
+
+    // Extract user_salt_list (required, so never None)
+    let user_salt_list = extract_parsed_list_with(table, "user_salt_list", &SALT_LIST_RULES, parse_hex_salt)?
+        .unwrap_or_default();
+
+    // Extract ip_addresses (after merge_address_lists, the only address list)
+    let ip_addresses = extract_parsed_list::<IpAddr>(table, "ip_addresses", &MERGED_IP_LIST_RULES)?;
+
+    // Extract gpg_key_public
+    let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+    };
+
+    // Extract sync_interval
+    let sync_interval = extract_u64(table, "sync_interval")?;
+
+    // Extract updated_at_timestamp
+    let updated_at_timestamp = extract_u64(table, "updated_at_timestamp")?;
+
+    Ok(CollaboratorTomlData {
+        user_name,
+        user_salt_list,
+        ip_addresses,
+        gpg_key_public,
+        sync_interval,
+        updated_at_timestamp,
+    })
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+// Helper function: TOML text -> CollaboratorTomlData with merged address lists
+fn deserialize_collaborator_from_toml(toml_string: &str) -> Result<CollaboratorTomlData, ThisProjectError> {
+    let mut table = match toml::from_str::<Value>(toml_string) {
+        Ok(Value::Table(table)) => table,
+        Ok(_) => return Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure: Expected a table".into())),
+        Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(e.to_string())),
+    };
+    merge_address_lists(&mut table)?;
+    collaborator_from_toml_table(&table)
+}
+
+/// Collaborators, file errors and policy warnings from `read_a_collaborator_setup_toml`.
+type LoadedCollaborators = (Vec<CollaboratorTomlData>, Vec<ThisProjectError>, Vec<PolicyWarning>);
+
+/// Toml Deserialization: Reads collaborator setup data from TOML files in a
+/// specified directory, applying an address policy.
+///
+/// Every `*.toml` file in `project_graph_data/collaborator_files_address_book`
+/// is parsed with `deserialize_collaborator_from_toml` and then goes through
+/// `apply_address_policy`. A file that does not parse, or has an address the
+/// policy rejects, is skipped and its error is added to the error vector;
+/// dropped and flagged addresses are warnings, and the collaborator is still
+/// returned.
+///
+/// # Returns
+///
+/// Returns a `Result` containing:
+/// - `Ok`: A tuple with:
+///     - A vector of successfully parsed `CollaboratorTomlData` instances.
+///     - A vector of any `ThisProjectError` encountered during parsing.
+///     - A vector of `PolicyWarning`s for the returned collaborators.
+/// - `Err`: A `ThisProjectError` if there was an error reading the directory or any file.
+fn read_a_collaborator_setup_toml(
+    policy: &AddressPolicy,
+) -> Result<LoadedCollaborators, ThisProjectError> {
+    let mut collaborators = Vec::new();
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    let dir_path = Path::new("project_graph_data/collaborator_files_address_book");
+
+    for entry in fs::read_dir(dir_path)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
+            let toml_string = fs::read_to_string(&path)?;
+
+            let mut collaborator = match deserialize_collaborator_from_toml(&toml_string) {
+                Ok(collaborator) => collaborator,
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                }
+            };
+            match apply_address_policy(&mut collaborator, policy) {
+                Ok(policy_warnings) => {
+                    warnings.extend(policy_warnings);
+                    collaborators.push(collaborator);
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+    }
+
+    Ok((collaborators, errors, warnings))
+}
+
+/// Serializes a `CollaboratorTomlData` struct into a TOML-formatted string.
+///
+/// One `key = value` line per field, in struct order, with escaped strings
+/// and salts as `0x` hex strings. The addresses are normalized and written as
+/// `ipv4_addresses` and `ipv6_addresses`, grouped by family, so every reader
+/// sees them; an empty list is left out.
+///
+/// # Error Handling
+///
+/// Returns an error if either address list would have more entries than
+/// `IP_LIST_RULES` allows, since the file could not be read back.
+fn serialize_collaborator_to_toml(collaborator: &CollaboratorTomlData) -> Result<String, ThisProjectError> {
+    let mut toml_string = String::new();
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", escape_toml_basic_string(&collaborator.user_name)));
+
+    // Add user_salt_list
+    serialize_list_with(&mut toml_string, "user_salt_list", &collaborator.user_salt_list, format_hex_salt);
+
+    // Add ipv4_addresses and ipv6_addresses
+    if let Some(addresses) = &collaborator.ip_addresses {
+        let (ipv4, ipv6): (Vec<IpAddr>, Vec<IpAddr>) =
+            addresses.iter().map(|addr| normalize_ip_addr(*addr)).partition(IpAddr::is_ipv4);
+        serialize_address_list(&mut toml_string, "ipv4_addresses", &ipv4)?;
+        serialize_address_list(&mut toml_string, "ipv6_addresses", &ipv6)?;
+    }
+
+    // Add gpg_key_public
+    toml_string.push_str(&format!("gpg_key_public = \"{}\"\n", escape_toml_basic_string(&collaborator.gpg_key_public)));
+
+    // Add sync_interval
+    toml_string.push_str(&format!("sync_interval = {}\n", collaborator.sync_interval));
+
+    // Add updated_at_timestamp
+    toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp));
+
+    Ok(toml_string)
+}
+
+// Helper function to write one address list, or nothing if it is empty
+fn serialize_address_list(toml_string: &mut String, key: &str, addresses: &[IpAddr]) -> Result<(), ThisProjectError> {
+    if addresses.len() > IP_LIST_RULES.max_len {
+        return Err(ThisProjectError::ListError(format!(
+            "{}: {} entries, at most {} can be written",
+            key, addresses.len(), IP_LIST_RULES.max_len
+        )));
+    }
+    if !addresses.is_empty() {
+        serialize_list(toml_string, key, addresses);
+    }
+    Ok(())
+}
+
+/// Writes `items` as a multi-line TOML array of strings using `Display`.
+fn serialize_list<T: fmt::Display>(toml_string: &mut String, key: &str, items: &[T]) {
+    serialize_list_with(toml_string, key, items, |item| item.to_string());
+}
+
+/// Writes `items` as a multi-line TOML array of strings, formatting each
+/// with `format` (the counterpart of the `parse` in `extract_parsed_list_with`).
+fn serialize_list_with<T>(toml_string: &mut String, key: &str, items: &[T], format: impl Fn(&T) -> String) {
+    toml_string.push_str(&format!("{} = [\n", key));
+    for item in items {
+        toml_string.push_str(&format!("    \"{}\",\n", escape_toml_basic_string(&format(item))));
+    }
+    toml_string.push_str("]\n");
+}
+
+// Helper function to escape a value for a TOML basic string ("...")
+fn escape_toml_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Function to write a TOML string to a file
+fn write_toml_to_file(file_path: &str, toml_string: &str) -> Result<(), ThisProjectError> {
+    // Attempt to create the file.
+    let mut file = match File::create(file_path) {
+        Ok(file) => file,
+        Err(e) => return Err(ThisProjectError::IoError(e)),
+    };
+
+    // Attempt to write to the file.
+    if let Err(e) = file.write_all(toml_string.as_bytes()) {
+        return Err(ThisProjectError::IoError(e));
+    }
+
+    // Everything successful!
+    Ok(())
+}
+
+fn main() {
+    // Example CollaboratorTomlData instance with one address of each class
+    let collaborator = CollaboratorTomlData {
+        user_name: "Bob".to_string(),
+        user_salt_list: vec![0x123456789abcdef0, 0xabcdef0123456789],
+        ip_addresses: Some(vec![
+            IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+            IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)),
+            IpAddr::V6(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1)),
+            IpAddr::V4(Ipv4Addr::new(203, 0, 113, 9)),
+            IpAddr::V4(Ipv4Addr::new(93, 184, 216, 34)),
+            IpAddr::V6(Ipv6Addr::new(0x2606, 0x2800, 0x220, 0x1, 0x248, 0x1893, 0x25c8, 0x1946)),
+        ]),
+        gpg_key_public: "-----BEGIN PGP PUBLIC KEY BLOCK----- ...".to_string(),
+        sync_interval: 300,
+        updated_at_timestamp: 1728308000,
+    };
+
+    for (address, reachability) in collaborator.classified_addresses() {
+        println!("{} is {}", address, reachability);
+    }
+    println!("Private: {:?}", collaborator.addresses_with_reachability(Reachability::Private));
+
+    match serialize_collaborator_to_toml(&collaborator) {
+        Ok(toml_string) => {
+            // Read back under the production policy
+            match deserialize_collaborator_from_toml(&toml_string) {
+                Ok(mut read_back) => match apply_address_policy(&mut read_back, &AddressPolicy::production()) {
+                    Ok(warnings) => {
+                        for warning in warnings {
+                            println!("{}", warning);
+                        }
+                        println!("Addresses under the production policy: {:?}", read_back.ip_addresses);
+                    }
+                    Err(e) => println!("{}", e),
+                },
+                Err(e) => println!("Error reading back: {}", e),
+            }
+
+            // Write the TOML string to a file (example file path)
+            match write_toml_to_file("collaborator_data.toml", &toml_string) {
+                Ok(_) => println!("TOML data written to file successfully."),
+                Err(e) => println!("Error writing to file: {}", e),
+            }
+        }
+        Err(e) => println!("Error serializing to TOML: {}", e),
+    }
+
+    // A policy that rejects loopback outright
+    let strict_policy = AddressPolicy {
+        loopback: ReachabilityAction::Reject,
+        ..AddressPolicy::production()
+    };
+    match read_a_collaborator_setup_toml(&strict_policy) {
+        Ok((collaborators, errors, warnings)) => {
+            if !errors.is_empty() {
+                println!("Errors encountered:");
+                for err in errors {
+                    println!("{}", err);
+                }
+            }
+            if !warnings.is_empty() {
+                println!("Warnings:");
+                for warning in warnings {
+                    println!("{}", warning);
+                }
+            }
+
+            println!("Collaborators:");
+            for collaborator in collaborators {
+                println!("{:#?}", collaborator);
+            }
+        }
+        Err(e) => {
+            println!("Error reading TOML files: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn carol_with_addresses(addresses: &str) -> CollaboratorTomlData {
+        let toml_string = format!(
+            "user_name = \"Carol\"\nuser_salt_list = [\"0x1\"]\nip_addresses = [{}]\ngpg_key_public = \"\"\nsync_interval = 60\nupdated_at_timestamp = 0\n",
+            addresses
+        );
+        deserialize_collaborator_from_toml(&toml_string).unwrap()
+    }
+
+    fn addresses(list: &[&str]) -> Option<Vec<IpAddr>> {
+        Some(list.iter().map(|a| a.parse().unwrap()).collect())
+    }
+
+    #[test]
+    fn classification_boundaries() {
+        let cases = [
+            // Shared address space 100.64.0.0/10
+            ("100.63.255.255", Reachability::Global),
+            ("100.64.0.0", Reachability::Private),
+            ("100.127.255.255", Reachability::Private),
+            ("100.128.0.0", Reachability::Global),
+            // 172.16.0.0/12
+            ("172.15.255.255", Reachability::Global),
+            ("172.16.0.0", Reachability::Private),
+            ("172.31.255.255", Reachability::Private),
+            ("172.32.0.0", Reachability::Global),
+            // 169.254.0.0/16
+            ("169.253.255.255", Reachability::Global),
+            ("169.254.0.1", Reachability::LinkLocal),
+            ("169.255.0.0", Reachability::Global),
+            ("127.0.0.1", Reachability::Loopback),
+            ("0.0.0.0", Reachability::Special),
+            ("255.255.255.255", Reachability::Special),
+            ("192.0.2.1", Reachability::Special),
+            ("8.8.8.8", Reachability::Global),
+            // fe80::/10; fec0:: (old site-local) is outside it
+            ("fe80::1", Reachability::LinkLocal),
+            ("febf:ffff::1", Reachability::LinkLocal),
+            ("fec0::1", Reachability::Global),
+            // fc00::/7
+            ("fbff:ffff::1", Reachability::Global),
+            ("fc00::1", Reachability::Private),
+            ("fdff:ffff::1", Reachability::Private),
+            ("::1", Reachability::Loopback),
+            ("::ffff:10.0.0.1", Reachability::Private),
+            ("::ffff:127.0.0.1", Reachability::Loopback),
+            ("2001:db8::", Reachability::Special),
+            ("2001:db9::", Reachability::Global),
+            ("ff02::1", Reachability::Special),
+            ("2606:4700::1111", Reachability::Global),
+        ];
+        for (address, expected) in cases {
+            assert_eq!(classify_ip_addr(address.parse().unwrap()), expected, "{}", address);
+        }
+    }
+
+    #[test]
+    fn production_tries_global_ipv6_first() {
+        let file_order = ["10.0.0.1", "2001:4860::1", "fd00::1", "8.8.8.8", "2606:4700::1"];
+        let quoted: Vec<String> = file_order.iter().map(|a| format!("\"{}\"", a)).collect();
+
+        let mut collaborator = carol_with_addresses(&quoted.join(", "));
+        apply_address_policy(&mut collaborator, &AddressPolicy::development()).unwrap();
+        assert_eq!(collaborator.ip_addresses, addresses(&file_order));
+
+        apply_address_policy(&mut collaborator, &AddressPolicy::production()).unwrap();
+        assert_eq!(
+            collaborator.ip_addresses,
+            addresses(&["2001:4860::1", "2606:4700::1", "10.0.0.1", "fd00::1", "8.8.8.8"])
+        );
+    }
+
+    #[test]
+    fn production_drops_and_warns() {
+        let mut collaborator = carol_with_addresses("\"fe80::1\", \"127.0.0.1\", \"10.0.0.2\", \"0.0.0.1\"");
+        let warnings = apply_address_policy(&mut collaborator, &AddressPolicy::production()).unwrap();
+
+        let summary: Vec<(String, Reachability, bool)> =
+            warnings.iter().map(|w| (w.address.to_string(), w.reachability, w.dropped)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("fe80::1".to_string(), Reachability::LinkLocal, false),
+                ("127.0.0.1".to_string(), Reachability::Loopback, true),
+                ("0.0.0.1".to_string(), Reachability::Special, true),
+            ]
+        );
+        assert_eq!(collaborator.ip_addresses, addresses(&["fe80::1", "10.0.0.2"]));
+    }
+
+    #[test]
+    fn rejecting_policy_leaves_the_collaborator_unchanged() {
+        let mut collaborator = carol_with_addresses("\"10.0.0.1\", \"127.0.0.1\"");
+        let policy = AddressPolicy {
+            loopback: ReachabilityAction::Reject,
+            ..AddressPolicy::production()
+        };
+        assert!(apply_address_policy(&mut collaborator, &policy).is_err());
+        assert_eq!(collaborator.ip_addresses, addresses(&["10.0.0.1", "127.0.0.1"]));
+    }
+}
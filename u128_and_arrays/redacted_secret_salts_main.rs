@@ -0,0 +1,417 @@
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{compiler_fence, Ordering};
+use toml::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<SecretSalt>,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+        }
+    }
+}
+
+/// Placeholder printed instead of a secret value.
+const REDACTED: &str = "<redacted>";
+
+/// A salt from `user_salt_list`, kept out of logs.
+///
+/// - `Debug` and `Display` print `<redacted>`, so `{:?}`, `{:#?}` and `{}`
+///   on a `CollaboratorTomlData` never show salt values.
+/// - The value is only available through `expose_secret`, so every place
+///   that uses the real value (the serializer, hashing) is easy to find.
+/// - The value is overwritten with zeros when the `SecretSalt` is dropped.
+///
+/// The zeroing covers this value only: copies made by `expose_secret`, the
+/// TOML text the salt was read from, and old buffers left behind when a
+/// `Vec<SecretSalt>` grows are not wiped.
+///
+/// Not `Copy`, so that every copy is an explicit `clone()` and is zeroed
+/// on drop as well.
+#[derive(Clone)]
+struct SecretSalt(u128);
+
+impl SecretSalt {
+    fn new(value: u128) -> SecretSalt {
+        SecretSalt(value)
+    }
+
+    /// Returns the salt value. Do not log or print the result.
+    fn expose_secret(&self) -> u128 {
+        self.0
+    }
+}
+
+impl PartialEq for SecretSalt {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for SecretSalt {}
+
+impl fmt::Debug for SecretSalt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SecretSalt({})", REDACTED)
+    }
+}
+
+impl fmt::Display for SecretSalt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", REDACTED)
+    }
+}
+
+impl Drop for SecretSalt {
+    fn drop(&mut self) {
+        // A volatile write is not optimized away even though the value is
+        // never read again; the fence keeps it from being reordered past
+        // the deallocation
+        unsafe {
+            std::ptr::write_volatile(&mut self.0, 0);
+        }
+        compiler_fence(Ordering::SeqCst);
+    }
+}
+
+/// Redacted pretty-printer for a whole record.
+///
+/// Prints every field, one per line, with salts replaced by their count:
+///
+/// ```text
+/// Collaborator "Bob"
+///     user_salt_list: 2 salts <redacted>
+///     ipv4_addresses: 192.168.1.1, 10.0.0.1
+///     ipv6_addresses: fe80::1, ::1
+///     gpg_key_public: -----BEGIN PGP PUBLIC KEY BLOCK----- ...
+///     sync_interval: 300
+///     updated_at_timestamp: 1728308000
+/// ```
+///
+/// Multi-line `gpg_key_public` values are shown as their first line plus
+/// the number of lines, to keep log entries short.
+impl fmt::Display for CollaboratorTomlData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Collaborator {:?}", self.user_name)?;
+        writeln!(
+            f,
+            "    user_salt_list: {} salt{} {}",
+            self.user_salt_list.len(),
+            if self.user_salt_list.len() == 1 { "" } else { "s" },
+            REDACTED
+        )?;
+        writeln!(f, "    ipv4_addresses: {}", join_addresses(&self.ipv4_addresses))?;
+        writeln!(f, "    ipv6_addresses: {}", join_addresses(&self.ipv6_addresses))?;
+
+        let key_line_count = self.gpg_key_public.lines().count();
+        let first_key_line = self.gpg_key_public.lines().next().unwrap_or("");
+        if key_line_count > 1 {
+            writeln!(f, "    gpg_key_public: {} ({} lines)", first_key_line, key_line_count)?;
+        } else {
+            writeln!(f, "    gpg_key_public: {}", first_key_line)?;
+        }
+
+        writeln!(f, "    sync_interval: {}", self.sync_interval)?;
+        write!(f, "    updated_at_timestamp: {}", self.updated_at_timestamp)
+    }
+}
+
+// Helper function to show an optional address list on one line
+fn join_addresses<T: fmt::Display>(addresses: &Option<Vec<T>>) -> String {
+    match addresses {
+        Some(addr_vec) => addr_vec.iter().map(|addr| addr.to_string()).collect::<Vec<_>>().join(", "),
+        None => "(none)".to_string(),
+    }
+}
+
+/// Reads collaborator setup data from a TOML file for a specific user.
+///
+/// Reads `project_graph_data/collaborator_files_address_book/{collaborator_name}__collaborator.toml`
+/// and stops at the first problem, which is returned as the error. Salts are
+/// wrapped in `SecretSalt` as soon as they are parsed, and salt parse errors
+/// do not include the salt text.
+fn read_one_collaborator_setup_toml(collaborator_name: &str) -> Result<CollaboratorTomlData, ThisProjectError> {
+
+    // 1. Construct File Path
+    let file_path = Path::new("project_graph_data/collaborator_files_address_book")
+        .join(format!("{}__collaborator.toml", collaborator_name));
+
+    // 2. Read TOML File
+    let toml_string = fs::read_to_string(&file_path)?;
+
+    // 3. Parse TOML Data (handle potential toml::de::Error)
+    let toml_value = match toml::from_str::<Value>(&toml_string) {
+        Ok(value) => value,
+        Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(e.to_string())),
+    };
+
+    // 4. Extract Data from TOML Value
+    if let Value::Table(table) = toml_value {
+
+        // Extract user_name
+        let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+            s.clone()
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+        };
+
+        // Extract user_salt_list
+        let user_salt_list = if let Some(Value::Array(arr)) = table.get("user_salt_list") {
+            arr.iter()
+                .map(|val| {
+                    if let Value::String(s) = val {
+                        u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                            .map(SecretSalt::new)
+                            .map_err(ThisProjectError::ParseIntError)
+                    } else {
+                        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                    }
+                })
+                .collect::<Result<Vec<SecretSalt>, ThisProjectError>>()?
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_salt_list".into()));
+        };
+
+        // Extract ipv4_addresses
+        let ipv4_addresses = extract_ipv4_addresses(&table, "ipv4_addresses")?;
+
+        // Extract ipv6_addresses
+        let ipv6_addresses = extract_ipv6_addresses(&table, "ipv6_addresses")?;
+
+        // Extract gpg_key_public
+        let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+            s.clone()
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+        };
+
+        // Extract sync_interval
+        let sync_interval = extract_u64(&table, "sync_interval")?;
+
+        // Extract updated_at_timestamp
+        let updated_at_timestamp = extract_u64(&table, "updated_at_timestamp")?;
+
+        // 5. Return CollaboratorTomlData
+        Ok(CollaboratorTomlData {
+            user_name,
+            user_salt_list,
+            ipv4_addresses,
+            ipv6_addresses,
+            gpg_key_public,
+            sync_interval,
+            updated_at_timestamp,
+        })
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure: Expected a table".into()))
+    }
+}
+
+fn extract_ipv4_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv4Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv4Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+fn extract_ipv6_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv6Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv6Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+/// Serializes a `CollaboratorTomlData` struct into a TOML-formatted string.
+///
+/// One `key = value` line per field, in struct order; salts are written as
+/// `0x` hex strings and an IP list that is `None` is left out.
+/// This is the one place that writes salt values out, via `expose_secret`;
+/// the returned string holds them in clear text, so do not log it.
+fn serialize_collaborator_to_toml(collaborator: &CollaboratorTomlData) -> Result<String, ThisProjectError> {
+    let mut toml_string = String::new();
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", collaborator.user_name));
+
+    // Add user_salt_list
+    toml_string.push_str("user_salt_list = [\n");
+    for salt in &collaborator.user_salt_list {
+        toml_string.push_str(&format!("    \"0x{:x}\",\n", salt.expose_secret()));
+    }
+    toml_string.push_str("]\n");
+
+    // Add ipv4_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv4_addresses", &collaborator.ipv4_addresses)?;
+
+    // Add ipv6_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv6_addresses", &collaborator.ipv6_addresses)?;
+
+    // Add gpg_key_public
+    toml_string.push_str(&format!("gpg_key_public = \"{}\"\n", collaborator.gpg_key_public));
+
+    // Add sync_interval
+    toml_string.push_str(&format!("sync_interval = {}\n", collaborator.sync_interval));
+
+    // Add updated_at_timestamp
+    toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp));
+
+    Ok(toml_string)
+}
+
+// Helper function to serialize IP addresses to TOML array format
+fn serialize_ip_addresses<T: std::fmt::Display>(
+    toml_string: &mut String,
+    key: &str,
+    addresses: &Option<Vec<T>>
+) -> Result<(), ThisProjectError> {
+    if let Some(addr_vec) = addresses {
+        toml_string.push_str(&format!("{} = [\n", key));
+        for addr in addr_vec {
+            toml_string.push_str(&format!("    \"{}\",\n", addr));
+        }
+        toml_string.push_str("]\n");
+    }
+    Ok(()) // Return Ok(()) if the addresses field is None
+}
+
+// Function to write a TOML string to a file
+fn write_toml_to_file(file_path: &str, toml_string: &str) -> Result<(), ThisProjectError> {
+    // Attempt to create the file.
+    let mut file = match File::create(file_path) {
+        Ok(file) => file,
+        Err(e) => return Err(ThisProjectError::IoError(e)),
+    };
+
+    // Attempt to write to the file.
+    if let Err(e) = file.write_all(toml_string.as_bytes()) {
+        return Err(ThisProjectError::IoError(e));
+    }
+
+    // Everything successful!
+    Ok(())
+}
+
+fn main() {
+    // Example CollaboratorTomlData instance
+    let collaborator = CollaboratorTomlData {
+        user_name: "Bob".to_string(),
+        user_salt_list: vec![SecretSalt::new(0x123456789abcdef0), SecretSalt::new(0xabcdef0123456789)],
+        ipv4_addresses: Some(vec![Ipv4Addr::new(192, 168, 1, 1), Ipv4Addr::new(10, 0, 0, 1)]),
+        ipv6_addresses: Some(vec![Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)]),
+        gpg_key_public: "-----BEGIN PGP PUBLIC KEY BLOCK----- ...".to_string(),
+        sync_interval: 300,
+        updated_at_timestamp: 1728308000,
+    };
+
+    // Debug output is safe to log: salts show as SecretSalt(<redacted>)
+    println!("{:#?}", collaborator);
+
+    // Redacted pretty-printer
+    println!("{}", collaborator);
+
+    // Serialize the collaborator data to a TOML string and write it to a
+    // file; the TOML text holds the real salts, so it is not printed
+    match serialize_collaborator_to_toml(&collaborator) {
+        Ok(toml_string) => match write_toml_to_file("collaborator_data.toml", &toml_string) {
+            Ok(_) => println!("TOML data written to file successfully."),
+            Err(e) => println!("Error writing to file: {}", e),
+        },
+        Err(e) => println!("Error serializing to TOML: {}", e),
+    }
+
+    // Read one collaborator back
+    let username = "alice";
+    match read_one_collaborator_setup_toml(username) {
+        Ok(collaborator) => {
+            println!("Collaborator Data for {}:", username);
+            println!("{}", collaborator);
+        }
+        Err(e) => {
+            println!("Error reading collaborator data for {}: {}", username, e);
+        }
+    }
+}
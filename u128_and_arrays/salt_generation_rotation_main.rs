@@ -0,0 +1,505 @@
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use toml::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    SaltError(String),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::SaltError(err) => write!(f, "Salt Error: {}", err),
+        }
+    }
+}
+
+/// The OS entropy source read by `generate_salts`.
+const ENTROPY_SOURCE_PATH: &str = "/dev/urandom";
+
+/// Settings for adding and rotating salts.
+///
+/// - `max_salts`: the longest `user_salt_list` allowed. `append_new_salts`
+///   refuses to grow a list past it; `rotate_salts` drops the oldest salts
+///   to stay within it. Must be at least 1. Default 8.
+#[derive(Debug, Clone)]
+struct SaltPolicy {
+    max_salts: usize,
+}
+
+impl Default for SaltPolicy {
+    fn default() -> Self {
+        SaltPolicy { max_salts: 8 }
+    }
+}
+
+/// Generates `count` new random salts from the OS entropy source
+/// (`/dev/urandom`, read with std only).
+///
+/// The returned salts are all different from each other, from every salt
+/// in `existing`, and from zero. With 128 random bits a repeat practically
+/// never happens; if one does, that salt is simply drawn again.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::IoError` if `/dev/urandom` cannot be opened
+/// or read (e.g. on a system without it). Salts are never produced from a
+/// weaker fallback source.
+///
+/// # use with
+/// let salts = generate_salts(2, &collaborator.user_salt_list)?;
+fn generate_salts(count: usize, existing: &[u128]) -> Result<Vec<u128>, ThisProjectError> {
+    let mut entropy = File::open(ENTROPY_SOURCE_PATH)?;
+    let mut salts: Vec<u128> = Vec::with_capacity(count);
+
+    while salts.len() < count {
+        let mut bytes = [0u8; 16];
+        entropy.read_exact(&mut bytes)?;
+        let salt = u128::from_be_bytes(bytes);
+
+        if salt != 0 && !existing.contains(&salt) && !salts.contains(&salt) {
+            salts.push(salt);
+        }
+    }
+
+    Ok(salts)
+}
+
+/// Removes repeated salts from a list, keeping the first (oldest) copy of
+/// each, and returns how many were removed.
+fn dedup_salts(salt_list: &mut Vec<u128>) -> usize {
+    let original_len = salt_list.len();
+    let mut seen = Vec::with_capacity(original_len);
+    salt_list.retain(|salt| {
+        if seen.contains(salt) {
+            false
+        } else {
+            seen.push(*salt);
+            true
+        }
+    });
+    original_len - salt_list.len()
+}
+
+// Helper function to reject a policy that allows no salts at all
+fn check_salt_policy(policy: &SaltPolicy) -> Result<(), ThisProjectError> {
+    if policy.max_salts == 0 {
+        return Err(ThisProjectError::SaltError("max_salts must be at least 1".into()));
+    }
+    Ok(())
+}
+
+/// Appends `count` new salts to the end of `salt_list` (which is ordered
+/// oldest first), without removing any.
+///
+/// Repeated salts already in the list are removed first (see `dedup_salts`),
+/// so afterwards every salt in the list is unique.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::SaltError`, leaving the list unchanged, if the
+/// list would grow past `policy.max_salts`. Use `rotate_salts` to make room.
+///
+/// # Returns
+///
+/// The newly added salts.
+fn append_new_salts(salt_list: &mut Vec<u128>, count: usize, policy: &SaltPolicy) -> Result<Vec<u128>, ThisProjectError> {
+    check_salt_policy(policy)?;
+
+    let mut unique_list = salt_list.clone();
+    dedup_salts(&mut unique_list);
+    if unique_list.len() + count > policy.max_salts {
+        return Err(ThisProjectError::SaltError(format!(
+            "Cannot add {} salt(s): the list has {} and max_salts is {}",
+            count,
+            unique_list.len(),
+            policy.max_salts
+        )));
+    }
+
+    let new_salts = generate_salts(count, &unique_list)?;
+    unique_list.extend_from_slice(&new_salts);
+    *salt_list = unique_list;
+    Ok(new_salts)
+}
+
+/// Rotates `salt_list`: appends `count` new salts and removes the oldest
+/// salts (from the front of the list) until it has at most
+/// `policy.max_salts` entries.
+///
+/// Repeated salts are removed first (see `dedup_salts`). A retired salt is
+/// never handed out again by this call, because new salts are drawn to be
+/// different from every salt in the list before rotation.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::SaltError`, leaving the list unchanged, if
+/// `count` is 0 or larger than `policy.max_salts` (the new salts would
+/// themselves be rotated out).
+///
+/// # Returns
+///
+/// A tuple of (new salts, retired salts), both oldest first.
+fn rotate_salts(salt_list: &mut Vec<u128>, count: usize, policy: &SaltPolicy) -> Result<(Vec<u128>, Vec<u128>), ThisProjectError> {
+    check_salt_policy(policy)?;
+    if count == 0 || count > policy.max_salts {
+        return Err(ThisProjectError::SaltError(format!(
+            "Cannot rotate in {} salt(s) with max_salts {}",
+            count, policy.max_salts
+        )));
+    }
+
+    let mut unique_list = salt_list.clone();
+    dedup_salts(&mut unique_list);
+
+    let new_salts = generate_salts(count, &unique_list)?;
+    unique_list.extend_from_slice(&new_salts);
+
+    let excess = unique_list.len().saturating_sub(policy.max_salts);
+    let retired: Vec<u128> = unique_list.drain(..excess).collect();
+
+    *salt_list = unique_list;
+    Ok((new_salts, retired))
+}
+
+/// Rotates the salts of one collaborator's file and saves the result.
+///
+/// Reads `project_graph_data/collaborator_files_address_book/{collaborator_name}__collaborator.toml`
+/// with `read_one_collaborator_setup_toml`, applies `rotate_salts`, sets
+/// `updated_at_timestamp` to the current time, and writes the file back
+/// with `serialize_collaborator_to_toml` and `write_toml_to_file`.
+///
+/// Nothing is written if reading, rotating or serializing fails.
+///
+/// # Returns
+///
+/// The updated collaborator data.
+///
+/// # use with
+/// let collaborator = rotate_collaborator_salts_in_file("alice", 1, &SaltPolicy::default())?;
+fn rotate_collaborator_salts_in_file(
+    collaborator_name: &str,
+    count: usize,
+    policy: &SaltPolicy,
+) -> Result<CollaboratorTomlData, ThisProjectError> {
+    let mut collaborator = read_one_collaborator_setup_toml(collaborator_name)?;
+
+    rotate_salts(&mut collaborator.user_salt_list, count, policy)?;
+    collaborator.updated_at_timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| ThisProjectError::SaltError(format!("System clock is before 1970: {}", e)))?
+        .as_secs();
+
+    let toml_string = serialize_collaborator_to_toml(&collaborator)?;
+    let file_path = Path::new("project_graph_data/collaborator_files_address_book")
+        .join(format!("{}__collaborator.toml", collaborator_name));
+    write_toml_to_file(&file_path.to_string_lossy(), &toml_string)?;
+
+    Ok(collaborator)
+}
+
+/// Reads collaborator setup data from a TOML file for a specific user.
+///
+/// Reads `project_graph_data/collaborator_files_address_book/{collaborator_name}__collaborator.toml`
+/// and stops at the first problem: an unreadable file, invalid TOML, or a
+/// missing or malformed field is returned as the error.
+fn read_one_collaborator_setup_toml(collaborator_name: &str) -> Result<CollaboratorTomlData, ThisProjectError> {
+
+    // 1. Construct File Path
+    let file_path = Path::new("project_graph_data/collaborator_files_address_book")
+        .join(format!("{}__collaborator.toml", collaborator_name));
+
+    // 2. Read TOML File
+    let toml_string = fs::read_to_string(&file_path)?;
+
+    // 3. Parse TOML Data (handle potential toml::de::Error)
+    let toml_value = match toml::from_str::<Value>(&toml_string) {
+        Ok(value) => value,
+        Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(e.to_string())),
+    };
+
+    // 4. Extract Data from TOML Value
+    if let Value::Table(table) = toml_value {
+
+        // Extract user_name
+        let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+            s.clone()
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+        };
+
+        // Extract user_salt_list
+        let user_salt_list = if let Some(Value::Array(arr)) = table.get("user_salt_list") {
+            arr.iter()
+                .map(|val| {
+                    if let Value::String(s) = val {
+                        u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                            .map_err(ThisProjectError::ParseIntError)
+                    } else {
+                        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                    }
+                })
+                .collect::<Result<Vec<u128>, ThisProjectError>>()?
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_salt_list".into()));
+        };
+
+        // Extract ipv4_addresses
+        let ipv4_addresses = extract_ipv4_addresses(&table, "ipv4_addresses")?;
+
+        // Extract ipv6_addresses
+        let ipv6_addresses = extract_ipv6_addresses(&table, "ipv6_addresses")?;
+
+        // Extract gpg_key_public
+        let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+            s.clone()
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+        };
+
+        // Extract sync_interval
+        let sync_interval = extract_u64(&table, "sync_interval")?;
+
+        // Extract updated_at_timestamp
+        let updated_at_timestamp = extract_u64(&table, "updated_at_timestamp")?;
+
+        // 5. Return CollaboratorTomlData
+        Ok(CollaboratorTomlData {
+            user_name,
+            user_salt_list,
+            ipv4_addresses,
+            ipv6_addresses,
+            gpg_key_public,
+            sync_interval,
+            updated_at_timestamp,
+        })
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure: Expected a table".into()))
+    }
+}
+
+fn extract_ipv4_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv4Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv4Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+fn extract_ipv6_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv6Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv6Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+/// Serializes a `CollaboratorTomlData` struct into a TOML-formatted string.
+///
+/// One `key = value` line per field, in struct order; salts are written as
+/// `0x` hex strings and an IP list that is `None` is left out. `user_name`
+/// and `gpg_key_public` are escaped, so that a file read back in by
+/// `rotate_collaborator_salts_in_file` is always written out as valid TOML.
+fn serialize_collaborator_to_toml(collaborator: &CollaboratorTomlData) -> Result<String, ThisProjectError> {
+    let mut toml_string = String::new();
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", escape_toml_basic_string(&collaborator.user_name)));
+
+    // Add user_salt_list
+    toml_string.push_str("user_salt_list = [\n");
+    for salt in &collaborator.user_salt_list {
+        toml_string.push_str(&format!("    \"0x{:x}\",\n", salt));
+    }
+    toml_string.push_str("]\n");
+
+    // Add ipv4_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv4_addresses", &collaborator.ipv4_addresses)?;
+
+    // Add ipv6_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv6_addresses", &collaborator.ipv6_addresses)?;
+
+    // Add gpg_key_public
+    toml_string.push_str(&format!("gpg_key_public = \"{}\"\n", escape_toml_basic_string(&collaborator.gpg_key_public)));
+
+    // Add sync_interval
+    toml_string.push_str(&format!("sync_interval = {}\n", collaborator.sync_interval));
+
+    // Add updated_at_timestamp
+    toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp));
+
+    Ok(toml_string)
+}
+
+// Helper function to escape a value for a TOML basic string ("...")
+fn escape_toml_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Helper function to serialize IP addresses to TOML array format
+fn serialize_ip_addresses<T: std::fmt::Display>(
+    toml_string: &mut String,
+    key: &str,
+    addresses: &Option<Vec<T>>
+) -> Result<(), ThisProjectError> {
+    if let Some(addr_vec) = addresses {
+        toml_string.push_str(&format!("{} = [\n", key));
+        for addr in addr_vec {
+            toml_string.push_str(&format!("    \"{}\",\n", addr));
+        }
+        toml_string.push_str("]\n");
+    }
+    Ok(()) // Return Ok(()) if the addresses field is None
+}
+
+// Function to write a TOML string to a file
+fn write_toml_to_file(file_path: &str, toml_string: &str) -> Result<(), ThisProjectError> {
+    // Attempt to create the file.
+    let mut file = match File::create(file_path) {
+        Ok(file) => file,
+        Err(e) => return Err(ThisProjectError::IoError(e)),
+    };
+
+    // Attempt to write to the file.
+    if let Err(e) = file.write_all(toml_string.as_bytes()) {
+        return Err(ThisProjectError::IoError(e));
+    }
+
+    // Everything successful!
+    Ok(())
+}
+
+fn main() {
+    let policy = SaltPolicy { max_salts: 4 };
+
+    // Example CollaboratorTomlData instance, with salts from the OS instead of typed in
+    let user_salt_list = match generate_salts(2, &[]) {
+        Ok(salts) => salts,
+        Err(e) => {
+            println!("Error generating salts: {}", e);
+            return;
+        }
+    };
+    let mut collaborator = CollaboratorTomlData {
+        user_name: "Bob".to_string(),
+        user_salt_list,
+        ipv4_addresses: Some(vec![Ipv4Addr::new(192, 168, 1, 1), Ipv4Addr::new(10, 0, 0, 1)]),
+        ipv6_addresses: Some(vec![Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)]),
+        gpg_key_public: "-----BEGIN PGP PUBLIC KEY BLOCK----- ...".to_string(),
+        sync_interval: 300,
+        updated_at_timestamp: 1728308000,
+    };
+
+    // Append until the list is full, then rotate
+    match append_new_salts(&mut collaborator.user_salt_list, 2, &policy) {
+        Ok(new_salts) => println!("Appended {} salt(s), list now has {}", new_salts.len(), collaborator.user_salt_list.len()),
+        Err(e) => println!("Error appending salts: {}", e),
+    }
+    if let Err(e) = append_new_salts(&mut collaborator.user_salt_list, 1, &policy) {
+        println!("As expected, the list is full: {}", e);
+    }
+    match rotate_salts(&mut collaborator.user_salt_list, 1, &policy) {
+        Ok((new_salts, retired)) => println!("Rotated in {} salt(s), retired {}", new_salts.len(), retired.len()),
+        Err(e) => println!("Error rotating salts: {}", e),
+    }
+
+    // Rotate and save a collaborator file from the address book
+    match rotate_collaborator_salts_in_file("alice", 1, &SaltPolicy::default()) {
+        Ok(alice) => println!("Saved alice with {} salt(s)", alice.user_salt_list.len()),
+        Err(e) => println!("Error rotating salts for alice: {}", e),
+    }
+}
@@ -0,0 +1,789 @@
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::ffi::OsStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use toml::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    SchemaVersionError(String),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::SchemaVersionError(err) => write!(f, "Schema Version Error: {}", err),
+        }
+    }
+}
+
+/*
+Schema versions of collaborator files
+
+    1   `user_name` and `user_salt_list` only (the `u128_array_only.rs` shape)
+    2   the seven `CollaboratorTomlData` fields
+
+Files written before the `schema_version` key existed do not have it; their
+version is inferred from their keys (see `detect_schema_version`). Files
+written by `serialize_collaborator_to_toml` in this file start with
+`schema_version = 2`.
+
+To change the layout: add the new version's shape above, raise
+`CURRENT_SCHEMA_VERSION`, and append a `Migration` from the previous
+version to `MIGRATIONS`.
+*/
+
+/// The schema version of `CollaboratorTomlData` as defined in this file.
+const CURRENT_SCHEMA_VERSION: i64 = 2;
+
+/// The TOML key recording a file's schema version.
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// The keys `serialize_collaborator_to_toml` writes; anything else in a
+/// file is lost when the file is rewritten.
+const SERIALIZED_KEYS: [&str; 8] = [
+    SCHEMA_VERSION_KEY,
+    "user_name",
+    "user_salt_list",
+    "ipv4_addresses",
+    "ipv6_addresses",
+    "gpg_key_public",
+    "sync_interval",
+    "updated_at_timestamp",
+];
+
+/// `sync_interval` given to collaborators migrated from version 1 (seconds).
+const MIGRATED_SYNC_INTERVAL: i64 = 60;
+
+/// One upgrade step, from `from_version` to `from_version + 1`.
+///
+/// `migrate` edits the TOML value tree in place; it does not need to touch
+/// `schema_version`, which `migrate_table_to_current` updates after each
+/// step.
+struct Migration {
+    from_version: i64,
+    description: &'static str,
+    migrate: fn(&mut toml::map::Map<String, Value>) -> Result<(), ThisProjectError>,
+}
+
+/// All migrations, in order. There must be exactly one for each version
+/// from 1 up to `CURRENT_SCHEMA_VERSION - 1`.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from_version: 1,
+        description: "1 -> 2: add gpg_key_public (empty), sync_interval and updated_at_timestamp",
+        migrate: migrate_v1_to_v2,
+    },
+];
+
+/// Version 1 -> 2: adds the fields that version 1 files do not have.
+///
+/// The IP address lists stay absent (they are optional). `gpg_key_public`
+/// becomes an empty string to be filled in by the collaborator,
+/// `sync_interval` becomes `MIGRATED_SYNC_INTERVAL`, and
+/// `updated_at_timestamp` becomes the time of the migration.
+fn migrate_v1_to_v2(table: &mut toml::map::Map<String, Value>) -> Result<(), ThisProjectError> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| ThisProjectError::SchemaVersionError(format!("System clock is before 1970: {}", e)))?
+        .as_secs();
+
+    table.entry("gpg_key_public").or_insert_with(|| Value::String(String::new()));
+    table.entry("sync_interval").or_insert(Value::Integer(MIGRATED_SYNC_INTERVAL));
+    table.entry("updated_at_timestamp").or_insert(Value::Integer(now as i64));
+    Ok(())
+}
+
+/// Returns the schema version of a TOML value tree and whether it was
+/// recorded in the file.
+///
+/// Without a `schema_version` key, a file with any of the keys added in
+/// version 2 is version 2, and anything else is version 1.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::SchemaVersionError` if `schema_version` is
+/// not an integer, or is below 1.
+fn detect_schema_version(table: &toml::map::Map<String, Value>) -> Result<(i64, bool), ThisProjectError> {
+    match table.get(SCHEMA_VERSION_KEY) {
+        Some(Value::Integer(version)) if *version >= 1 => Ok((*version, true)),
+        Some(other) => Err(ThisProjectError::SchemaVersionError(format!(
+            "Invalid {}: expected an integer of at least 1, found {}",
+            SCHEMA_VERSION_KEY, other
+        ))),
+        None => {
+            let version_2_keys = ["ipv4_addresses", "ipv6_addresses", "gpg_key_public", "sync_interval", "updated_at_timestamp"];
+            if version_2_keys.iter().any(|key| table.contains_key(*key)) {
+                Ok((2, false))
+            } else {
+                Ok((1, false))
+            }
+        }
+    }
+}
+
+/// What `migrate_table_to_current` did to one value tree.
+#[derive(Debug, Clone, PartialEq)]
+struct MigrationPlan {
+    /// The version the tree had (recorded or inferred).
+    from_version: i64,
+    /// Whether the tree had a `schema_version` key.
+    had_version_key: bool,
+    /// Descriptions of the migrations applied, in order.
+    steps: Vec<&'static str>,
+    /// Keys that rewriting the file would drop: keys this version does not
+    /// know, and address lists that are not arrays.
+    dropped_keys: Vec<String>,
+    /// Whether the file has comments, which rewriting would drop.
+    has_comments: bool,
+}
+
+impl MigrationPlan {
+    /// Whether the file needs rewriting: it was migrated, or it is current
+    /// but does not record its version yet.
+    fn needs_write(&self) -> bool {
+        !self.steps.is_empty() || !self.had_version_key
+    }
+
+    /// Whether rewriting the file would lose anything it holds.
+    fn loses_data(&self) -> bool {
+        !self.dropped_keys.is_empty() || self.has_comments
+    }
+}
+
+/// Upgrades a TOML value tree step by step to `CURRENT_SCHEMA_VERSION`,
+/// and sets its `schema_version` key.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::SchemaVersionError` if the version is newer
+/// than this program understands, or if a migration step is missing; the
+/// error of a failing migration step is returned as is. The table may be
+/// partly migrated after an error.
+fn migrate_table_to_current(table: &mut toml::map::Map<String, Value>) -> Result<MigrationPlan, ThisProjectError> {
+    let (from_version, had_version_key) = detect_schema_version(table)?;
+    if from_version > CURRENT_SCHEMA_VERSION {
+        return Err(ThisProjectError::SchemaVersionError(format!(
+            "Schema version {} is newer than the newest version this program knows ({})",
+            from_version, CURRENT_SCHEMA_VERSION
+        )));
+    }
+
+    let mut steps = Vec::new();
+    let mut version = from_version;
+    while version < CURRENT_SCHEMA_VERSION {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|migration| migration.from_version == version)
+            .ok_or_else(|| ThisProjectError::SchemaVersionError(format!("No migration from schema version {}", version)))?;
+        (migration.migrate)(table)?;
+        steps.push(migration.description);
+        version += 1;
+    }
+
+    table.insert(SCHEMA_VERSION_KEY.to_string(), Value::Integer(CURRENT_SCHEMA_VERSION));
+    Ok(MigrationPlan { from_version, had_version_key, steps, dropped_keys: dropped_keys(table), has_comments: false })
+}
+
+// Helper function: the keys of a (migrated) table that
+// `serialize_collaborator_to_toml` would not write back
+fn dropped_keys(table: &toml::map::Map<String, Value>) -> Vec<String> {
+    table
+        .iter()
+        .filter(|(key, value)| match key.as_str() {
+            "ipv4_addresses" | "ipv6_addresses" => !value.is_array(),
+            key => !SERIALIZED_KEYS.contains(&key),
+        })
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+/// Returns `true` if TOML text has a comment: a `#` outside of any string.
+///
+/// Only strings need tracking, as TOML allows `#` nowhere else outside a
+/// comment.
+fn has_toml_comment(toml_string: &str) -> bool {
+    let mut rest = toml_string;
+    while let Some(c) = rest.chars().next() {
+        let (delimiter, escapes) = match c {
+            '#' => return true,
+            '"' => (if rest.starts_with("\"\"\"") { "\"\"\"" } else { "\"" }, true),
+            '\'' => (if rest.starts_with("'''") { "'''" } else { "'" }, false),
+            _ => {
+                rest = &rest[c.len_utf8()..];
+                continue;
+            }
+        };
+
+        // Skip to the end of the string
+        rest = &rest[delimiter.len()..];
+        loop {
+            if rest.is_empty() {
+                return false;
+            }
+            if escapes && rest.starts_with('\\') {
+                // Skip the backslash and the escaped character
+                let escaped_len = rest[1..].chars().next().map_or(0, char::len_utf8);
+                rest = &rest[1 + escaped_len..];
+            } else if rest.starts_with(delimiter) {
+                // A multi-line string may end in up to two extra quotes
+                rest = &rest[delimiter.len()..];
+                if delimiter.len() == 3 {
+                    let quote = delimiter.chars().next().unwrap_or('"');
+                    rest = rest.trim_start_matches(quote);
+                }
+                break;
+            } else {
+                let c = rest.chars().next().map_or(1, char::len_utf8);
+                rest = &rest[c..];
+            }
+        }
+    }
+    false
+}
+
+/// Builds a `CollaboratorTomlData` from a (current version) TOML table.
+///
+/// Returns the first missing or malformed field as the error.
+fn collaborator_from_toml_table(table: &toml::map::Map<String, Value>) -> Result<CollaboratorTomlData, ThisProjectError> {
+
+    // Extract user_name
+    let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+    };
+
+    // Extract user_salt_list
+    let user_salt_list = if let Some(Value::Array(arr)) = table.get("user_salt_list") {
+        arr.iter()
+            .map(|val| {
+                if let Value::String(s) = val {
+                    u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                        .map_err(ThisProjectError::ParseIntError)
+                } else {
+                    Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                }
+            })
+            .collect::<Result<Vec<u128>, ThisProjectError>>()?
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_salt_list".into()));
+    };
+
+    // Extract ipv4_addresses
+    let ipv4_addresses = extract_ipv4_addresses(table, "ipv4_addresses")?;
+
+    // Extract ipv6_addresses
+    let ipv6_addresses = extract_ipv6_addresses(table, "ipv6_addresses")?;
+
+    // Extract gpg_key_public
+    let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+    };
+
+    // Extract sync_interval
+    let sync_interval = extract_u64(table, "sync_interval")?;
+
+    // Extract updated_at_timestamp
+    let updated_at_timestamp = extract_u64(table, "updated_at_timestamp")?;
+
+    Ok(CollaboratorTomlData {
+        user_name,
+        user_salt_list,
+        ipv4_addresses,
+        ipv6_addresses,
+        gpg_key_public,
+        sync_interval,
+        updated_at_timestamp,
+    })
+}
+
+fn extract_ipv4_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv4Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv4Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+fn extract_ipv6_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv6Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv6Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+// Helper function: TOML text -> migrated CollaboratorTomlData and what was done
+fn migrate_collaborator_toml(toml_string: &str) -> Result<(CollaboratorTomlData, MigrationPlan), ThisProjectError> {
+    let mut table = match toml::from_str::<Value>(toml_string) {
+        Ok(Value::Table(table)) => table,
+        Ok(_) => return Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure: Expected a table".into())),
+        Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(e.to_string())),
+    };
+    let mut plan = migrate_table_to_current(&mut table)?;
+    plan.has_comments = has_toml_comment(toml_string);
+    let collaborator = collaborator_from_toml_table(&table)?;
+    Ok((collaborator, plan))
+}
+
+/// Toml Deserialization: Reads collaborator setup data from TOML files in a
+/// specified directory, upgrading older schema versions in memory.
+///
+/// Every `*.toml` file in `project_graph_data/collaborator_files_address_book`
+/// goes through `migrate_table_to_current` before its fields are extracted,
+/// so version 1 files load as complete `CollaboratorTomlData`. A file that
+/// cannot be migrated or has a bad field is skipped and its error is added
+/// to the error vector.
+/// Files are not changed on disk; use `migrate_collaborator_directory` for
+/// that.
+///
+/// # Returns
+///
+/// Returns a `Result` containing:
+/// - `Ok`: A tuple with:
+///     - A vector of successfully parsed `CollaboratorTomlData` instances.
+///     - A vector of any `ThisProjectError` encountered during parsing.
+/// - `Err`: A `ThisProjectError` if there was an error reading the directory or any file.
+fn read_a_collaborator_setup_toml() -> Result<(Vec<CollaboratorTomlData>, Vec<ThisProjectError>), ThisProjectError> {
+    let mut collaborators = Vec::new();
+    let mut errors = Vec::new();
+    let dir_path = Path::new("project_graph_data/collaborator_files_address_book");
+
+    for entry in fs::read_dir(dir_path)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
+            let toml_string = fs::read_to_string(&path)?;
+
+            match migrate_collaborator_toml(&toml_string) {
+                Ok((collaborator, _)) => collaborators.push(collaborator),
+                Err(e) => errors.push(e),
+            }
+        }
+    }
+
+    Ok((collaborators, errors))
+}
+
+/// The result of migrating one file in `migrate_collaborator_directory`.
+#[derive(Debug)]
+enum FileMigrationStatus {
+    /// Already at `CURRENT_SCHEMA_VERSION` with a `schema_version` key.
+    UpToDate,
+    /// Would be rewritten (dry run only).
+    WouldMigrate(MigrationPlan),
+    /// Rewritten in place.
+    Migrated(MigrationPlan),
+    /// Needs migrating, but rewriting would drop keys or comments (see
+    /// `MigrationPlan::loses_data`); the file is unchanged. Move what would
+    /// be lost out of the file (or delete it) and run again.
+    Refused(MigrationPlan),
+    /// Could not be read, migrated or written; the file is unchanged.
+    Failed(ThisProjectError),
+}
+
+/// Per-file outcome of `migrate_collaborator_directory`, in file name order.
+#[derive(Debug)]
+struct DirectoryMigrationReport {
+    dry_run: bool,
+    files: Vec<(PathBuf, FileMigrationStatus)>,
+}
+
+impl DirectoryMigrationReport {
+    fn failed_count(&self) -> usize {
+        self.files.iter().filter(|(_, status)| matches!(status, FileMigrationStatus::Failed(_))).count()
+    }
+
+    fn refused_count(&self) -> usize {
+        self.files.iter().filter(|(_, status)| matches!(status, FileMigrationStatus::Refused(_))).count()
+    }
+}
+
+impl fmt::Display for DirectoryMigrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Schema migration to version {}{}:",
+            CURRENT_SCHEMA_VERSION,
+            if self.dry_run { " (dry run, nothing written)" } else { "" }
+        )?;
+        for (path, status) in &self.files {
+            match status {
+                FileMigrationStatus::UpToDate => writeln!(f, "  {}: up to date", path.display())?,
+                FileMigrationStatus::WouldMigrate(plan) | FileMigrationStatus::Migrated(plan) => {
+                    let verb = if self.dry_run { "would migrate" } else { "migrated" };
+                    writeln!(f, "  {}: {} from version {}", path.display(), verb, plan.from_version)?;
+                    if !plan.had_version_key {
+                        writeln!(f, "      record {} = {}", SCHEMA_VERSION_KEY, CURRENT_SCHEMA_VERSION)?;
+                    }
+                    for step in &plan.steps {
+                        writeln!(f, "      {}", step)?;
+                    }
+                }
+                FileMigrationStatus::Refused(plan) => {
+                    writeln!(
+                        f,
+                        "  {}: NOT migrated from version {}: rewriting would lose data",
+                        path.display(),
+                        plan.from_version
+                    )?;
+                    if !plan.dropped_keys.is_empty() {
+                        writeln!(f, "      keys not written by this version: {}", plan.dropped_keys.join(", "))?;
+                    }
+                    if plan.has_comments {
+                        writeln!(f, "      comments")?;
+                    }
+                }
+                FileMigrationStatus::Failed(e) => writeln!(f, "  {}: FAILED: {}", path.display(), e)?,
+            }
+        }
+        write!(
+            f,
+            "{} file(s), {} refused, {} failed",
+            self.files.len(),
+            self.refused_count(),
+            self.failed_count()
+        )
+    }
+}
+
+/// Migrates every `.toml` file in a directory to `CURRENT_SCHEMA_VERSION`,
+/// in place.
+///
+/// Each file is read, migrated (`migrate_table_to_current`), checked by
+/// building a `CollaboratorTomlData` from it, and written back with
+/// `serialize_collaborator_to_toml`. Files that are already current are not
+/// touched. A file that rewriting would lose data from (unknown keys,
+/// comments) is not touched either, but reported as
+/// `FileMigrationStatus::Refused` with what would be lost. A file is written to `{file}.migrating` first and then renamed
+/// over the original, so an interrupted run never leaves a half-written
+/// file. One file failing does not stop the others.
+///
+/// With `dry_run`, nothing is written; the report says what would change.
+///
+/// # Error Handling
+///
+/// Returns `Err` only if the directory cannot be listed; per-file problems
+/// are reported as `FileMigrationStatus::Failed`.
+///
+/// # use with
+/// let report = migrate_collaborator_directory(Path::new("project_graph_data/collaborator_files_address_book"), true)?;
+/// println!("{}", report);
+fn migrate_collaborator_directory(dir_path: &Path, dry_run: bool) -> Result<DirectoryMigrationReport, ThisProjectError> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir_path)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut files = Vec::new();
+    for path in paths {
+        let status = match migrate_one_file(&path, dry_run) {
+            Ok(status) => status,
+            Err(e) => FileMigrationStatus::Failed(e),
+        };
+        files.push((path, status));
+    }
+
+    Ok(DirectoryMigrationReport { dry_run, files })
+}
+
+// Helper function to migrate (or plan the migration of) one file
+fn migrate_one_file(path: &Path, dry_run: bool) -> Result<FileMigrationStatus, ThisProjectError> {
+    let toml_string = fs::read_to_string(path)?;
+    let (collaborator, plan) = migrate_collaborator_toml(&toml_string)?;
+
+    if !plan.needs_write() {
+        return Ok(FileMigrationStatus::UpToDate);
+    }
+    if plan.loses_data() {
+        return Ok(FileMigrationStatus::Refused(plan));
+    }
+    if dry_run {
+        return Ok(FileMigrationStatus::WouldMigrate(plan));
+    }
+
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".migrating");
+    let temp_path = PathBuf::from(temp_path);
+
+    let new_toml_string = serialize_collaborator_to_toml(&collaborator)?;
+    write_toml_to_file(&temp_path.to_string_lossy(), &new_toml_string)?;
+    if let Err(e) = fs::rename(&temp_path, path) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(ThisProjectError::IoError(e));
+    }
+    Ok(FileMigrationStatus::Migrated(plan))
+}
+
+/// Serializes a `CollaboratorTomlData` struct into a TOML-formatted string.
+///
+/// Writes `schema_version` first, then one `key = value` line per field in
+/// struct order (the keys in `SERIALIZED_KEYS`); salts are `0x` hex strings,
+/// an IP list that is `None` is left out, and strings are escaped.
+fn serialize_collaborator_to_toml(collaborator: &CollaboratorTomlData) -> Result<String, ThisProjectError> {
+    let mut toml_string = String::new();
+
+    // Add schema_version
+    toml_string.push_str(&format!("{} = {}\n", SCHEMA_VERSION_KEY, CURRENT_SCHEMA_VERSION));
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", escape_toml_basic_string(&collaborator.user_name)));
+
+    // Add user_salt_list
+    toml_string.push_str("user_salt_list = [\n");
+    for salt in &collaborator.user_salt_list {
+        toml_string.push_str(&format!("    \"0x{:x}\",\n", salt));
+    }
+    toml_string.push_str("]\n");
+
+    // Add ipv4_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv4_addresses", &collaborator.ipv4_addresses)?;
+
+    // Add ipv6_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv6_addresses", &collaborator.ipv6_addresses)?;
+
+    // Add gpg_key_public
+    toml_string.push_str(&format!("gpg_key_public = \"{}\"\n", escape_toml_basic_string(&collaborator.gpg_key_public)));
+
+    // Add sync_interval
+    toml_string.push_str(&format!("sync_interval = {}\n", collaborator.sync_interval));
+
+    // Add updated_at_timestamp
+    toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp));
+
+    Ok(toml_string)
+}
+
+// Helper function to escape a value for a TOML basic string ("...")
+fn escape_toml_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Helper function to serialize IP addresses to TOML array format
+fn serialize_ip_addresses<T: std::fmt::Display>(
+    toml_string: &mut String,
+    key: &str,
+    addresses: &Option<Vec<T>>
+) -> Result<(), ThisProjectError> {
+    if let Some(addr_vec) = addresses {
+        toml_string.push_str(&format!("{} = [\n", key));
+        for addr in addr_vec {
+            toml_string.push_str(&format!("    \"{}\",\n", addr));
+        }
+        toml_string.push_str("]\n");
+    }
+    Ok(()) // Return Ok(()) if the addresses field is None
+}
+
+// Function to write a TOML string to a file
+fn write_toml_to_file(file_path: &str, toml_string: &str) -> Result<(), ThisProjectError> {
+    // Attempt to create the file.
+    let mut file = match File::create(file_path) {
+        Ok(file) => file,
+        Err(e) => return Err(ThisProjectError::IoError(e)),
+    };
+
+    // Attempt to write to the file.
+    if let Err(e) = file.write_all(toml_string.as_bytes()) {
+        return Err(ThisProjectError::IoError(e));
+    }
+
+    // Everything successful!
+    Ok(())
+}
+
+fn main() {
+    let dir_path = Path::new("project_graph_data/collaborator_files_address_book");
+
+    // Dry run first: report what would change
+    match migrate_collaborator_directory(dir_path, true) {
+        Ok(report) => println!("{}\n", report),
+        Err(e) => {
+            println!("Error reading {}: {}", dir_path.display(), e);
+            return;
+        }
+    }
+
+    // Then migrate in place
+    match migrate_collaborator_directory(dir_path, false) {
+        Ok(report) => println!("{}\n", report),
+        Err(e) => println!("Error migrating {}: {}", dir_path.display(), e),
+    }
+
+    // Every file now loads at the current version
+    match read_a_collaborator_setup_toml() {
+        Ok((collaborators, errors)) => {
+            if !errors.is_empty() {
+                println!("Errors encountered:");
+                for err in errors {
+                    println!("{}", err);
+                }
+            }
+
+            println!("Collaborators:");
+            for collaborator in collaborators {
+                println!("{:#?}", collaborator);
+            }
+        }
+        Err(e) => {
+            println!("Error reading TOML files: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VERSION_1_FILE: &str = "user_name = \"alice\"\nuser_salt_list = [\"0x11\"]\n";
+
+    #[test]
+    fn plain_version_1_file_migrates_without_loss() {
+        let (collaborator, plan) = migrate_collaborator_toml(VERSION_1_FILE).unwrap();
+        assert_eq!(collaborator.user_name, "alice");
+        assert_eq!(plan.from_version, 1);
+        assert!(plan.needs_write());
+        assert!(!plan.loses_data());
+    }
+
+    #[test]
+    fn unknown_keys_and_comments_are_reported() {
+        let toml_string = format!("# alice's laptop\n{}nickname = \"al\"\n[extra]\nx = 1\n", VERSION_1_FILE);
+        let (_, plan) = migrate_collaborator_toml(&toml_string).unwrap();
+        assert_eq!(plan.dropped_keys, vec!["extra".to_string(), "nickname".to_string()]);
+        assert!(plan.has_comments);
+        assert!(plan.loses_data());
+    }
+
+    #[test]
+    fn address_list_that_is_not_an_array_is_reported() {
+        let toml_string = format!(
+            "{}ipv4_addresses = \"10.0.0.1\"\ngpg_key_public = \"\"\nsync_interval = 60\nupdated_at_timestamp = 1\n",
+            VERSION_1_FILE
+        );
+        let (_, plan) = migrate_collaborator_toml(&toml_string).unwrap();
+        assert_eq!(plan.dropped_keys, vec!["ipv4_addresses".to_string()]);
+    }
+
+    #[test]
+    fn hash_inside_strings_is_not_a_comment() {
+        assert!(!has_toml_comment("a = \"#1\"\nb = '#2'\nc = \"\"\"\n#3\"\"\"\"\nd = '''#4'''\ne = \"\\\"#5\"\n"));
+        assert!(has_toml_comment("a = \"#1\" # note\n"));
+        assert!(has_toml_comment("a = '''x''' # note\n"));
+    }
+
+    #[test]
+    fn file_that_would_lose_data_is_left_unchanged() {
+        let dir = std::env::temp_dir().join(format!("schema_migration_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("alice__collaborator.toml");
+        let toml_string = format!("{}# keep me\n", VERSION_1_FILE);
+        fs::write(&path, &toml_string).unwrap();
+
+        let report = migrate_collaborator_directory(&dir, false).unwrap();
+        assert!(matches!(report.files[0].1, FileMigrationStatus::Refused(_)));
+        assert_eq!(fs::read_to_string(&path).unwrap(), toml_string);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,1556 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::ffi::OsStr;
+use toml::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    SignatureError(String),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::SignatureError(err) => write!(f, "Signature Error: {}", err),
+        }
+    }
+}
+
+/// Result of checking the detached signature of one collaborator file.
+#[derive(Debug, Clone, PartialEq)]
+enum SignatureStatus {
+    /// Signatures were not checked (`SignatureCheck::Skip`).
+    NotChecked,
+    /// There is no `{file}.sig` next to the collaborator file.
+    Unsigned,
+    /// The signature matches the canonical bytes and the collaborator's key.
+    Valid,
+    /// There is a signature, but it does not verify; the reason says why.
+    Invalid(String),
+}
+
+impl fmt::Display for SignatureStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SignatureStatus::NotChecked => write!(f, "Not checked"),
+            SignatureStatus::Unsigned => write!(f, "Unsigned"),
+            SignatureStatus::Valid => write!(f, "Valid"),
+            SignatureStatus::Invalid(reason) => write!(f, "Invalid ({})", reason),
+        }
+    }
+}
+
+/// Ed25519 public keys the caller trusts, by `user_name`.
+///
+/// Signatures are only ever checked against these keys, never against the
+/// key a collaborator file carries about itself. See `read_trusted_keys`.
+type TrustedKeys = HashMap<String, [u8; 32]>;
+
+/// Collaborators read from the address book, each with its signature status.
+type SignedCollaborators = Vec<(CollaboratorTomlData, SignatureStatus)>;
+
+/// How `read_a_collaborator_setup_toml` handles detached signatures.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SignatureCheck {
+    /// Do not look for `.sig` files.
+    Skip,
+    /// Check every collaborator and report its `SignatureStatus`.
+    Report,
+    /// Check every collaborator; only collaborators with a `Valid` signature
+    /// are returned, the others are reported in the error vector.
+    RequireValid,
+}
+
+/// Toml Deserialization: Reads collaborator setup data from TOML files in a
+/// specified directory, and checks each file's detached signature.
+///
+/// Every `*.toml` file in `project_graph_data/collaborator_files_address_book`
+/// is parsed field by field; a file with a missing or malformed field is
+/// skipped and reported in the error vector.
+///
+/// # Detached Signatures
+///
+/// For a file `project_graph_data/collaborator_files_address_book/alice__collaborator.toml`
+/// the signature is looked for at
+/// `project_graph_data/collaborator_files_address_book/alice__collaborator.toml.sig`.
+///
+/// The signature is an Ed25519 signature (RFC 8032) made with the
+/// collaborator's own key, over the canonical TOML bytes of the collaborator
+/// (`serialize_collaborator_to_canonical_toml`), not over the file bytes.
+/// Reformatting a file or adding comments therefore does not break its
+/// signature, but changing any value does.
+///
+/// The signature is verified against the key pinned for the collaborator's
+/// `user_name` in `trusted_keys`, which the caller obtained out of band (see
+/// `read_trusted_keys`). The `gpg_key_public` in the file is not trusted: a
+/// file can name any key, so verifying against it would only show that the
+/// file is consistent with itself. The `.sig` file holds the 64-byte
+/// signature, either raw or as hex or base64 text (see
+/// `parse_ed25519_signature`).
+///
+/// # Returns
+///
+/// Returns a `Result` containing:
+/// - `Ok`: A tuple with:
+///     - A vector of successfully parsed `CollaboratorTomlData` instances,
+///       each with its `SignatureStatus`.
+///     - A vector of any `ThisProjectError` encountered during parsing (and,
+///       with `SignatureCheck::RequireValid`, one `SignatureError` for each
+///       collaborator refused because its signature is missing or invalid).
+/// - `Err`: A `ThisProjectError` if there was an error reading the directory
+///   or a collaborator file. An unreadable `.sig` file does not stop the
+///   load; it makes that collaborator's signature `Invalid`.
+fn read_a_collaborator_setup_toml(
+    signature_check: SignatureCheck,
+    trusted_keys: &TrustedKeys,
+) -> Result<(SignedCollaborators, Vec<ThisProjectError>), ThisProjectError> {
+    let mut collaborators = Vec::new();
+    let mut errors = Vec::new();
+    let dir_path = Path::new("project_graph_data/collaborator_files_address_book");
+
+    for entry in fs::read_dir(dir_path)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
+            let toml_string = fs::read_to_string(&path)?;
+
+            match toml::from_str::<Value>(&toml_string) {
+                Ok(toml_value) => {
+                    if let Value::Table(table) = toml_value {
+                        // Extract user_name
+                        let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+                            s.clone()
+                        } else {
+                            errors.push(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+                            continue;
+                        };
+
+                        // Extract user_salt_list
+                        let user_salt_list = if let Some(Value::Array(arr)) = table.get("user_salt_list") {
+                            arr.iter()
+                                .map(|val| {
+                                    if let Value::String(s) = val {
+                                        u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                                            .map_err(ThisProjectError::ParseIntError)
+                                    } else {
+                                        Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                                    }
+                                })
+                                .collect::<Result<Vec<u128>, ThisProjectError>>()?
+                        } else {
+                            errors.push(ThisProjectError::TomlVanillaDeserialStrError("Missing user_salt_list".into()));
+                            continue;
+                        };
+
+                        // Extract ipv4_addresses
+                        let ipv4_addresses = extract_ipv4_addresses(&table, "ipv4_addresses", &mut errors)?;
+
+                        // Extract ipv6_addresses
+                        let ipv6_addresses = extract_ipv6_addresses(&table, "ipv6_addresses", &mut errors)?;
+
+                        // Extract gpg_key_public
+                        let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+                            s.clone()
+                        } else {
+                            errors.push(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+                            continue;
+                        };
+
+                        // Extract sync_interval
+                        let sync_interval = extract_u64(&table, "sync_interval", &mut errors)?;
+
+                        // Extract updated_at_timestamp
+                        let updated_at_timestamp = extract_u64(&table, "updated_at_timestamp", &mut errors)?;
+
+                        // Create CollaboratorTomlData instance
+                        let collaborator = CollaboratorTomlData {
+                            user_name,
+                            user_salt_list,
+                            ipv4_addresses,
+                            ipv6_addresses,
+                            gpg_key_public,
+                            sync_interval,
+                            updated_at_timestamp,
+                        };
+
+                        // Check the detached signature
+                        let status = if signature_check == SignatureCheck::Skip {
+                            SignatureStatus::NotChecked
+                        } else {
+                            check_detached_signature(&path, &collaborator, trusted_keys)
+                        };
+
+                        if signature_check == SignatureCheck::RequireValid && status != SignatureStatus::Valid {
+                            errors.push(ThisProjectError::SignatureError(format!(
+                                "Refusing collaborator {} from {}: signature {}",
+                                collaborator.user_name,
+                                path.display(),
+                                status
+                            )));
+                            continue;
+                        }
+
+                        collaborators.push((collaborator, status));
+                    } else {
+                        errors.push(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure".into()));
+                    }
+                }
+                Err(e) => {
+                    errors.push(ThisProjectError::TomlVanillaDeserialStrError(e.to_string()));
+                }
+            }
+        }
+    }
+
+    Ok((collaborators, errors))
+}
+
+// Helper function to extract and parse IPv4 addresses from a toml::Value::Table
+fn extract_ipv4_addresses(
+    table: &toml::map::Map<String, Value>,
+    key: &str,
+    errors: &mut Vec<ThisProjectError>
+) -> Result<Option<Vec<Ipv4Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new(); // Create an empty vector to store addresses
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv4Addr>() {
+                    Ok(ip) => addresses.push(ip), // Push successful IP address
+                    Err(e) => errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() { // If no valid addresses were found
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None) // Return None if the key is not present
+    }
+}
+
+// Helper function to extract and parse IPv6 addresses from a toml::Value::Table
+fn extract_ipv6_addresses(table: &toml::map::Map<String, Value>, key: &str, errors: &mut Vec<ThisProjectError>) -> Result<Option<Vec<Ipv6Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new(); // Create an empty vector to store addresses
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv6Addr>() {
+                    Ok(ip) => addresses.push(ip), // Push successful IP address
+                    Err(e) => errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() { // If no valid addresses were found
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None) // Return None if the key is not present
+    }
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str, errors: &mut Vec<ThisProjectError>) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)));
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        errors.push(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)));
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+/// Checks the detached signature `{toml_path}.sig` of one collaborator file
+/// against the key pinned for its `user_name` in `trusted_keys`.
+///
+/// A missing `.sig` file gives `SignatureStatus::Unsigned`. Everything else
+/// that keeps an existing signature from verifying gives
+/// `SignatureStatus::Invalid` with the reason:
+/// - the `.sig` file cannot be read,
+/// - there is no trusted key for the collaborator,
+/// - `gpg_key_public` holds an Ed25519 key other than the trusted one,
+/// - the signature is malformed, or does not match the key and the data.
+fn check_detached_signature(
+    toml_path: &Path,
+    collaborator: &CollaboratorTomlData,
+    trusted_keys: &TrustedKeys,
+) -> SignatureStatus {
+    let mut sig_path = toml_path.as_os_str().to_owned();
+    sig_path.push(".sig");
+    let sig_path = Path::new(&sig_path);
+
+    if !sig_path.exists() {
+        return SignatureStatus::Unsigned;
+    }
+    let sig_bytes = match fs::read(sig_path) {
+        Ok(sig_bytes) => sig_bytes,
+        Err(e) => return SignatureStatus::Invalid(format!("cannot read {}: {}", sig_path.display(), e)),
+    };
+
+    let public_key = match trusted_keys.get(&collaborator.user_name) {
+        Some(public_key) => public_key,
+        None => return SignatureStatus::Invalid(format!("no trusted key for {}", collaborator.user_name)),
+    };
+    // A file announcing a different key than the pinned one has been
+    // re-keyed (or tampered with); the pin has to be updated first
+    if let Ok(file_key) = parse_ed25519_public_key(&collaborator.gpg_key_public) {
+        if &file_key != public_key {
+            return SignatureStatus::Invalid("gpg_key_public does not match the trusted key".into());
+        }
+    }
+    let signature = match parse_ed25519_signature(&sig_bytes) {
+        Ok(signature) => signature,
+        Err(e) => return SignatureStatus::Invalid(e.to_string()),
+    };
+
+    let canonical = serialize_collaborator_to_canonical_toml(collaborator);
+    if ed25519_verify(public_key, canonical.as_bytes(), &signature) {
+        SignatureStatus::Valid
+    } else {
+        SignatureStatus::Invalid("signature does not match key and data".into())
+    }
+}
+
+/// Reads the trusted (pinned) Ed25519 keys from a TOML file with one
+/// `user_name = "key"` entry per collaborator, e.g.
+///
+/// ```toml
+/// alice = "ed25519:d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+/// bob = """
+/// -----BEGIN PGP PUBLIC KEY BLOCK-----
+/// ...
+/// -----END PGP PUBLIC KEY BLOCK-----
+/// """
+/// ```
+///
+/// This file is kept by the person running the loader and is never part of
+/// the synced address book. Each key may take any form accepted by
+/// `parse_ed25519_public_key`.
+///
+/// # Error Handling
+///
+/// Returns `Err` if the file cannot be read or parsed, or if any entry is
+/// not a string holding an Ed25519 key; the error names the entry.
+fn read_trusted_keys(path: &Path) -> Result<TrustedKeys, ThisProjectError> {
+    let toml_string = fs::read_to_string(path)?;
+    let table = toml::from_str::<toml::map::Map<String, Value>>(&toml_string)
+        .map_err(|e| ThisProjectError::TomlVanillaDeserialStrError(format!("{}: {}", path.display(), e)))?;
+
+    let mut trusted_keys = TrustedKeys::new();
+    for (user_name, value) in &table {
+        let key_text = value.as_str().ok_or_else(|| {
+            ThisProjectError::SignatureError(format!("{}: {}: expected a key string", path.display(), user_name))
+        })?;
+        let public_key = parse_ed25519_public_key(key_text)
+            .map_err(|e| ThisProjectError::SignatureError(format!("{}: {}: {}", path.display(), user_name, e)))?;
+        trusted_keys.insert(user_name.clone(), public_key);
+    }
+    Ok(trusted_keys)
+}
+
+/// Reads an Ed25519 public key, as found in `gpg_key_public` or in the
+/// trusted keys file.
+///
+/// Accepted forms (surrounding whitespace is ignored):
+///
+/// - 64 hex digits, with an optional `ed25519:` prefix, e.g.
+///   `"ed25519:d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"`
+/// - base64 of the 32 key bytes, e.g. `"11qYAYKxCrfVS/7TyWQHOg7hcvPapiMlrwIaaPcHURo="`
+/// - an OpenPGP ASCII-armored public key (as in `armored_public_key_main.rs`)
+///   whose primary key is Ed25519: a version 4 EdDSA key (GnuPG's
+///   `ed25519`), or a version 4 or 6 Ed25519 key (RFC 9580)
+fn parse_ed25519_public_key(gpg_key_public: &str) -> Result<[u8; 32], ThisProjectError> {
+    let text = gpg_key_public.trim();
+    if text.starts_with(ARMOR_BEGIN) {
+        return ed25519_key_from_armor(text);
+    }
+    let text = text.strip_prefix("ed25519:").unwrap_or(text).trim();
+    let bytes = decode_hex_or_base64(text).ok_or_else(|| {
+        ThisProjectError::SignatureError("gpg_key_public does not hold an Ed25519 public key (hex, base64 or armor)".into())
+    })?;
+    bytes.try_into().map_err(|_| {
+        ThisProjectError::SignatureError("gpg_key_public: an Ed25519 public key must be 32 bytes".into())
+    })
+}
+
+const ARMOR_BEGIN: &str = "-----BEGIN PGP PUBLIC KEY BLOCK-----";
+const ARMOR_END: &str = "-----END PGP PUBLIC KEY BLOCK-----";
+
+/// OpenPGP public-key algorithm IDs (RFC 9580, section 9.1).
+const OPENPGP_ALGORITHM_EDDSA_LEGACY: u8 = 22;
+const OPENPGP_ALGORITHM_ED25519: u8 = 27;
+
+/// Curve OID of Ed25519 in version 4 EdDSA keys (1.3.6.1.4.1.11591.15.1).
+const ED25519_LEGACY_OID: [u8; 9] = [0x2b, 0x06, 0x01, 0x04, 0x01, 0xda, 0x47, 0x0f, 0x01];
+
+/// Takes the Ed25519 key out of the primary public-key packet of an armored
+/// OpenPGP key.
+///
+/// Only the armor, its CRC24 (if present) and the first packet are looked
+/// at; user IDs and self-signatures are not checked, because trust comes
+/// from the caller pinning the key, not from the key block.
+fn ed25519_key_from_armor(armor: &str) -> Result<[u8; 32], ThisProjectError> {
+    let armor_error = |message: &str| ThisProjectError::SignatureError(format!("gpg_key_public armor: {}", message));
+
+    // Body lines sit between the blank line after the headers and the END line
+    let mut lines = armor.lines().map(|line| line.trim_end()).skip(1);
+    for line in lines.by_ref() {
+        if line.is_empty() {
+            break;
+        }
+    }
+    let mut body = String::new();
+    let mut checksum = None;
+    for line in lines.by_ref() {
+        if line == ARMOR_END {
+            break;
+        }
+        match line.strip_prefix('=') {
+            Some(crc) => checksum = Some(crc),
+            None => body.push_str(line),
+        }
+    }
+    let data = decode_base64(&body).ok_or_else(|| armor_error("invalid base64"))?;
+    if let Some(crc) = checksum {
+        let crc = decode_base64(crc).filter(|bytes| bytes.len() == 3).ok_or_else(|| armor_error("invalid checksum line"))?;
+        if crc24(&data) != u32::from_be_bytes([0, crc[0], crc[1], crc[2]]) {
+            return Err(armor_error("checksum mismatch"));
+        }
+    }
+
+    let packet = first_public_key_packet(&data).ok_or_else(|| armor_error("no public-key packet at the start"))?;
+    let truncated = || armor_error("public-key packet is too short");
+    let version = *packet.first().ok_or_else(truncated)?;
+    // Version 4: version, creation time, algorithm. Version 6 adds a
+    // four-octet length of the key material.
+    let algorithm_at = 5;
+    let material_at = match version {
+        4 => 6,
+        6 => 10,
+        _ => return Err(armor_error(&format!("unsupported key version {}", version))),
+    };
+    let algorithm = *packet.get(algorithm_at).ok_or_else(truncated)?;
+    let material = packet.get(material_at..).ok_or_else(truncated)?;
+
+    let key_bytes = match algorithm {
+        OPENPGP_ALGORITHM_ED25519 => material.get(..32),
+        OPENPGP_ALGORITHM_EDDSA_LEGACY if version == 4 => {
+            // OID length and OID, then an MPI of 263 bits: 0x40 and the 32-byte key
+            let oid_len = *material.first().ok_or_else(truncated)? as usize;
+            if material.get(1..1 + oid_len) != Some(&ED25519_LEGACY_OID[..]) {
+                return Err(armor_error("EdDSA key is not on Ed25519"));
+            }
+            let mpi = material.get(1 + oid_len..).ok_or_else(truncated)?;
+            if mpi.get(..3) != Some(&[0x01, 0x07, 0x40][..]) {
+                return Err(armor_error("EdDSA key point is not in native form"));
+            }
+            mpi.get(3..35)
+        }
+        _ => return Err(armor_error(&format!("primary key algorithm {} is not Ed25519", algorithm))),
+    };
+    let mut public_key = [0u8; 32];
+    public_key.copy_from_slice(key_bytes.ok_or_else(truncated)?);
+    Ok(public_key)
+}
+
+// Helper function: body of the first packet if it is a public-key packet
+// (tag 6), in either OpenPGP packet header format
+fn first_public_key_packet(data: &[u8]) -> Option<&[u8]> {
+    let header = *data.first()?;
+    let (tag, start, length) = if header & 0xc0 == 0xc0 {
+        let first = *data.get(1)? as usize;
+        match first {
+            0..=191 => (header & 0x3f, 2, first),
+            192..=223 => (header & 0x3f, 3, ((first - 192) << 8) + *data.get(2)? as usize + 192),
+            255 => (header & 0x3f, 6, u32::from_be_bytes(data.get(2..6)?.try_into().ok()?) as usize),
+            _ => return None,
+        }
+    } else if header & 0x80 != 0 {
+        let length_bytes = [1, 2, 4].get((header & 0x03) as usize)?;
+        let length = data.get(1..1 + length_bytes)?.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        ((header >> 2) & 0x0f, 1 + length_bytes, length)
+    } else {
+        return None;
+    };
+    if tag != 6 {
+        return None;
+    }
+    data.get(start..start.checked_add(length)?)
+}
+
+/// CRC-24 of the armor checksum (RFC 9580, section 6.1).
+fn crc24(data: &[u8]) -> u32 {
+    const CRC24_INIT: u32 = 0xb704ce;
+    const CRC24_POLY: u32 = 0x1864cfb;
+
+    let mut crc = CRC24_INIT;
+    for byte in data {
+        crc ^= (*byte as u32) << 16;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x1000000 != 0 {
+                crc ^= CRC24_POLY;
+            }
+        }
+    }
+    crc & 0xffffff
+}
+
+/// Reads a 64-byte Ed25519 signature from the contents of a `.sig` file:
+/// either the raw 64 bytes, or 128 hex digits, or base64 text.
+fn parse_ed25519_signature(sig_bytes: &[u8]) -> Result<[u8; 64], ThisProjectError> {
+    if sig_bytes.len() == 64 {
+        let mut signature = [0u8; 64];
+        signature.copy_from_slice(sig_bytes);
+        return Ok(signature);
+    }
+    let text = std::str::from_utf8(sig_bytes)
+        .map_err(|_| ThisProjectError::SignatureError("signature file is neither 64 raw bytes nor text".into()))?;
+    let bytes = decode_hex_or_base64(text.trim())
+        .ok_or_else(|| ThisProjectError::SignatureError("signature file is not valid hex or base64".into()))?;
+    bytes.try_into().map_err(|_| ThisProjectError::SignatureError("an Ed25519 signature must be 64 bytes".into()))
+}
+
+// Helper function to decode hex (even length, all hex digits) or else standard base64
+fn decode_hex_or_base64(text: &str) -> Option<Vec<u8>> {
+    if !text.is_empty() && text.len().is_multiple_of(2) && text.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).ok())
+            .collect();
+    }
+    decode_base64(text)
+}
+
+// Helper function to decode standard base64 (RFC 4648), with or without '=' padding
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim_end_matches('=');
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in text.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    // Leftover bits must be padding zeros, and one leftover character is never valid
+    if bits >= 6 || buffer & ((1 << bits) - 1) != 0 {
+        return None;
+    }
+    Some(bytes)
+}
+
+// Helper function to encode bytes as lower-case hex
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Serializes a `CollaboratorTomlData` struct into canonical TOML: the bytes
+/// that are signed and verified.
+///
+/// Fields come in struct order, salts as 32-digit lower-case hex, IP lists
+/// in the order stored and omitted when `None`, and strings escaped, so the
+/// same values always give the same bytes however the file was formatted.
+fn serialize_collaborator_to_canonical_toml(collaborator: &CollaboratorTomlData) -> String {
+    let mut toml_string = String::new();
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", escape_toml_basic_string(&collaborator.user_name)));
+
+    // Add user_salt_list
+    toml_string.push_str("user_salt_list = [\n");
+    for salt in &collaborator.user_salt_list {
+        toml_string.push_str(&format!("    \"0x{:032x}\",\n", salt));
+    }
+    toml_string.push_str("]\n");
+
+    // Add ipv4_addresses
+    serialize_canonical_ip_addresses(&mut toml_string, "ipv4_addresses", &collaborator.ipv4_addresses);
+
+    // Add ipv6_addresses (Ipv6Addr's Display already follows RFC 5952)
+    serialize_canonical_ip_addresses(&mut toml_string, "ipv6_addresses", &collaborator.ipv6_addresses);
+
+    // Add gpg_key_public
+    toml_string.push_str(&format!("gpg_key_public = \"{}\"\n", escape_toml_basic_string(&collaborator.gpg_key_public)));
+
+    // Add sync_interval
+    toml_string.push_str(&format!("sync_interval = {}\n", collaborator.sync_interval));
+
+    // Add updated_at_timestamp
+    toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp));
+
+    toml_string
+}
+
+// Helper function to serialize IP addresses to canonical TOML array format
+fn serialize_canonical_ip_addresses<T: std::fmt::Display>(
+    toml_string: &mut String,
+    key: &str,
+    addresses: &Option<Vec<T>>
+) {
+    if let Some(addr_vec) = addresses {
+        toml_string.push_str(&format!("{} = [\n", key));
+        for addr in addr_vec {
+            toml_string.push_str(&format!("    \"{}\",\n", addr));
+        }
+        toml_string.push_str("]\n");
+    }
+}
+
+// Helper function to escape a value for a TOML basic string ("...")
+fn escape_toml_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Signs the canonical bytes of a collaborator and writes `{toml_path}.sig`
+/// as hex text.
+///
+/// `secret_seed` is the 32-byte Ed25519 private key (the RFC 8032 "seed").
+/// The matching public key (see `ed25519_public_key_from_seed`) must be
+/// pinned for the collaborator in the verifier's trusted keys for the
+/// signature to verify.
+///
+/// # use with
+/// let collaborator = read_one_collaborator_setup_toml("alice")?;
+/// write_detached_signature(Path::new(".../alice__collaborator.toml"), &collaborator, &alice_seed)?;
+fn write_detached_signature(toml_path: &Path, collaborator: &CollaboratorTomlData, secret_seed: &[u8; 32]) -> Result<(), ThisProjectError> {
+    let canonical = serialize_collaborator_to_canonical_toml(collaborator);
+    let signature = ed25519_sign(secret_seed, canonical.as_bytes());
+
+    let mut sig_path = toml_path.as_os_str().to_owned();
+    sig_path.push(".sig");
+    fs::write(Path::new(&sig_path), format!("{}\n", encode_hex(&signature)))?;
+    Ok(())
+}
+
+/*
+Ed25519 (RFC 8032), implemented here without a crypto dependency.
+
+Only what is needed to sign and verify is included. Verification works on
+public data only, so the arithmetic below is not constant-time; signing uses
+the same code and so should only be used for tooling and tests, not on a
+shared machine where timing can be observed.
+*/
+
+/// An element of the field of integers modulo p = 2^255 - 19, stored as five
+/// 51-bit limbs (value = l0 + l1*2^51 + l2*2^102 + l3*2^153 + l4*2^204).
+#[derive(Clone, Copy, Debug)]
+struct FieldElement([u64; 5]);
+
+const LOW_51_BITS: u64 = (1 << 51) - 1;
+
+impl FieldElement {
+    const ZERO: FieldElement = FieldElement([0, 0, 0, 0, 0]);
+    const ONE: FieldElement = FieldElement([1, 0, 0, 0, 0]);
+
+    fn from_u64(value: u64) -> FieldElement {
+        FieldElement([value & LOW_51_BITS, value >> 51, 0, 0, 0])
+    }
+
+    // Reads 255 bits, little-endian; the top bit of byte 31 is ignored
+    fn from_bytes(bytes: &[u8; 32]) -> FieldElement {
+        let load = |i: usize| {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&bytes[i..i + 8]);
+            u64::from_le_bytes(word)
+        };
+        FieldElement([
+            load(0) & LOW_51_BITS,
+            (load(6) >> 3) & LOW_51_BITS,
+            (load(12) >> 6) & LOW_51_BITS,
+            (load(19) >> 1) & LOW_51_BITS,
+            (load(24) >> 12) & LOW_51_BITS,
+        ])
+    }
+
+    // Fully reduced (canonical) little-endian encoding
+    fn to_bytes(self) -> [u8; 32] {
+        let mut limbs = FieldElement::weak_reduce(self.0);
+
+        // limbs now hold a value below 2p; subtract p once if needed
+        let mut q = (limbs[0] + 19) >> 51;
+        q = (limbs[1] + q) >> 51;
+        q = (limbs[2] + q) >> 51;
+        q = (limbs[3] + q) >> 51;
+        q = (limbs[4] + q) >> 51;
+
+        limbs[0] += 19 * q;
+        limbs[1] += limbs[0] >> 51;
+        limbs[0] &= LOW_51_BITS;
+        limbs[2] += limbs[1] >> 51;
+        limbs[1] &= LOW_51_BITS;
+        limbs[3] += limbs[2] >> 51;
+        limbs[2] &= LOW_51_BITS;
+        limbs[4] += limbs[3] >> 51;
+        limbs[3] &= LOW_51_BITS;
+        limbs[4] &= LOW_51_BITS;
+
+        let words = [
+            limbs[0] | (limbs[1] << 51),
+            (limbs[1] >> 13) | (limbs[2] << 38),
+            (limbs[2] >> 26) | (limbs[3] << 25),
+            (limbs[3] >> 39) | (limbs[4] << 12),
+        ];
+        let mut bytes = [0u8; 32];
+        for (i, word) in words.iter().enumerate() {
+            bytes[i * 8..i * 8 + 8].copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    // Carries each limb into the next, so that every limb is about 51 bits
+    fn weak_reduce(mut limbs: [u64; 5]) -> [u64; 5] {
+        let c0 = limbs[0] >> 51;
+        let c1 = limbs[1] >> 51;
+        let c2 = limbs[2] >> 51;
+        let c3 = limbs[3] >> 51;
+        let c4 = limbs[4] >> 51;
+        limbs[0] &= LOW_51_BITS;
+        limbs[1] &= LOW_51_BITS;
+        limbs[2] &= LOW_51_BITS;
+        limbs[3] &= LOW_51_BITS;
+        limbs[4] &= LOW_51_BITS;
+        limbs[0] += c4 * 19; // 2^255 = 19 (mod p)
+        limbs[1] += c0;
+        limbs[2] += c1;
+        limbs[3] += c2;
+        limbs[4] += c3;
+        limbs
+    }
+
+    fn add(self, other: FieldElement) -> FieldElement {
+        let mut limbs = [0u64; 5];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            *limb = self.0[i] + other.0[i];
+        }
+        FieldElement(FieldElement::weak_reduce(limbs))
+    }
+
+    fn sub(self, other: FieldElement) -> FieldElement {
+        // Add 16p first so that no limb goes negative
+        const SIXTEEN_P: [u64; 5] = [
+            36028797018963664, // 16 * (2^51 - 19)
+            36028797018963952, // 16 * (2^51 - 1)
+            36028797018963952,
+            36028797018963952,
+            36028797018963952,
+        ];
+        let mut limbs = [0u64; 5];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            *limb = (self.0[i] + SIXTEEN_P[i]) - other.0[i];
+        }
+        FieldElement(FieldElement::weak_reduce(limbs))
+    }
+
+    fn neg(self) -> FieldElement {
+        FieldElement::ZERO.sub(self)
+    }
+
+    fn mul(self, other: FieldElement) -> FieldElement {
+        let a = self.0;
+        let b = other.0;
+        let m = |x: u64, y: u64| (x as u128) * (y as u128);
+
+        // Products that overflow 2^255 wrap around multiplied by 19
+        let b1_19 = b[1] * 19;
+        let b2_19 = b[2] * 19;
+        let b3_19 = b[3] * 19;
+        let b4_19 = b[4] * 19;
+
+        let c0 = m(a[0], b[0]) + m(a[4], b1_19) + m(a[3], b2_19) + m(a[2], b3_19) + m(a[1], b4_19);
+        let mut c1 = m(a[1], b[0]) + m(a[0], b[1]) + m(a[4], b2_19) + m(a[3], b3_19) + m(a[2], b4_19);
+        let mut c2 = m(a[2], b[0]) + m(a[1], b[1]) + m(a[0], b[2]) + m(a[4], b3_19) + m(a[3], b4_19);
+        let mut c3 = m(a[3], b[0]) + m(a[2], b[1]) + m(a[1], b[2]) + m(a[0], b[3]) + m(a[4], b4_19);
+        let mut c4 = m(a[4], b[0]) + m(a[3], b[1]) + m(a[2], b[2]) + m(a[1], b[3]) + m(a[0], b[4]);
+
+        c1 += c0 >> 51;
+        c2 += c1 >> 51;
+        c3 += c2 >> 51;
+        c4 += c3 >> 51;
+        let carry = (c4 >> 51) as u64;
+
+        let mut limbs = [
+            (c0 as u64) & LOW_51_BITS,
+            (c1 as u64) & LOW_51_BITS,
+            (c2 as u64) & LOW_51_BITS,
+            (c3 as u64) & LOW_51_BITS,
+            (c4 as u64) & LOW_51_BITS,
+        ];
+        limbs[0] += carry * 19;
+        limbs[1] += limbs[0] >> 51;
+        limbs[0] &= LOW_51_BITS;
+        FieldElement(limbs)
+    }
+
+    fn square(self) -> FieldElement {
+        self.mul(self)
+    }
+
+    // self^exponent, with the exponent as 32 little-endian bytes
+    fn pow(self, exponent: &[u8; 32]) -> FieldElement {
+        let mut result = FieldElement::ONE;
+        for i in (0..256).rev() {
+            result = result.square();
+            if (exponent[i / 8] >> (i % 8)) & 1 == 1 {
+                result = result.mul(self);
+            }
+        }
+        result
+    }
+
+    fn invert(self) -> FieldElement {
+        // p - 2 = 2^255 - 21
+        let mut exponent = [0xffu8; 32];
+        exponent[0] = 0xeb;
+        exponent[31] = 0x7f;
+        self.pow(&exponent)
+    }
+
+    fn is_negative(self) -> bool {
+        self.to_bytes()[0] & 1 == 1
+    }
+
+    fn equals(self, other: FieldElement) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+
+    // d = -121665 / 121666, the curve constant of edwards25519
+    fn edwards_d() -> FieldElement {
+        FieldElement::from_u64(121665).neg().mul(FieldElement::from_u64(121666).invert())
+    }
+
+    // sqrt(-1) = 2^((p - 1) / 4)
+    fn sqrt_minus_one() -> FieldElement {
+        // (p - 1) / 4 = 2^253 - 5
+        let mut exponent = [0xffu8; 32];
+        exponent[0] = 0xfb;
+        exponent[31] = 0x1f;
+        FieldElement::from_u64(2).pow(&exponent)
+    }
+}
+
+/// A point on edwards25519 (-x^2 + y^2 = 1 + d x^2 y^2) in extended
+/// coordinates: x = X/Z, y = Y/Z, x*y = T/Z.
+#[derive(Clone, Copy, Debug)]
+struct EdwardsPoint {
+    x: FieldElement,
+    y: FieldElement,
+    z: FieldElement,
+    t: FieldElement,
+}
+
+impl EdwardsPoint {
+    fn identity() -> EdwardsPoint {
+        EdwardsPoint { x: FieldElement::ZERO, y: FieldElement::ONE, z: FieldElement::ONE, t: FieldElement::ZERO }
+    }
+
+    // The standard base point B (y = 4/5, x positive)
+    fn base_point() -> EdwardsPoint {
+        let mut encoded = [0x66u8; 32];
+        encoded[0] = 0x58;
+        // The encoding is a constant, so decoding cannot fail
+        EdwardsPoint::decompress(&encoded).unwrap_or_else(EdwardsPoint::identity)
+    }
+
+    /// Decodes a 32-byte point encoding (RFC 8032, section 5.1.3).
+    /// Returns `None` for a non-canonical y or a y with no matching x.
+    fn decompress(encoded: &[u8; 32]) -> Option<EdwardsPoint> {
+        let sign = encoded[31] >> 7;
+        let y = FieldElement::from_bytes(encoded);
+
+        // Reject y >= p (non-canonical encoding)
+        let mut y_bytes = *encoded;
+        y_bytes[31] &= 0x7f;
+        if y.to_bytes() != y_bytes {
+            return None;
+        }
+
+        // x^2 = (y^2 - 1) / (d y^2 + 1) = u / v
+        let y2 = y.square();
+        let u = y2.sub(FieldElement::ONE);
+        let v = FieldElement::edwards_d().mul(y2).add(FieldElement::ONE);
+
+        // Candidate root x = u v^3 (u v^7)^((p - 5) / 8)
+        let v3 = v.square().mul(v);
+        let v7 = v3.square().mul(v);
+        let mut exponent = [0xffu8; 32]; // (p - 5) / 8 = 2^252 - 3
+        exponent[0] = 0xfd;
+        exponent[31] = 0x0f;
+        let mut x = u.mul(v3).mul(u.mul(v7).pow(&exponent));
+
+        let vx2 = v.mul(x.square());
+        if vx2.equals(u) {
+            // x is a square root
+        } else if vx2.equals(u.neg()) {
+            x = x.mul(FieldElement::sqrt_minus_one());
+        } else {
+            return None;
+        }
+
+        if x.equals(FieldElement::ZERO) && sign == 1 {
+            return None;
+        }
+        if x.is_negative() != (sign == 1) {
+            x = x.neg();
+        }
+
+        Some(EdwardsPoint { x, y, z: FieldElement::ONE, t: x.mul(y) })
+    }
+
+    fn compress(&self) -> [u8; 32] {
+        let z_inverse = self.z.invert();
+        let x = self.x.mul(z_inverse);
+        let y = self.y.mul(z_inverse);
+        let mut encoded = y.to_bytes();
+        if x.is_negative() {
+            encoded[31] |= 0x80;
+        }
+        encoded
+    }
+
+    // Unified addition (add-2008-hwcd-3); also correct for doubling
+    fn add(&self, other: &EdwardsPoint, d2: FieldElement) -> EdwardsPoint {
+        let a = self.y.sub(self.x).mul(other.y.sub(other.x));
+        let b = self.y.add(self.x).mul(other.y.add(other.x));
+        let c = self.t.mul(d2).mul(other.t);
+        let d = self.z.add(self.z).mul(other.z);
+        let e = b.sub(a);
+        let f = d.sub(c);
+        let g = d.add(c);
+        let h = b.add(a);
+        EdwardsPoint { x: e.mul(f), y: g.mul(h), z: f.mul(g), t: e.mul(h) }
+    }
+
+    fn neg(&self) -> EdwardsPoint {
+        EdwardsPoint { x: self.x.neg(), y: self.y, z: self.z, t: self.t.neg() }
+    }
+
+    // [scalar]P, scalar as 32 little-endian bytes (double-and-add)
+    fn mul_scalar(&self, scalar: &[u8; 32]) -> EdwardsPoint {
+        let d2 = FieldElement::edwards_d().add(FieldElement::edwards_d());
+        let mut result = EdwardsPoint::identity();
+        for i in (0..256).rev() {
+            result = result.add(&result, d2);
+            if (scalar[i / 8] >> (i % 8)) & 1 == 1 {
+                result = result.add(self, d2);
+            }
+        }
+        result
+    }
+}
+
+/// The group order L = 2^252 + 27742317777372353535851937790883648493,
+/// as little-endian bytes.
+const ED25519_L: [u8; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+];
+
+// Helper function: little-endian bytes -> four u64 limbs
+fn scalar_limbs(bytes: &[u8; 32]) -> [u64; 4] {
+    let mut limbs = [0u64; 4];
+    for (i, limb) in limbs.iter_mut().enumerate() {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&bytes[i * 8..i * 8 + 8]);
+        *limb = u64::from_le_bytes(word);
+    }
+    limbs
+}
+
+// Helper function: a >= b for four-limb numbers
+fn limbs_greater_or_equal(a: &[u64; 4], b: &[u64; 4]) -> bool {
+    for i in (0..4).rev() {
+        if a[i] != b[i] {
+            return a[i] > b[i];
+        }
+    }
+    true
+}
+
+/// Reduces a little-endian number of any length modulo L.
+///
+/// Works bit by bit (shift in one bit, subtract L if needed), which is slow
+/// compared to Barrett reduction but short and easy to check.
+fn scalar_reduce(bytes: &[u8]) -> [u8; 32] {
+    let l = scalar_limbs(&ED25519_L);
+    let mut r = [0u64; 4];
+    for i in (0..bytes.len() * 8).rev() {
+        // r = 2r + bit (r < L < 2^253, so this cannot overflow 256 bits)
+        let bit = ((bytes[i / 8] >> (i % 8)) & 1) as u64;
+        for j in (1..4).rev() {
+            r[j] = (r[j] << 1) | (r[j - 1] >> 63);
+        }
+        r[0] = (r[0] << 1) | bit;
+
+        if limbs_greater_or_equal(&r, &l) {
+            let mut borrow = 0u64;
+            for j in 0..4 {
+                let (d1, b1) = r[j].overflowing_sub(l[j]);
+                let (d2, b2) = d1.overflowing_sub(borrow);
+                r[j] = d2;
+                borrow = (b1 || b2) as u64;
+            }
+        }
+    }
+
+    let mut out = [0u8; 32];
+    for (j, limb) in r.iter().enumerate() {
+        out[j * 8..j * 8 + 8].copy_from_slice(&limb.to_le_bytes());
+    }
+    out
+}
+
+// Helper function: (a * b + c) mod L, for 32-byte little-endian a, b, c
+fn scalar_mul_add(a: &[u8; 32], b: &[u8; 32], c: &[u8; 32]) -> [u8; 32] {
+    let a = scalar_limbs(a);
+    let b = scalar_limbs(b);
+    let c = scalar_limbs(c);
+
+    // 512-bit product plus c, in 64-bit limbs with 128-bit intermediates
+    let mut wide = [0u128; 9];
+    for i in 0..4 {
+        for j in 0..4 {
+            let product = (a[i] as u128) * (b[j] as u128);
+            wide[i + j] += product & 0xffff_ffff_ffff_ffff;
+            wide[i + j + 1] += product >> 64;
+        }
+        wide[i] += c[i] as u128;
+    }
+    let mut bytes = Vec::with_capacity(72);
+    let mut carry = 0u128;
+    for limb in wide.iter() {
+        let value = limb + carry;
+        bytes.extend_from_slice(&(value as u64).to_le_bytes());
+        carry = value >> 64;
+    }
+    scalar_reduce(&bytes)
+}
+
+/// Verifies an Ed25519 signature (RFC 8032, section 5.1.7).
+///
+/// Returns `false` for an invalid public key encoding, a non-canonical `S`
+/// (S >= L, which would allow signature malleability), or a signature that
+/// does not match.
+fn ed25519_verify(public_key: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    let a = match EdwardsPoint::decompress(public_key) {
+        Some(point) => point,
+        None => return false,
+    };
+
+    let mut r_bytes = [0u8; 32];
+    r_bytes.copy_from_slice(&signature[..32]);
+    let mut s_bytes = [0u8; 32];
+    s_bytes.copy_from_slice(&signature[32..]);
+    if limbs_greater_or_equal(&scalar_limbs(&s_bytes), &scalar_limbs(&ED25519_L)) {
+        return false;
+    }
+
+    // k = SHA-512(R || A || M) mod L
+    let mut hasher = Sha512::new();
+    hasher.update(&r_bytes);
+    hasher.update(public_key);
+    hasher.update(message);
+    let k = scalar_reduce(&hasher.finalize());
+
+    // Check [S]B - [k]A == R
+    let d2 = FieldElement::edwards_d().add(FieldElement::edwards_d());
+    let check = EdwardsPoint::base_point()
+        .mul_scalar(&s_bytes)
+        .add(&a.neg().mul_scalar(&k), d2);
+    check.compress() == r_bytes
+}
+
+// Helper function: expands a 32-byte seed into the secret scalar and the nonce prefix
+fn ed25519_expand_seed(secret_seed: &[u8; 32]) -> ([u8; 32], [u8; 32]) {
+    let hash = sha512(secret_seed);
+    let mut scalar = [0u8; 32];
+    scalar.copy_from_slice(&hash[..32]);
+    scalar[0] &= 248;
+    scalar[31] &= 127;
+    scalar[31] |= 64;
+    let mut prefix = [0u8; 32];
+    prefix.copy_from_slice(&hash[32..]);
+    (scalar, prefix)
+}
+
+/// The Ed25519 public key for a 32-byte secret seed (RFC 8032, section 5.1.5).
+fn ed25519_public_key_from_seed(secret_seed: &[u8; 32]) -> [u8; 32] {
+    let (scalar, _) = ed25519_expand_seed(secret_seed);
+    EdwardsPoint::base_point().mul_scalar(&scalar).compress()
+}
+
+/// Signs a message with Ed25519 (RFC 8032, section 5.1.6).
+fn ed25519_sign(secret_seed: &[u8; 32], message: &[u8]) -> [u8; 64] {
+    let (scalar, prefix) = ed25519_expand_seed(secret_seed);
+    let public_key = EdwardsPoint::base_point().mul_scalar(&scalar).compress();
+
+    // r = SHA-512(prefix || M) mod L, R = [r]B
+    let mut hasher = Sha512::new();
+    hasher.update(&prefix);
+    hasher.update(message);
+    let r = scalar_reduce(&hasher.finalize());
+    let r_bytes = EdwardsPoint::base_point().mul_scalar(&r).compress();
+
+    // k = SHA-512(R || A || M) mod L, S = (r + k * a) mod L
+    let mut hasher = Sha512::new();
+    hasher.update(&r_bytes);
+    hasher.update(&public_key);
+    hasher.update(message);
+    let k = scalar_reduce(&hasher.finalize());
+    let s = scalar_mul_add(&k, &scalar, &r);
+
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(&r_bytes);
+    signature[32..].copy_from_slice(&s);
+    signature
+}
+
+/// SHA-512 round constants (FIPS 180-4, section 4.2.3).
+const SHA512_K: [u64; 80] = [
+    0x428a2f98d728ae22, 0x7137449123ef65cd, 0xb5c0fbcfec4d3b2f, 0xe9b5dba58189dbbc,
+    0x3956c25bf348b538, 0x59f111f1b605d019, 0x923f82a4af194f9b, 0xab1c5ed5da6d8118,
+    0xd807aa98a3030242, 0x12835b0145706fbe, 0x243185be4ee4b28c, 0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f, 0x80deb1fe3b1696b1, 0x9bdc06a725c71235, 0xc19bf174cf692694,
+    0xe49b69c19ef14ad2, 0xefbe4786384f25e3, 0x0fc19dc68b8cd5b5, 0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275, 0x4a7484aa6ea6e483, 0x5cb0a9dcbd41fbd4, 0x76f988da831153b5,
+    0x983e5152ee66dfab, 0xa831c66d2db43210, 0xb00327c898fb213f, 0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2, 0xd5a79147930aa725, 0x06ca6351e003826f, 0x142929670a0e6e70,
+    0x27b70a8546d22ffc, 0x2e1b21385c26c926, 0x4d2c6dfc5ac42aed, 0x53380d139d95b3df,
+    0x650a73548baf63de, 0x766a0abb3c77b2a8, 0x81c2c92e47edaee6, 0x92722c851482353b,
+    0xa2bfe8a14cf10364, 0xa81a664bbc423001, 0xc24b8b70d0f89791, 0xc76c51a30654be30,
+    0xd192e819d6ef5218, 0xd69906245565a910, 0xf40e35855771202a, 0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8, 0x1e376c085141ab53, 0x2748774cdf8eeb99, 0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63, 0x4ed8aa4ae3418acb, 0x5b9cca4f7763e373, 0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc, 0x78a5636f43172f60, 0x84c87814a1f0ab72, 0x8cc702081a6439ec,
+    0x90befffa23631e28, 0xa4506cebde82bde9, 0xbef9a3f7b2c67915, 0xc67178f2e372532b,
+    0xca273eceea26619c, 0xd186b8c721c0c207, 0xeada7dd6cde0eb1e, 0xf57d4f7fee6ed178,
+    0x06f067aa72176fba, 0x0a637dc5a2c898a6, 0x113f9804bef90dae, 0x1b710b35131c471b,
+    0x28db77f523047d84, 0x32caab7b40c72493, 0x3c9ebe0a15c9bebc, 0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6, 0x597f299cfc657e2a, 0x5fcb6fab3ad6faec, 0x6c44198c4a475817,
+];
+
+/// SHA-512 initial hash value (FIPS 180-4, section 5.3.5).
+const SHA512_H0: [u64; 8] = [
+    0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+    0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+];
+
+/// Incremental SHA-512 (FIPS 180-4), as used by Ed25519.
+struct Sha512 {
+    state: [u64; 8],
+    buffer: [u8; 128],
+    buffer_len: usize,
+    total_len: u128, // in bytes
+}
+
+impl Sha512 {
+    fn new() -> Sha512 {
+        Sha512 { state: SHA512_H0, buffer: [0; 128], buffer_len: 0, total_len: 0 }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        self.total_len = self.total_len.wrapping_add(data.len() as u128);
+
+        // Fill up a partial block first
+        if self.buffer_len > 0 {
+            let take = (128 - self.buffer_len).min(data.len());
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+            if self.buffer_len < 128 {
+                return;
+            }
+            let block = self.buffer;
+            self.compress(&block);
+            self.buffer_len = 0;
+        }
+
+        // Whole blocks straight from the input
+        while data.len() >= 128 {
+            let mut block = [0u8; 128];
+            block.copy_from_slice(&data[..128]);
+            self.compress(&block);
+            data = &data[128..];
+        }
+
+        // Keep the rest for later
+        self.buffer[..data.len()].copy_from_slice(data);
+        self.buffer_len = data.len();
+    }
+
+    fn finalize(mut self) -> [u8; 64] {
+        let bit_len = self.total_len.wrapping_mul(8);
+
+        // Padding: 0x80, zeros, then the message length in bits (big-endian u128)
+        let mut padding = vec![0x80u8];
+        let pad_zeros = (128 + 112 - (self.buffer_len + 1) % 128) % 128;
+        padding.resize(1 + pad_zeros, 0);
+        padding.extend_from_slice(&bit_len.to_be_bytes());
+        self.update(&padding);
+
+        let mut digest = [0u8; 64];
+        for (i, word) in self.state.iter().enumerate() {
+            digest[i * 8..i * 8 + 8].copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+
+    fn compress(&mut self, block: &[u8; 128]) {
+        let mut w = [0u64; 80];
+        for i in 0..16 {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&block[i * 8..i * 8 + 8]);
+            w[i] = u64::from_be_bytes(word);
+        }
+        for i in 16..80 {
+            let s0 = w[i - 15].rotate_right(1) ^ w[i - 15].rotate_right(8) ^ (w[i - 15] >> 7);
+            let s1 = w[i - 2].rotate_right(19) ^ w[i - 2].rotate_right(61) ^ (w[i - 2] >> 6);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+        for i in 0..80 {
+            let s1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+            let ch = (e & f) ^ (!e & g);
+            let temp1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(SHA512_K[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(maj);
+
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp1.wrapping_add(temp2);
+        }
+
+        for (word, value) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *word = word.wrapping_add(value);
+        }
+    }
+}
+
+/// SHA-512 of a byte slice in one call.
+fn sha512(data: &[u8]) -> [u8; 64] {
+    let mut hasher = Sha512::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+fn main() {
+    // Sign an example record with a throwaway key and check it
+    let secret_seed = sha512(b"example seed, do not use for real keys");
+    let mut seed = [0u8; 32];
+    seed.copy_from_slice(&secret_seed[..32]);
+    let public_key = ed25519_public_key_from_seed(&seed);
+
+    let mut collaborator = CollaboratorTomlData {
+        user_name: "Bob".to_string(),
+        user_salt_list: vec![0x123456789abcdef0, 0xabcdef0123456789],
+        ipv4_addresses: Some(vec![Ipv4Addr::new(192, 168, 1, 1), Ipv4Addr::new(10, 0, 0, 1)]),
+        ipv6_addresses: Some(vec![Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)]),
+        gpg_key_public: format!("ed25519:{}", encode_hex(&public_key)),
+        sync_interval: 300,
+        updated_at_timestamp: 1728308000,
+    };
+    let signature = ed25519_sign(&seed, serialize_collaborator_to_canonical_toml(&collaborator).as_bytes());
+    println!("Example signature: {}", encode_hex(&signature));
+    println!(
+        "Verifies: {}",
+        ed25519_verify(&public_key, serialize_collaborator_to_canonical_toml(&collaborator).as_bytes(), &signature)
+    );
+
+    // Detached signature next to a file, checked the same way as the loader
+    // does: against the key the verifier pinned for Bob
+    let mut trusted_keys = TrustedKeys::new();
+    trusted_keys.insert(collaborator.user_name.clone(), public_key);
+    let example_path = std::env::temp_dir().join("bob__collaborator.toml");
+    match fs::write(&example_path, serialize_collaborator_to_canonical_toml(&collaborator))
+        .map_err(ThisProjectError::from)
+        .and_then(|_| write_detached_signature(&example_path, &collaborator, &seed))
+    {
+        Ok(()) => {
+            let status = check_detached_signature(&example_path, &collaborator, &trusted_keys);
+            println!("Detached signature of {}: {}", example_path.display(), status);
+            let status = check_detached_signature(&example_path, &collaborator, &TrustedKeys::new());
+            println!("Same file without a trusted key: {}", status);
+        }
+        Err(e) => println!("Error writing example signature: {}", e),
+    }
+
+    collaborator.sync_interval = 1;
+    println!(
+        "Verifies after tampering: {}",
+        ed25519_verify(&public_key, serialize_collaborator_to_canonical_toml(&collaborator).as_bytes(), &signature)
+    );
+
+    // Check every collaborator in the address book against the local pins
+    let trusted_keys_path = Path::new("project_graph_data/trusted_keys.toml");
+    let trusted_keys = if trusted_keys_path.exists() {
+        match read_trusted_keys(trusted_keys_path) {
+            Ok(trusted_keys) => trusted_keys,
+            Err(e) => {
+                println!("Error reading trusted keys: {}", e);
+                return;
+            }
+        }
+    } else {
+        TrustedKeys::new()
+    };
+    match read_a_collaborator_setup_toml(SignatureCheck::Report, &trusted_keys) {
+        Ok((collaborators, errors)) => {
+            if !errors.is_empty() {
+                println!("Errors encountered:");
+                for err in errors {
+                    println!("{}", err);
+                }
+            }
+
+            println!("Collaborators:");
+            for (collaborator, status) in collaborators {
+                println!("{}: signature {}", collaborator.user_name, status);
+            }
+        }
+        Err(e) => {
+            println!("Error reading TOML files: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex_bytes<const N: usize>(hex: &str) -> [u8; N] {
+        let bytes: Vec<u8> = (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect();
+        bytes.try_into().unwrap()
+    }
+
+    // SHA-512 known answers (FIPS 180-4 / NIST examples)
+
+    #[test]
+    fn sha512_known_answers() {
+        assert_eq!(
+            encode_hex(&sha512(b"")),
+            "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce\
+             47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"
+        );
+        assert_eq!(
+            encode_hex(&sha512(b"abc")),
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+             2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+        );
+        assert_eq!(
+            encode_hex(&sha512(
+                b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu"
+            )),
+            "8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018\
+             501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909"
+        );
+    }
+
+    // Ed25519 known answers (RFC 8032, section 7.1, TEST 1 to 3)
+
+    const RFC8032_VECTORS: [(&str, &str, &str, &str); 3] = [
+        (
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            "",
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155\
+             5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        ),
+        (
+            "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            "72",
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+             085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+        ),
+        (
+            "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+            "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+            "af82",
+            "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac\
+             18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+        ),
+    ];
+
+    #[test]
+    fn ed25519_rfc8032_vectors() {
+        for (secret, public, message, signature) in RFC8032_VECTORS {
+            let secret: [u8; 32] = hex_bytes(secret);
+            let public: [u8; 32] = hex_bytes(public);
+            let message: Vec<u8> = (0..message.len()).step_by(2).map(|i| u8::from_str_radix(&message[i..i + 2], 16).unwrap()).collect();
+            let signature: [u8; 64] = hex_bytes(signature);
+
+            assert_eq!(ed25519_public_key_from_seed(&secret), public);
+            assert_eq!(ed25519_sign(&secret, &message), signature);
+            assert!(ed25519_verify(&public, &message, &signature));
+
+            let mut tampered = message.clone();
+            tampered.push(0);
+            assert!(!ed25519_verify(&public, &tampered, &signature));
+        }
+    }
+
+    #[test]
+    fn ed25519_rejects_non_canonical_s() {
+        // S + L verifies mathematically but must be rejected (RFC 8032, 5.1.7)
+        let (secret, public, _, signature) = RFC8032_VECTORS[0];
+        let secret: [u8; 32] = hex_bytes(secret);
+        let public: [u8; 32] = hex_bytes(public);
+        let mut signature: [u8; 64] = hex_bytes(signature);
+        assert_eq!(ed25519_sign(&secret, b""), signature);
+
+        let mut carry = 0u16;
+        for i in 0..32 {
+            let sum = signature[32 + i] as u16 + ED25519_L[i] as u16 + carry;
+            signature[32 + i] = sum as u8;
+            carry = sum >> 8;
+        }
+        assert!(!ed25519_verify(&public, b"", &signature));
+    }
+
+    // An ed25519 key exported by GnuPG 2 (version 4, EdDSA legacy packet)
+    const GNUPG_ED25519_KEY: &str = "-----BEGIN PGP PUBLIC KEY BLOCK-----
+
+mDMEatTS3hYJKwYBBAHaRw8BAQdAYFKnByshL+w8VaY6bcqwJyVPomrzpRjx6Fkg
+3VNY0/C0IUFsaWNlIEV4YW1wbGUgPGFsaWNlQGV4YW1wbGUub3JnPoiQBBMWCAA4
+FiEEd0VzuBOvBsKahEhh+Nofj4OVvJ0FAmrU0t4CGwMFCwkIBwIGFQoJCAsCBBYC
+AwECHgECF4AACgkQ+Nofj4OVvJ0LeAD+PA0jsdpriwcvowMTwL2NUpEijDXFKUVz
+9vq6VZWkzX0A/3orugxH8edB7fQTf/jhm40SLggNaJeAoNAq50g50+UI
+=WRH0
+-----END PGP PUBLIC KEY BLOCK-----
+";
+
+    #[test]
+    fn armored_eddsa_key_gives_its_ed25519_point() {
+        assert_eq!(
+            encode_hex(&parse_ed25519_public_key(GNUPG_ED25519_KEY).unwrap()),
+            "6052a7072b212fec3c55a63a6dcab027254fa26af3a518f1e85920dd5358d3f0"
+        );
+        let corrupted = GNUPG_ED25519_KEY.replace("=WRH0", "=WRH1");
+        assert!(parse_ed25519_public_key(&corrupted).is_err());
+    }
+
+    fn example_collaborator(gpg_key_public: String) -> CollaboratorTomlData {
+        CollaboratorTomlData {
+            user_name: "alice".to_string(),
+            user_salt_list: vec![1, 2],
+            ipv4_addresses: Some(vec![Ipv4Addr::new(10, 0, 0, 1)]),
+            ipv6_addresses: None,
+            gpg_key_public,
+            sync_interval: 60,
+            updated_at_timestamp: 1728308000,
+        }
+    }
+
+    #[test]
+    fn signature_is_checked_against_the_pinned_key_only() {
+        let dir = std::env::temp_dir().join(format!("signed_collaborator_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let toml_path = dir.join("alice__collaborator.toml");
+
+        // Attacker-made file: names the attacker's key and is signed with it
+        let attacker_seed = [7u8; 32];
+        let attacker_key = ed25519_public_key_from_seed(&attacker_seed);
+        let forged = example_collaborator(format!("ed25519:{}", encode_hex(&attacker_key)));
+        write_detached_signature(&toml_path, &forged, &attacker_seed).unwrap();
+
+        let alice_seed = [9u8; 32];
+        let alice_key = ed25519_public_key_from_seed(&alice_seed);
+        let mut trusted_keys = TrustedKeys::new();
+        trusted_keys.insert("alice".to_string(), alice_key);
+        assert!(matches!(check_detached_signature(&toml_path, &forged, &trusted_keys), SignatureStatus::Invalid(_)));
+        assert!(matches!(check_detached_signature(&toml_path, &forged, &TrustedKeys::new()), SignatureStatus::Invalid(_)));
+
+        // The real file, signed by alice, verifies against her pin
+        let genuine = example_collaborator(format!("ed25519:{}", encode_hex(&alice_key)));
+        write_detached_signature(&toml_path, &genuine, &alice_seed).unwrap();
+        assert_eq!(check_detached_signature(&toml_path, &genuine, &trusted_keys), SignatureStatus::Valid);
+
+        // An unreadable .sig (here: a directory) is an invalid signature, not an abort
+        let mut sig_path = toml_path.as_os_str().to_owned();
+        sig_path.push(".sig");
+        fs::remove_file(&sig_path).unwrap();
+        fs::create_dir(&sig_path).unwrap();
+        assert!(matches!(check_detached_signature(&toml_path, &genuine, &trusted_keys), SignatureStatus::Invalid(_)));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
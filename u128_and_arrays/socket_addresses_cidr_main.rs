@@ -0,0 +1,625 @@
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::ffi::OsStr;
+use std::str::FromStr;
+use toml::Value;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ipv4_addresses: Option<Vec<Ipv4Entry>>,
+    ipv6_addresses: Option<Vec<Ipv6Entry>>,
+    socket_addresses: Option<Vec<PeerSocketAddr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    AddressError(String),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::AddressError(err) => write!(f, "Address Error: {}", err),
+        }
+    }
+}
+
+/*
+Address list entries
+
+    ipv4_addresses      "10.0.0.1"            an address
+                        "10.0.0.0/24"         a CIDR network
+    ipv6_addresses      "fe80::1"             an address
+                        "fe80::1%eth0"        an address with a zone (scope)
+                        "fe80::1%3"           ... numeric scope id
+                        "2001:db8::/32"       a CIDR network
+    socket_addresses    "10.0.0.1:4040"
+                        "[fe80::1]:4040"
+                        "[fe80::1%eth0]:4040"
+
+Zones are kept exactly as written. A numeric zone is also the scope id of
+the `SocketAddrV6`; an interface name cannot be resolved without the OS and
+leaves the scope id at 0. CIDR networks must have their host bits zero
+("10.0.0.0/24", not "10.0.0.1/24").
+*/
+
+// Helper function: an error about one address string
+fn address_error(text: &str, reason: &str) -> ThisProjectError {
+    ThisProjectError::AddressError(format!("Invalid address \"{}\": {}", text, reason))
+}
+
+// Helper function: split "address/prefix" and check the prefix length
+fn split_cidr(text: &str, max_prefix_len: u8) -> Result<Option<(&str, u8)>, ThisProjectError> {
+    let (address_text, prefix_text) = match text.split_once('/') {
+        Some(parts) => parts,
+        None => return Ok(None),
+    };
+    let prefix_len = prefix_text
+        .parse::<u8>()
+        .map_err(|_| address_error(text, "prefix length is not a number"))?;
+    if prefix_len > max_prefix_len {
+        return Err(address_error(text, &format!("prefix length {} is above {}", prefix_len, max_prefix_len)));
+    }
+    Ok(Some((address_text, prefix_len)))
+}
+
+// Helper function: check that the bits after `prefix_len` are zero
+fn check_network_bits(text: &str, address_bits: u128, total_bits: u32, prefix_len: u8) -> Result<(), ThisProjectError> {
+    let host_bits = total_bits - prefix_len as u32;
+    let host_mask = if host_bits == 0 { 0 } else { u128::MAX >> (128 - host_bits) };
+    if address_bits & host_mask != 0 {
+        return Err(address_error(text, "host bits are set; write the network address"));
+    }
+    Ok(())
+}
+
+// Helper function: check a zone (the part after '%')
+fn check_zone(text: &str, zone: &str) -> Result<(), ThisProjectError> {
+    if zone.is_empty() {
+        return Err(address_error(text, "empty zone after '%'"));
+    }
+    if zone.chars().any(|c| c.is_whitespace() || matches!(c, '%' | '/' | '[' | ']')) {
+        return Err(address_error(text, "zone contains whitespace, '%', '/' or brackets"));
+    }
+    Ok(())
+}
+
+// Helper function: name the list an address error came from
+fn address_error_in_list(key: &str, err: ThisProjectError) -> ThisProjectError {
+    match err {
+        ThisProjectError::AddressError(message) => ThisProjectError::AddressError(format!("{}: {}", key, message)),
+        other => other,
+    }
+}
+
+/// An IPv6 address with an optional zone, e.g. `fe80::1%eth0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ZonedIpv6Addr {
+    addr: Ipv6Addr,
+    zone: Option<String>,
+}
+
+impl ZonedIpv6Addr {
+    /// The zone as a scope id, if it is numeric.
+    fn scope_id(&self) -> Option<u32> {
+        self.zone.as_ref().and_then(|zone| zone.parse::<u32>().ok())
+    }
+}
+
+impl FromStr for ZonedIpv6Addr {
+    type Err = ThisProjectError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address_text, zone) = match s.split_once('%') {
+            Some((address_text, zone)) => {
+                check_zone(s, zone)?;
+                (address_text, Some(zone.to_string()))
+            }
+            None => (s, None),
+        };
+        let addr = address_text
+            .parse::<Ipv6Addr>()
+            .map_err(|e| address_error(s, &e.to_string()))?;
+        Ok(ZonedIpv6Addr { addr, zone })
+    }
+}
+
+impl fmt::Display for ZonedIpv6Addr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.zone {
+            Some(zone) => write!(f, "{}%{}", self.addr, zone),
+            None => write!(f, "{}", self.addr),
+        }
+    }
+}
+
+/// An `ipv4_addresses` entry: an address or a CIDR network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Ipv4Entry {
+    Address(Ipv4Addr),
+    Network(Ipv4Addr, u8),
+}
+
+impl FromStr for Ipv4Entry {
+    type Err = ThisProjectError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match split_cidr(s, 32)? {
+            Some((address_text, prefix_len)) => {
+                let network = address_text
+                    .parse::<Ipv4Addr>()
+                    .map_err(|e| address_error(s, &e.to_string()))?;
+                check_network_bits(s, u32::from(network) as u128, 32, prefix_len)?;
+                Ok(Ipv4Entry::Network(network, prefix_len))
+            }
+            None => s
+                .parse::<Ipv4Addr>()
+                .map(Ipv4Entry::Address)
+                .map_err(|e| address_error(s, &e.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Ipv4Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Ipv4Entry::Address(addr) => write!(f, "{}", addr),
+            Ipv4Entry::Network(network, prefix_len) => write!(f, "{}/{}", network, prefix_len),
+        }
+    }
+}
+
+/// An `ipv6_addresses` entry: an address (with optional zone) or a CIDR
+/// network. Networks have no zone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Ipv6Entry {
+    Address(ZonedIpv6Addr),
+    Network(Ipv6Addr, u8),
+}
+
+impl FromStr for Ipv6Entry {
+    type Err = ThisProjectError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match split_cidr(s, 128)? {
+            Some((address_text, prefix_len)) => {
+                if address_text.contains('%') {
+                    return Err(address_error(s, "a network cannot have a zone"));
+                }
+                let network = address_text
+                    .parse::<Ipv6Addr>()
+                    .map_err(|e| address_error(s, &e.to_string()))?;
+                check_network_bits(s, u128::from(network), 128, prefix_len)?;
+                Ok(Ipv6Entry::Network(network, prefix_len))
+            }
+            None => s.parse::<ZonedIpv6Addr>().map(Ipv6Entry::Address),
+        }
+    }
+}
+
+impl fmt::Display for Ipv6Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Ipv6Entry::Address(addr) => write!(f, "{}", addr),
+            Ipv6Entry::Network(network, prefix_len) => write!(f, "{}/{}", network, prefix_len),
+        }
+    }
+}
+
+/// A `socket_addresses` entry. `addr` is ready to connect to; `zone` keeps
+/// an IPv6 zone as written (see the notes above).
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PeerSocketAddr {
+    addr: SocketAddr,
+    zone: Option<String>,
+}
+
+impl FromStr for PeerSocketAddr {
+    type Err = ThisProjectError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // IPv6: "[address%zone]:port"
+        if let Some(rest) = s.strip_prefix('[') {
+            let (host, port_text) = rest
+                .split_once("]:")
+                .ok_or_else(|| address_error(s, "expected [address]:port"))?;
+            let host = host.parse::<ZonedIpv6Addr>().map_err(|_| address_error(s, "invalid IPv6 address"))?;
+            let port = port_text
+                .parse::<u16>()
+                .map_err(|_| address_error(s, "port is not a number from 0 to 65535"))?;
+            let scope_id = host.scope_id().unwrap_or(0);
+            return Ok(PeerSocketAddr {
+                addr: SocketAddr::V6(SocketAddrV6::new(host.addr, port, 0, scope_id)),
+                zone: host.zone,
+            });
+        }
+
+        // IPv4: "address:port"
+        let addr = s
+            .parse::<SocketAddrV4>()
+            .map_err(|_| address_error(s, "expected address:port or [address]:port"))?;
+        Ok(PeerSocketAddr { addr: SocketAddr::V4(addr), zone: None })
+    }
+}
+
+impl fmt::Display for PeerSocketAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.addr, &self.zone) {
+            (SocketAddr::V6(v6), Some(zone)) => write!(f, "[{}%{}]:{}", v6.ip(), zone, v6.port()),
+            (SocketAddr::V6(v6), None) => write!(f, "[{}]:{}", v6.ip(), v6.port()),
+            (SocketAddr::V4(v4), _) => write!(f, "{}", v4),
+        }
+    }
+}
+
+// Helper function: TOML table -> CollaboratorTomlData, failing on the first bad field
+fn collaborator_from_toml_table(table: &toml::map::Map<String, Value>) -> Result<CollaboratorTomlData, ThisProjectError> {
+
+    // Extract user_name
+    let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+    };
+
+    // Extract user_salt_list
+    let user_salt_list = if let Some(Value::Array(arr)) = table.get("user_salt_list") {
+        arr.iter()
+            .map(|val| {
+                if let Value::String(s) = val {
+                    u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                        .map_err(ThisProjectError::ParseIntError)
+                } else {
+                    Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                }
+            })
+            .collect::<Result<Vec<u128>, ThisProjectError>>()?
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_salt_list".into()));
+    };
+
+    // Extract ipv4_addresses
+    let ipv4_addresses = extract_parsed_list::<Ipv4Entry>(table, "ipv4_addresses")?;
+
+    // Extract ipv6_addresses
+    let ipv6_addresses = extract_parsed_list::<Ipv6Entry>(table, "ipv6_addresses")?;
+
+    // Extract socket_addresses
+    let socket_addresses = extract_parsed_list::<PeerSocketAddr>(table, "socket_addresses")?;
+
+    // Extract gpg_key_public
+    let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+    };
+
+    // Extract sync_interval
+    let sync_interval = extract_u64(table, "sync_interval")?;
+
+    // Extract updated_at_timestamp
+    let updated_at_timestamp = extract_u64(table, "updated_at_timestamp")?;
+
+    Ok(CollaboratorTomlData {
+        user_name,
+        user_salt_list,
+        ipv4_addresses,
+        ipv6_addresses,
+        socket_addresses,
+        gpg_key_public,
+        sync_interval,
+        updated_at_timestamp,
+    })
+}
+
+/// Extracts the optional list of strings at `key` and parses each entry
+/// with `T::from_str`; used for `ipv4_addresses` (`Ipv4Entry`),
+/// `ipv6_addresses` (`Ipv6Entry`) and `socket_addresses` (`PeerSocketAddr`).
+///
+/// An absent key or an empty list is `None`.
+///
+/// # Error Handling
+///
+/// Fails on the first problem: a value that is not an array, an entry that
+/// is not a string, or an entry that does not parse (its error is prefixed
+/// with `key`, e.g. `ipv4_addresses: Invalid address "10.0.0.1/33": ...`).
+fn extract_parsed_list<T>(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<T>>, ThisProjectError>
+where
+    T: FromStr<Err = ThisProjectError>,
+{
+    let arr = match table.get(key) {
+        Some(Value::Array(arr)) => arr,
+        Some(_) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Expected array", key))),
+        None => return Ok(None),
+    };
+
+    let mut items = Vec::with_capacity(arr.len());
+    for val in arr {
+        if let Value::String(s) = val {
+            items.push(s.parse::<T>().map_err(|e| address_error_in_list(key, e))?);
+        } else {
+            return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string", key)));
+        }
+    }
+
+    if items.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(items))
+    }
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+// Helper function: TOML text -> CollaboratorTomlData
+fn deserialize_collaborator_from_toml(toml_string: &str) -> Result<CollaboratorTomlData, ThisProjectError> {
+    match toml::from_str::<Value>(toml_string) {
+        Ok(Value::Table(table)) => collaborator_from_toml_table(&table),
+        Ok(_) => Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure: Expected a table".into())),
+        Err(e) => Err(ThisProjectError::TomlVanillaDeserialStrError(e.to_string())),
+    }
+}
+
+/// Toml Deserialization: Reads collaborator setup data from TOML files in a
+/// specified directory.
+///
+/// Every `*.toml` file in `project_graph_data/collaborator_files_address_book`
+/// is parsed with `deserialize_collaborator_from_toml`; a file that does not
+/// parse is skipped and its error is added to the error vector. The address
+/// lists may hold CIDR networks and IPv6 zones, and the optional
+/// `socket_addresses` list is read as well.
+///
+/// # Returns
+///
+/// Returns a `Result` containing:
+/// - `Ok`: A tuple with:
+///     - A vector of successfully parsed `CollaboratorTomlData` instances.
+///     - A vector of any `ThisProjectError` encountered during parsing.
+/// - `Err`: A `ThisProjectError` if there was an error reading the directory or any file.
+fn read_a_collaborator_setup_toml() -> Result<(Vec<CollaboratorTomlData>, Vec<ThisProjectError>), ThisProjectError> {
+    let mut collaborators = Vec::new();
+    let mut errors = Vec::new();
+    let dir_path = Path::new("project_graph_data/collaborator_files_address_book");
+
+    for entry in fs::read_dir(dir_path)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
+            let toml_string = fs::read_to_string(&path)?;
+
+            match deserialize_collaborator_from_toml(&toml_string) {
+                Ok(collaborator) => collaborators.push(collaborator),
+                Err(e) => errors.push(e),
+            }
+        }
+    }
+
+    Ok((collaborators, errors))
+}
+
+/// Serializes a `CollaboratorTomlData` struct into a TOML-formatted string.
+///
+/// One `key = value` line per field, in struct order, with escaped strings,
+/// salts as `0x` hex strings and `None` address lists left out;
+/// `socket_addresses` comes after `ipv6_addresses`.
+/// Networks, zones and ports are written as they are read, so a file
+/// round-trips unchanged.
+fn serialize_collaborator_to_toml(collaborator: &CollaboratorTomlData) -> Result<String, ThisProjectError> {
+    let mut toml_string = String::new();
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", escape_toml_basic_string(&collaborator.user_name)));
+
+    // Add user_salt_list
+    toml_string.push_str("user_salt_list = [\n");
+    for salt in &collaborator.user_salt_list {
+        toml_string.push_str(&format!("    \"0x{:x}\",\n", salt));
+    }
+    toml_string.push_str("]\n");
+
+    // Add ipv4_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv4_addresses", &collaborator.ipv4_addresses)?;
+
+    // Add ipv6_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv6_addresses", &collaborator.ipv6_addresses)?;
+
+    // Add socket_addresses
+    serialize_ip_addresses(&mut toml_string, "socket_addresses", &collaborator.socket_addresses)?;
+
+    // Add gpg_key_public
+    toml_string.push_str(&format!("gpg_key_public = \"{}\"\n", escape_toml_basic_string(&collaborator.gpg_key_public)));
+
+    // Add sync_interval
+    toml_string.push_str(&format!("sync_interval = {}\n", collaborator.sync_interval));
+
+    // Add updated_at_timestamp
+    toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp));
+
+    Ok(toml_string)
+}
+
+// Helper function to escape a value for a TOML basic string ("...")
+fn escape_toml_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Helper function to serialize IP addresses, networks or socket addresses (any
+// `Display` entry) to TOML array format
+fn serialize_ip_addresses<T: std::fmt::Display>(
+    toml_string: &mut String,
+    key: &str,
+    addresses: &Option<Vec<T>>
+) -> Result<(), ThisProjectError> {
+    if let Some(addr_vec) = addresses {
+        toml_string.push_str(&format!("{} = [\n", key));
+        for addr in addr_vec {
+            toml_string.push_str(&format!("    \"{}\",\n", addr));
+        }
+        toml_string.push_str("]\n");
+    }
+    Ok(()) // Return Ok(()) if the addresses field is None
+}
+
+// Function to write a TOML string to a file
+fn write_toml_to_file(file_path: &str, toml_string: &str) -> Result<(), ThisProjectError> {
+    // Attempt to create the file.
+    let mut file = match File::create(file_path) {
+        Ok(file) => file,
+        Err(e) => return Err(ThisProjectError::IoError(e)),
+    };
+
+    // Attempt to write to the file.
+    if let Err(e) = file.write_all(toml_string.as_bytes()) {
+        return Err(ThisProjectError::IoError(e));
+    }
+
+    // Everything successful!
+    Ok(())
+}
+
+fn main() {
+    // Entries as written in a file, parsed and formatted back
+    for text in ["10.0.0.1", "10.0.0.0/24", "10.0.0.1/24", "10.0.0.0/33"] {
+        match text.parse::<Ipv4Entry>() {
+            Ok(entry) => println!("ipv4 \"{}\" -> {:?} -> \"{}\"", text, entry, entry),
+            Err(e) => println!("{}", e),
+        }
+    }
+    for text in ["fe80::1%eth0", "fe80::1%3", "2001:db8::/32", "fe80::%eth0/64", "fe80::1%"] {
+        match text.parse::<Ipv6Entry>() {
+            Ok(entry) => println!("ipv6 \"{}\" -> {:?} -> \"{}\"", text, entry, entry),
+            Err(e) => println!("{}", e),
+        }
+    }
+    for text in ["10.0.0.1:4040", "[fe80::1]:4040", "[fe80::1%eth0]:4040", "[fe80::1%3]:4040", "10.0.0.1", "[fe80::1]:70000"] {
+        match text.parse::<PeerSocketAddr>() {
+            Ok(entry) => println!("socket \"{}\" -> {:?} -> \"{}\"", text, entry.addr, entry),
+            Err(e) => println!("{}", e),
+        }
+    }
+
+    // Example CollaboratorTomlData instance
+    let collaborator = CollaboratorTomlData {
+        user_name: "Bob".to_string(),
+        user_salt_list: vec![0x123456789abcdef0, 0xabcdef0123456789],
+        ipv4_addresses: Some(vec![
+            Ipv4Entry::Address(Ipv4Addr::new(192, 168, 1, 1)),
+            Ipv4Entry::Network(Ipv4Addr::new(10, 0, 0, 0), 24),
+        ]),
+        ipv6_addresses: Some(vec![
+            Ipv6Entry::Address(ZonedIpv6Addr { addr: Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), zone: Some("eth0".to_string()) }),
+            Ipv6Entry::Address(ZonedIpv6Addr { addr: Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1), zone: None }),
+        ]),
+        socket_addresses: Some(vec![
+            PeerSocketAddr { addr: SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 1), 4040)), zone: None },
+            PeerSocketAddr {
+                addr: SocketAddr::V6(SocketAddrV6::new(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), 4040, 0, 0)),
+                zone: Some("eth0".to_string()),
+            },
+        ]),
+        gpg_key_public: "-----BEGIN PGP PUBLIC KEY BLOCK----- ...".to_string(),
+        sync_interval: 300,
+        updated_at_timestamp: 1728308000,
+    };
+
+    match serialize_collaborator_to_toml(&collaborator) {
+        Ok(toml_string) => {
+            println!("Serialized TOML:\n{}", toml_string);
+
+            match deserialize_collaborator_from_toml(&toml_string) {
+                Ok(read_back) => println!(
+                    "Read back: {}",
+                    if read_back.ipv4_addresses == collaborator.ipv4_addresses
+                        && read_back.ipv6_addresses == collaborator.ipv6_addresses
+                        && read_back.socket_addresses == collaborator.socket_addresses
+                    {
+                        "addresses unchanged"
+                    } else {
+                        "addresses differ"
+                    }
+                ),
+                Err(e) => println!("Error reading back: {}", e),
+            }
+
+            // Write the TOML string to a file (example file path)
+            match write_toml_to_file("collaborator_data.toml", &toml_string) {
+                Ok(_) => println!("TOML data written to file successfully."),
+                Err(e) => println!("Error writing to file: {}", e),
+            }
+        }
+        Err(e) => println!("Error serializing to TOML: {}", e),
+    }
+
+    // Files with plain address lists still load
+    match read_a_collaborator_setup_toml() {
+        Ok((collaborators, errors)) => {
+            if !errors.is_empty() {
+                println!("Errors encountered:");
+                for err in errors {
+                    println!("{}", err);
+                }
+            }
+
+            println!("Collaborators:");
+            for collaborator in collaborators {
+                println!("{:#?}", collaborator);
+            }
+        }
+        Err(e) => {
+            println!("Error reading TOML files: {}", e);
+        }
+    }
+}
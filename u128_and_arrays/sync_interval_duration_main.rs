@@ -0,0 +1,568 @@
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::ffi::OsStr;
+use std::time::Duration;
+use toml::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    gpg_key_public: String,
+    sync_interval: Duration,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    DurationError(String),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::DurationError(err) => write!(f, "Duration Error: {}", err),
+        }
+    }
+}
+
+/*
+Duration strings for `sync_interval`
+
+A duration string is one or more `<integer><unit>` parts, largest unit
+first, each unit at most once, with no spaces:
+
+    d    days (86400 s)         "1d"
+    h    hours                  "1h30m"
+    m    minutes                "5m"
+    s    seconds                "90s"
+    ms   milliseconds           "250ms"
+    us   microseconds (or µs)
+    ns   nanoseconds
+
+A bare TOML integer is seconds, as before: `sync_interval = 60` and
+`sync_interval = "1m"` are the same interval.
+*/
+
+// Units of a duration string, largest first, with their length in nanoseconds
+const DURATION_UNITS: [(&str, u128); 8] = [
+    ("d", 86_400_000_000_000),
+    ("h", 3_600_000_000_000),
+    ("m", 60_000_000_000),
+    ("s", 1_000_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("µs", 1_000),
+    ("ns", 1),
+];
+
+/// Parses a duration string such as `"5m"`, `"1h30m"` or `"250ms"`.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::DurationError` for an empty string, a part
+/// without a number or unit, an unknown unit, units out of order or
+/// repeated, or a total that does not fit in a `Duration`.
+fn parse_duration_str(s: &str) -> Result<Duration, ThisProjectError> {
+    let invalid = |reason: String| ThisProjectError::DurationError(format!("Invalid duration \"{}\": {}", s, reason));
+    if s.is_empty() {
+        return Err(invalid("empty string".into()));
+    }
+
+    let mut total_nanos: u128 = 0;
+    let mut previous_unit_index: Option<usize> = None;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let digit_count = rest.bytes().take_while(|b| b.is_ascii_digit()).count();
+        if digit_count == 0 {
+            return Err(invalid(format!("expected a number at \"{}\"", rest)));
+        }
+        let number = rest[..digit_count]
+            .parse::<u64>()
+            .map_err(|e| invalid(format!("{}", e)))?;
+        rest = &rest[digit_count..];
+
+        let unit_len = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let unit = &rest[..unit_len];
+        rest = &rest[unit_len..];
+        if unit.is_empty() {
+            return Err(invalid(format!("missing unit after {} (use d, h, m, s, ms, us or ns)", number)));
+        }
+        let unit_index = match DURATION_UNITS.iter().position(|(name, _)| *name == unit) {
+            Some(index) => index,
+            None => return Err(invalid(format!("unknown unit \"{}\" (use d, h, m, s, ms, us or ns)", unit))),
+        };
+
+        // "us" and "µs" rank the same
+        let unit_rank = if DURATION_UNITS[unit_index].0 == "µs" { unit_index - 1 } else { unit_index };
+        if let Some(previous) = previous_unit_index {
+            if unit_rank <= previous {
+                return Err(invalid(format!("unit \"{}\" is repeated or out of order (largest unit first)", unit)));
+            }
+        }
+        previous_unit_index = Some(unit_rank);
+
+        total_nanos += number as u128 * DURATION_UNITS[unit_index].1;
+    }
+
+    let seconds = u64::try_from(total_nanos / 1_000_000_000)
+        .map_err(|_| invalid("too large".into()))?;
+    Ok(Duration::new(seconds, (total_nanos % 1_000_000_000) as u32))
+}
+
+/// Formats a duration as the shortest duration string, e.g. `"1h30m"` or
+/// `"250ms"`; zero is `"0s"`. Parsing the result gives back `duration`.
+fn format_duration(duration: Duration) -> String {
+    let mut remaining = duration.as_nanos();
+    if remaining == 0 {
+        return "0s".to_string();
+    }
+    let mut formatted = String::new();
+    for (unit, unit_nanos) in DURATION_UNITS.iter() {
+        if *unit == "µs" {
+            continue;
+        }
+        let count = remaining / unit_nanos;
+        if count > 0 {
+            formatted.push_str(&format!("{}{}", count, unit));
+            remaining %= unit_nanos;
+        }
+    }
+    formatted
+}
+
+/// How `serialize_collaborator_to_toml` writes `sync_interval`.
+///
+/// - `Seconds`: an integer, as before, e.g. `sync_interval = 300`. An
+///   interval with a fraction of a second cannot be an integer and is
+///   written as a duration string instead.
+/// - `Human`: a duration string, e.g. `sync_interval = "5m"`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DurationFormat {
+    Seconds,
+    Human,
+}
+
+/// Allowed range of `sync_interval`, checked by the reader and the
+/// serializer. The default allows 100 milliseconds to one day.
+#[derive(Debug, Clone)]
+struct SyncIntervalPolicy {
+    min_interval: Duration,
+    max_interval: Duration,
+}
+
+impl Default for SyncIntervalPolicy {
+    fn default() -> Self {
+        SyncIntervalPolicy {
+            min_interval: Duration::from_millis(100),
+            max_interval: Duration::from_secs(24 * 60 * 60),
+        }
+    }
+}
+
+impl SyncIntervalPolicy {
+    /// Returns `ThisProjectError::DurationError` if `interval` is outside
+    /// `min_interval..=max_interval`.
+    fn check(&self, interval: Duration) -> Result<(), ThisProjectError> {
+        if interval < self.min_interval {
+            return Err(ThisProjectError::DurationError(format!(
+                "sync_interval {} is below the minimum of {}",
+                format_duration(interval), format_duration(self.min_interval)
+            )));
+        }
+        if interval > self.max_interval {
+            return Err(ThisProjectError::DurationError(format!(
+                "sync_interval {} is above the maximum of {}",
+                format_duration(interval), format_duration(self.max_interval)
+            )));
+        }
+        Ok(())
+    }
+}
+
+// Helper function to extract a duration that may be an integer (seconds) or a duration string
+fn extract_duration(table: &toml::map::Map<String, Value>, key: &str) -> Result<Duration, ThisProjectError> {
+    match table.get(key) {
+        Some(Value::Integer(i)) => {
+            if *i >= 0 {
+                Ok(Duration::from_secs(*i as u64))
+            } else {
+                Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+            }
+        }
+        Some(Value::String(s)) => parse_duration_str(s),
+        _ => Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key))),
+    }
+}
+
+// Helper function: TOML table -> CollaboratorTomlData, failing on the first bad field
+fn collaborator_from_toml_table(
+    table: &toml::map::Map<String, Value>,
+    policy: &SyncIntervalPolicy,
+) -> Result<CollaboratorTomlData, ThisProjectError> {
+
+    // Extract user_name
+    let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+    };
+
+    // Extract user_salt_list
+    let user_salt_list = if let Some(Value::Array(arr)) = table.get("user_salt_list") {
+        arr.iter()
+            .map(|val| {
+                if let Value::String(s) = val {
+                    u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                        .map_err(ThisProjectError::ParseIntError)
+                } else {
+                    Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                }
+            })
+            .collect::<Result<Vec<u128>, ThisProjectError>>()?
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_salt_list".into()));
+    };
+
+    // Extract ipv4_addresses
+    let ipv4_addresses = extract_ipv4_addresses(table, "ipv4_addresses")?;
+
+    // Extract ipv6_addresses
+    let ipv6_addresses = extract_ipv6_addresses(table, "ipv6_addresses")?;
+
+    // Extract gpg_key_public
+    let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+    };
+
+    // Extract sync_interval (seconds or duration string), within the policy range
+    let sync_interval = extract_duration(table, "sync_interval")?;
+    policy.check(sync_interval)?;
+
+    // Extract updated_at_timestamp
+    let updated_at_timestamp = extract_u64(table, "updated_at_timestamp")?;
+
+    Ok(CollaboratorTomlData {
+        user_name,
+        user_salt_list,
+        ipv4_addresses,
+        ipv6_addresses,
+        gpg_key_public,
+        sync_interval,
+        updated_at_timestamp,
+    })
+}
+
+fn extract_ipv4_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv4Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv4Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+fn extract_ipv6_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv6Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv6Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+// Helper function: TOML text -> CollaboratorTomlData
+fn deserialize_collaborator_from_toml(toml_string: &str, policy: &SyncIntervalPolicy) -> Result<CollaboratorTomlData, ThisProjectError> {
+    match toml::from_str::<Value>(toml_string) {
+        Ok(Value::Table(table)) => collaborator_from_toml_table(&table, policy),
+        Ok(_) => Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure: Expected a table".into())),
+        Err(e) => Err(ThisProjectError::TomlVanillaDeserialStrError(e.to_string())),
+    }
+}
+
+/// Toml Deserialization: Reads collaborator setup data from TOML files in a
+/// specified directory.
+///
+/// Every `*.toml` file in `project_graph_data/collaborator_files_address_book`
+/// is parsed with `deserialize_collaborator_from_toml`; a file that does not
+/// parse is skipped and its error is added to the error vector.
+///
+/// `sync_interval` may be written as seconds (`sync_interval = 300`) or as a
+/// duration string (`sync_interval = "5m"`); an interval outside `policy`
+/// is one of the errors that skips the file.
+///
+/// # Returns
+///
+/// Returns a `Result` containing:
+/// - `Ok`: A tuple with:
+///     - A vector of successfully parsed `CollaboratorTomlData` instances.
+///     - A vector of any `ThisProjectError` encountered during parsing.
+/// - `Err`: A `ThisProjectError` if there was an error reading the directory or any file.
+fn read_a_collaborator_setup_toml(
+    policy: &SyncIntervalPolicy,
+) -> Result<(Vec<CollaboratorTomlData>, Vec<ThisProjectError>), ThisProjectError> {
+    let mut collaborators = Vec::new();
+    let mut errors = Vec::new();
+    let dir_path = Path::new("project_graph_data/collaborator_files_address_book");
+
+    for entry in fs::read_dir(dir_path)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
+            let toml_string = fs::read_to_string(&path)?;
+
+            match deserialize_collaborator_from_toml(&toml_string, policy) {
+                Ok(collaborator) => collaborators.push(collaborator),
+                Err(e) => errors.push(e),
+            }
+        }
+    }
+
+    Ok((collaborators, errors))
+}
+
+/// Serializes a `CollaboratorTomlData` struct into a TOML-formatted string.
+///
+/// One `key = value` line per field, in struct order, with escaped strings,
+/// salts as `0x` hex strings and `None` IP lists left out; `sync_interval`
+/// is written in `duration_format`.
+///
+/// # Error Handling
+///
+/// A `sync_interval` outside `policy` returns `ThisProjectError::DurationError`,
+/// so a file is never written that the reader would reject.
+fn serialize_collaborator_to_toml(
+    collaborator: &CollaboratorTomlData,
+    duration_format: DurationFormat,
+    policy: &SyncIntervalPolicy,
+) -> Result<String, ThisProjectError> {
+    policy.check(collaborator.sync_interval)?;
+
+    let mut toml_string = String::new();
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", escape_toml_basic_string(&collaborator.user_name)));
+
+    // Add user_salt_list
+    toml_string.push_str("user_salt_list = [\n");
+    for salt in &collaborator.user_salt_list {
+        toml_string.push_str(&format!("    \"0x{:x}\",\n", salt));
+    }
+    toml_string.push_str("]\n");
+
+    // Add ipv4_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv4_addresses", &collaborator.ipv4_addresses)?;
+
+    // Add ipv6_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv6_addresses", &collaborator.ipv6_addresses)?;
+
+    // Add gpg_key_public
+    toml_string.push_str(&format!("gpg_key_public = \"{}\"\n", escape_toml_basic_string(&collaborator.gpg_key_public)));
+
+    // Add sync_interval
+    let interval = collaborator.sync_interval;
+    if duration_format == DurationFormat::Seconds && interval.subsec_nanos() == 0 {
+        toml_string.push_str(&format!("sync_interval = {}\n", interval.as_secs()));
+    } else {
+        toml_string.push_str(&format!("sync_interval = \"{}\"\n", format_duration(interval)));
+    }
+
+    // Add updated_at_timestamp
+    toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp));
+
+    Ok(toml_string)
+}
+
+// Helper function to escape a value for a TOML basic string ("...")
+fn escape_toml_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Helper function to serialize IP addresses to TOML array format
+fn serialize_ip_addresses<T: std::fmt::Display>(
+    toml_string: &mut String,
+    key: &str,
+    addresses: &Option<Vec<T>>
+) -> Result<(), ThisProjectError> {
+    if let Some(addr_vec) = addresses {
+        toml_string.push_str(&format!("{} = [\n", key));
+        for addr in addr_vec {
+            toml_string.push_str(&format!("    \"{}\",\n", addr));
+        }
+        toml_string.push_str("]\n");
+    }
+    Ok(()) // Return Ok(()) if the addresses field is None
+}
+
+// Function to write a TOML string to a file
+fn write_toml_to_file(file_path: &str, toml_string: &str) -> Result<(), ThisProjectError> {
+    // Attempt to create the file.
+    let mut file = match File::create(file_path) {
+        Ok(file) => file,
+        Err(e) => return Err(ThisProjectError::IoError(e)),
+    };
+
+    // Attempt to write to the file.
+    if let Err(e) = file.write_all(toml_string.as_bytes()) {
+        return Err(ThisProjectError::IoError(e));
+    }
+
+    // Everything successful!
+    Ok(())
+}
+
+
+fn main() {
+    let policy = SyncIntervalPolicy::default();
+
+    // Duration strings, and how they are written back
+    for text in ["5m", "1h30m", "250ms", "90s", "1d", "30m1h", "5 minutes", "10"] {
+        match parse_duration_str(text) {
+            Ok(duration) => println!("\"{}\" = {:?}, written as \"{}\"", text, duration, format_duration(duration)),
+            Err(e) => println!("{}", e),
+        }
+    }
+
+    // Example CollaboratorTomlData instance
+    let collaborator = CollaboratorTomlData {
+        user_name: "Bob".to_string(),
+        user_salt_list: vec![0x123456789abcdef0, 0xabcdef0123456789],
+        ipv4_addresses: Some(vec![Ipv4Addr::new(192, 168, 1, 1), Ipv4Addr::new(10, 0, 0, 1)]),
+        ipv6_addresses: Some(vec![Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)]),
+        gpg_key_public: "-----BEGIN PGP PUBLIC KEY BLOCK----- ...".to_string(),
+        sync_interval: Duration::from_secs(5400),
+        updated_at_timestamp: 1728307160,
+    };
+
+    // Written with a duration string, then read back
+    match serialize_collaborator_to_toml(&collaborator, DurationFormat::Human, &policy) {
+        Ok(toml_string) => {
+            println!("Serialized TOML:\n{}", toml_string);
+
+            match deserialize_collaborator_from_toml(&toml_string, &policy) {
+                Ok(read_back) => println!("Read back sync_interval: {:?}", read_back.sync_interval),
+                Err(e) => println!("Error reading back: {}", e),
+            }
+
+            // Write the TOML string to a file (example file path)
+            match write_toml_to_file("collaborator_data.toml", &toml_string) {
+                Ok(_) => println!("TOML data written to file successfully."),
+                Err(e) => println!("Error writing to file: {}", e),
+            }
+        }
+        Err(e) => println!("Error serializing to TOML: {}", e),
+    }
+
+    // A stricter policy rejects the same interval
+    let strict_policy = SyncIntervalPolicy {
+        min_interval: Duration::from_secs(10),
+        max_interval: Duration::from_secs(60 * 60),
+    };
+    if let Err(e) = serialize_collaborator_to_toml(&collaborator, DurationFormat::Seconds, &strict_policy) {
+        println!("With a one hour maximum: {}", e);
+    }
+
+    // Existing files with integer seconds still load
+    match read_a_collaborator_setup_toml(&policy) {
+        Ok((collaborators, errors)) => {
+            if !errors.is_empty() {
+                println!("Errors encountered:");
+                for err in errors {
+                    println!("{}", err);
+                }
+            }
+
+            println!("Collaborators:");
+            for collaborator in collaborators {
+                println!("{:#?}", collaborator);
+            }
+        }
+        Err(e) => {
+            println!("Error reading TOML files: {}", e);
+        }
+    }
+}
@@ -0,0 +1,825 @@
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::ffi::OsStr;
+use std::str::FromStr;
+use toml::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ipv4_addresses: Option<Vec<Ipv4Addr>>,
+    ipv6_addresses: Option<Vec<Ipv6Addr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: TomlDatetime,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    DatetimeError(String),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::DatetimeError(err) => write!(f, "Datetime Error: {}", err),
+        }
+    }
+}
+
+/*
+TOML datetimes (TOML 1.0, section "Offset Date-Time" to "Local Time")
+
+    offset datetime   1979-05-27T07:32:00Z, 1979-05-27T00:32:00.999999-07:00
+    local datetime    1979-05-27T07:32:00
+    local date        1979-05-27
+    local time        07:32:00, 00:32:00.999999
+
+The `T` may be `t` or a space, and `Z` may be `z`. Seconds are required.
+Fractional seconds beyond nanoseconds are truncated.
+
+Only an offset datetime is an instant. For conversion to Unix seconds a
+local datetime is read as UTC and a local date as midnight UTC; a local
+time has no date and cannot be converted.
+*/
+
+/// A calendar date, `0000-01-01` to `9999-12-31`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TomlDate {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+/// A time of day. `second` may be 60 for a leap second, as in RFC 3339.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TomlTime {
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanosecond: u32,
+}
+
+/// A UTC offset: `Z`, or minutes east of UTC (`+02:00` is 120).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TomlOffset {
+    Z,
+    Minutes(i16),
+}
+
+/// One of the four TOML datetime kinds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TomlDatetime {
+    OffsetDatetime(TomlDate, TomlTime, TomlOffset),
+    LocalDatetime(TomlDate, TomlTime),
+    LocalDate(TomlDate),
+    LocalTime(TomlTime),
+}
+
+impl TomlDatetime {
+    /// The instant `seconds` after 1970-01-01T00:00:00Z, as an offset
+    /// datetime in UTC (`...Z`).
+    fn from_unix_seconds(seconds: i64) -> Result<TomlDatetime, ThisProjectError> {
+        let days = seconds.div_euclid(86_400);
+        let seconds_of_day = seconds.rem_euclid(86_400);
+        let (year, month, day) = civil_from_days(days);
+        if !(0..=9999).contains(&year) {
+            return Err(ThisProjectError::DatetimeError(format!(
+                "Unix time {} is outside the years 0000 to 9999", seconds
+            )));
+        }
+        let date = TomlDate { year: year as u16, month, day };
+        let time = TomlTime {
+            hour: (seconds_of_day / 3600) as u8,
+            minute: (seconds_of_day % 3600 / 60) as u8,
+            second: (seconds_of_day % 60) as u8,
+            nanosecond: 0,
+        };
+        Ok(TomlDatetime::OffsetDatetime(date, time, TomlOffset::Z))
+    }
+
+    /// Seconds since 1970-01-01T00:00:00Z, rounded down to a whole second.
+    /// Local datetimes are read as UTC and local dates as midnight UTC.
+    ///
+    /// # Error Handling
+    ///
+    /// A local time has no date and returns `ThisProjectError::DatetimeError`.
+    fn to_unix_seconds(self) -> Result<i64, ThisProjectError> {
+        let (date, time, offset_minutes) = match self {
+            TomlDatetime::OffsetDatetime(date, time, TomlOffset::Z) => (date, Some(time), 0),
+            TomlDatetime::OffsetDatetime(date, time, TomlOffset::Minutes(m)) => (date, Some(time), m as i64),
+            TomlDatetime::LocalDatetime(date, time) => (date, Some(time), 0),
+            TomlDatetime::LocalDate(date) => (date, None, 0),
+            TomlDatetime::LocalTime(_) => {
+                return Err(ThisProjectError::DatetimeError(format!(
+                    "Local time {} has no date and cannot be converted to Unix time", self
+                )));
+            }
+        };
+        let days = days_from_civil(date.year as i64, date.month, date.day);
+        let seconds_of_day = match time {
+            Some(t) => t.hour as i64 * 3600 + t.minute as i64 * 60 + t.second as i64,
+            None => 0,
+        };
+        Ok(days * 86_400 + seconds_of_day - offset_minutes * 60)
+    }
+
+    /// The RFC 3339 form: offset datetimes as they are, local datetimes as
+    /// UTC and local dates as midnight UTC, as in `to_unix_seconds`.
+    ///
+    /// # Error Handling
+    ///
+    /// A local time has no date and returns `ThisProjectError::DatetimeError`.
+    fn to_rfc3339(self) -> Result<TomlDatetime, ThisProjectError> {
+        let midnight = TomlTime { hour: 0, minute: 0, second: 0, nanosecond: 0 };
+        match self {
+            TomlDatetime::OffsetDatetime(..) => Ok(self),
+            TomlDatetime::LocalDatetime(date, time) => Ok(TomlDatetime::OffsetDatetime(date, time, TomlOffset::Z)),
+            TomlDatetime::LocalDate(date) => Ok(TomlDatetime::OffsetDatetime(date, midnight, TomlOffset::Z)),
+            TomlDatetime::LocalTime(_) => Err(ThisProjectError::DatetimeError(format!(
+                "Local time {} has no date and cannot be written as RFC 3339", self
+            ))),
+        }
+    }
+
+    /// Which of the four kinds this is, for messages.
+    fn kind_name(&self) -> &'static str {
+        match self {
+            TomlDatetime::OffsetDatetime(..) => "offset datetime",
+            TomlDatetime::LocalDatetime(..) => "local datetime",
+            TomlDatetime::LocalDate(_) => "local date",
+            TomlDatetime::LocalTime(_) => "local time",
+        }
+    }
+}
+
+impl FromStr for TomlDatetime {
+    type Err = ThisProjectError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| ThisProjectError::DatetimeError(format!("Invalid datetime \"{}\": {}", s, reason));
+        if !s.is_ascii() {
+            return Err(invalid("non-ASCII character"));
+        }
+        let bytes = s.as_bytes();
+
+        // Local time: no date part
+        if bytes.len() < 10 || bytes[4] != b'-' {
+            let (time, rest) = parse_toml_time(s).map_err(|e| invalid(&e))?;
+            if !rest.is_empty() {
+                return Err(invalid("a local time cannot have an offset or trailing text"));
+            }
+            return Ok(TomlDatetime::LocalTime(time));
+        }
+
+        let date = parse_toml_date(&s[..10]).map_err(|e| invalid(&e))?;
+        if bytes.len() == 10 {
+            return Ok(TomlDatetime::LocalDate(date));
+        }
+        if !matches!(bytes[10], b'T' | b't' | b' ') {
+            return Err(invalid("expected 'T', 't' or a space after the date"));
+        }
+
+        let (time, rest) = parse_toml_time(&s[11..]).map_err(|e| invalid(&e))?;
+        if rest.is_empty() {
+            return Ok(TomlDatetime::LocalDatetime(date, time));
+        }
+        let offset = parse_toml_offset(rest).map_err(|e| invalid(&e))?;
+        Ok(TomlDatetime::OffsetDatetime(date, time, offset))
+    }
+}
+
+/// Writes the TOML form, which for an offset datetime is RFC 3339
+/// (always with an upper-case `T` and `Z`).
+impl fmt::Display for TomlDatetime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TomlDatetime::OffsetDatetime(date, time, offset) => write!(f, "{}T{}{}", date, time, offset),
+            TomlDatetime::LocalDatetime(date, time) => write!(f, "{}T{}", date, time),
+            TomlDatetime::LocalDate(date) => write!(f, "{}", date),
+            TomlDatetime::LocalTime(time) => write!(f, "{}", time),
+        }
+    }
+}
+
+impl fmt::Display for TomlDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl fmt::Display for TomlTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+        if self.nanosecond != 0 {
+            let fraction = format!("{:09}", self.nanosecond);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TomlOffset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TomlOffset::Z => write!(f, "Z"),
+            TomlOffset::Minutes(m) => {
+                let sign = if *m < 0 { '-' } else { '+' };
+                write!(f, "{}{:02}:{:02}", sign, m.abs() / 60, m.abs() % 60)
+            }
+        }
+    }
+}
+
+// Helper function to parse exactly `s.len()` ASCII digits
+fn parse_fixed_digits(s: &str, what: &str) -> Result<u32, String> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("expected {} digits for the {}", s.len(), what));
+    }
+    s.parse::<u32>().map_err(|e| format!("{}: {}", what, e))
+}
+
+// Helper function: `YYYY-MM-DD`, with the day checked against the month
+fn parse_toml_date(s: &str) -> Result<TomlDate, String> {
+    let bytes = s.as_bytes();
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return Err("expected a date as YYYY-MM-DD".into());
+    }
+    let year = parse_fixed_digits(&s[0..4], "year")?;
+    let month = parse_fixed_digits(&s[5..7], "month")?;
+    let day = parse_fixed_digits(&s[8..10], "day")?;
+    if !(1..=12).contains(&month) {
+        return Err(format!("month {} is not 01 to 12", month));
+    }
+    let days_in_month = match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    if day < 1 || day > days_in_month {
+        return Err(format!("day {} is not valid for {:04}-{:02}", day, year, month));
+    }
+    Ok(TomlDate { year: year as u16, month: month as u8, day: day as u8 })
+}
+
+// Helper function: `HH:MM:SS[.fraction]`, returning the unparsed rest
+fn parse_toml_time(s: &str) -> Result<(TomlTime, &str), String> {
+    let bytes = s.as_bytes();
+    if bytes.len() < 8 || bytes[2] != b':' || bytes[5] != b':' {
+        return Err("expected a time as HH:MM:SS".into());
+    }
+    let hour = parse_fixed_digits(&s[0..2], "hour")?;
+    let minute = parse_fixed_digits(&s[3..5], "minute")?;
+    let second = parse_fixed_digits(&s[6..8], "second")?;
+    if hour > 23 || minute > 59 || second > 60 {
+        return Err(format!("time {:02}:{:02}:{:02} is out of range", hour, minute, second));
+    }
+
+    let mut rest = &s[8..];
+    let mut nanosecond = 0;
+    if let Some(after_dot) = rest.strip_prefix('.') {
+        let digit_count = after_dot.bytes().take_while(|b| b.is_ascii_digit()).count();
+        if digit_count == 0 {
+            return Err("expected digits after the decimal point".into());
+        }
+        // Keep nanosecond precision; further digits are truncated
+        let mut fraction = after_dot[..digit_count.min(9)].to_string();
+        while fraction.len() < 9 {
+            fraction.push('0');
+        }
+        nanosecond = parse_fixed_digits(&fraction, "fraction")?;
+        rest = &after_dot[digit_count..];
+    }
+
+    let time = TomlTime {
+        hour: hour as u8,
+        minute: minute as u8,
+        second: second as u8,
+        nanosecond,
+    };
+    Ok((time, rest))
+}
+
+// Helper function: `Z`, `z`, `+HH:MM` or `-HH:MM`
+fn parse_toml_offset(s: &str) -> Result<TomlOffset, String> {
+    if s == "Z" || s == "z" {
+        return Ok(TomlOffset::Z);
+    }
+    let bytes = s.as_bytes();
+    if bytes.len() != 6 || !matches!(bytes[0], b'+' | b'-') || bytes[3] != b':' {
+        return Err(format!("expected an offset as Z, +HH:MM or -HH:MM, found \"{}\"", s));
+    }
+    let hours = parse_fixed_digits(&s[1..3], "offset hours")?;
+    let minutes = parse_fixed_digits(&s[4..6], "offset minutes")?;
+    if hours > 23 || minutes > 59 {
+        return Err(format!("offset {} is out of range", s));
+    }
+    let total = (hours * 60 + minutes) as i16;
+    Ok(TomlOffset::Minutes(if bytes[0] == b'-' { -total } else { total }))
+}
+
+fn is_leap_year(year: u32) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+// Days since 1970-01-01 for a proleptic Gregorian date
+// (H. Hinnant, "chrono-Compatible Low-Level Date Algorithms")
+fn days_from_civil(year: i64, month: u8, day: u8) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// Inverse of `days_from_civil`
+fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// How `serialize_collaborator_to_toml` writes `updated_at_timestamp`.
+///
+/// - `UnixSeconds`: an integer, as before; readable by the other programs
+///   in this directory.
+/// - `Rfc3339`: an offset datetime such as `2024-10-07T13:19:20Z`. Offset
+///   datetimes keep the offset they were read with; local datetimes are
+///   written as UTC and local dates as midnight UTC (see
+///   `TomlDatetime::to_rfc3339`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TimestampFormat {
+    UnixSeconds,
+    Rfc3339,
+}
+
+// Helper function to extract a timestamp that may be an integer or a datetime
+fn extract_timestamp(table: &toml::map::Map<String, Value>, key: &str) -> Result<TomlDatetime, ThisProjectError> {
+    match table.get(key) {
+        Some(Value::Integer(i)) => {
+            if *i >= 0 {
+                TomlDatetime::from_unix_seconds(*i)
+            } else {
+                Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+            }
+        }
+        Some(Value::Datetime(dt)) => {
+            let datetime = dt.to_string().parse::<TomlDatetime>()?;
+            if let TomlDatetime::LocalTime(_) = datetime {
+                return Err(ThisProjectError::DatetimeError(format!(
+                    "Invalid {}: {} is a {}, expected a date", key, datetime, datetime.kind_name()
+                )));
+            }
+            Ok(datetime)
+        }
+        _ => Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key))),
+    }
+}
+
+// Helper function: TOML table -> CollaboratorTomlData, failing on the first bad field
+fn collaborator_from_toml_table(table: &toml::map::Map<String, Value>) -> Result<CollaboratorTomlData, ThisProjectError> {
+
+    // Extract user_name
+    let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+    };
+
+    // Extract user_salt_list
+    let user_salt_list = if let Some(Value::Array(arr)) = table.get("user_salt_list") {
+        arr.iter()
+            .map(|val| {
+                if let Value::String(s) = val {
+                    u128::from_str_radix(s.trim_start_matches("0x"), 16)
+                        .map_err(ThisProjectError::ParseIntError)
+                } else {
+                    Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid salt format: Expected string".into()))
+                }
+            })
+            .collect::<Result<Vec<u128>, ThisProjectError>>()?
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_salt_list".into()));
+    };
+
+    // Extract ipv4_addresses
+    let ipv4_addresses = extract_ipv4_addresses(table, "ipv4_addresses")?;
+
+    // Extract ipv6_addresses
+    let ipv6_addresses = extract_ipv6_addresses(table, "ipv6_addresses")?;
+
+    // Extract gpg_key_public
+    let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+    };
+
+    // Extract sync_interval
+    let sync_interval = extract_u64(table, "sync_interval")?;
+
+    // Extract updated_at_timestamp (integer or datetime)
+    let updated_at_timestamp = extract_timestamp(table, "updated_at_timestamp")?;
+
+    Ok(CollaboratorTomlData {
+        user_name,
+        user_salt_list,
+        ipv4_addresses,
+        ipv6_addresses,
+        gpg_key_public,
+        sync_interval,
+        updated_at_timestamp,
+    })
+}
+
+fn extract_ipv4_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv4Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv4Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+fn extract_ipv6_addresses(table: &toml::map::Map<String, Value>, key: &str) -> Result<Option<Vec<Ipv6Addr>>, ThisProjectError> {
+    if let Some(Value::Array(arr)) = table.get(key) {
+        let mut addresses = Vec::new();
+        for val in arr {
+            if let Value::String(s) = val {
+                match s.parse::<Ipv6Addr>() {
+                    Ok(ip) => addresses.push(ip),
+                    Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: {}. Skipping this address.", key, e))),
+                }
+            } else {
+                return Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {} format: Expected string. Skipping this address.", key)));
+            }
+        }
+
+        if addresses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(addresses))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+// Helper function: TOML text -> CollaboratorTomlData
+fn deserialize_collaborator_from_toml(toml_string: &str) -> Result<CollaboratorTomlData, ThisProjectError> {
+    match toml::from_str::<Value>(toml_string) {
+        Ok(Value::Table(table)) => collaborator_from_toml_table(&table),
+        Ok(_) => Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure: Expected a table".into())),
+        Err(e) => Err(ThisProjectError::TomlVanillaDeserialStrError(e.to_string())),
+    }
+}
+
+/// Toml Deserialization: Reads collaborator setup data from TOML files in a
+/// specified directory.
+///
+/// Every `*.toml` file in `project_graph_data/collaborator_files_address_book`
+/// is parsed with `deserialize_collaborator_from_toml`; a file that does not
+/// parse is skipped and its error is added to the error vector.
+///
+/// `updated_at_timestamp` may be written either as Unix seconds
+/// (`updated_at_timestamp = 1728307160`) or as a TOML datetime with a date
+/// (`updated_at_timestamp = 2024-10-07T13:19:20Z`).
+///
+/// # Returns
+///
+/// Returns a `Result` containing:
+/// - `Ok`: A tuple with:
+///     - A vector of successfully parsed `CollaboratorTomlData` instances.
+///     - A vector of any `ThisProjectError` encountered during parsing.
+/// - `Err`: A `ThisProjectError` if there was an error reading the directory or any file.
+fn read_a_collaborator_setup_toml() -> Result<(Vec<CollaboratorTomlData>, Vec<ThisProjectError>), ThisProjectError> {
+    let mut collaborators = Vec::new();
+    let mut errors = Vec::new();
+    let dir_path = Path::new("project_graph_data/collaborator_files_address_book");
+
+    for entry in fs::read_dir(dir_path)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
+            let toml_string = fs::read_to_string(&path)?;
+
+            match deserialize_collaborator_from_toml(&toml_string) {
+                Ok(collaborator) => collaborators.push(collaborator),
+                Err(e) => errors.push(e),
+            }
+        }
+    }
+
+    Ok((collaborators, errors))
+}
+
+/// Serializes a `CollaboratorTomlData` struct into a TOML-formatted string.
+///
+/// One `key = value` line per field, in struct order, with escaped strings,
+/// salts as `0x` hex strings and `None` IP lists left out;
+/// `updated_at_timestamp` is written in `timestamp_format`.
+///
+/// # Error Handling
+///
+/// A timestamp that is a local time returns `ThisProjectError::DatetimeError`,
+/// and so does one before 1970 with `TimestampFormat::UnixSeconds`.
+fn serialize_collaborator_to_toml(
+    collaborator: &CollaboratorTomlData,
+    timestamp_format: TimestampFormat,
+) -> Result<String, ThisProjectError> {
+    let mut toml_string = String::new();
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", escape_toml_basic_string(&collaborator.user_name)));
+
+    // Add user_salt_list
+    toml_string.push_str("user_salt_list = [\n");
+    for salt in &collaborator.user_salt_list {
+        toml_string.push_str(&format!("    \"0x{:x}\",\n", salt));
+    }
+    toml_string.push_str("]\n");
+
+    // Add ipv4_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv4_addresses", &collaborator.ipv4_addresses)?;
+
+    // Add ipv6_addresses
+    serialize_ip_addresses(&mut toml_string, "ipv6_addresses", &collaborator.ipv6_addresses)?;
+
+    // Add gpg_key_public
+    toml_string.push_str(&format!("gpg_key_public = \"{}\"\n", escape_toml_basic_string(&collaborator.gpg_key_public)));
+
+    // Add sync_interval
+    toml_string.push_str(&format!("sync_interval = {}\n", collaborator.sync_interval));
+
+    // Add updated_at_timestamp
+    match timestamp_format {
+        TimestampFormat::UnixSeconds => {
+            let seconds = collaborator.updated_at_timestamp.to_unix_seconds()?;
+            if seconds < 0 {
+                return Err(ThisProjectError::DatetimeError(format!(
+                    "updated_at_timestamp {} is before 1970 and has no u64 Unix time",
+                    collaborator.updated_at_timestamp
+                )));
+            }
+            toml_string.push_str(&format!("updated_at_timestamp = {}\n", seconds));
+        }
+        TimestampFormat::Rfc3339 => {
+            toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp.to_rfc3339()?));
+        }
+    }
+
+    Ok(toml_string)
+}
+
+// Helper function to escape a value for a TOML basic string ("...")
+fn escape_toml_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Helper function to serialize IP addresses to TOML array format
+fn serialize_ip_addresses<T: std::fmt::Display>(
+    toml_string: &mut String,
+    key: &str,
+    addresses: &Option<Vec<T>>
+) -> Result<(), ThisProjectError> {
+    if let Some(addr_vec) = addresses {
+        toml_string.push_str(&format!("{} = [\n", key));
+        for addr in addr_vec {
+            toml_string.push_str(&format!("    \"{}\",\n", addr));
+        }
+        toml_string.push_str("]\n");
+    }
+    Ok(()) // Return Ok(()) if the addresses field is None
+}
+
+// Function to write a TOML string to a file
+fn write_toml_to_file(file_path: &str, toml_string: &str) -> Result<(), ThisProjectError> {
+    // Attempt to create the file.
+    let mut file = match File::create(file_path) {
+        Ok(file) => file,
+        Err(e) => return Err(ThisProjectError::IoError(e)),
+    };
+
+    // Attempt to write to the file.
+    if let Err(e) = file.write_all(toml_string.as_bytes()) {
+        return Err(ThisProjectError::IoError(e));
+    }
+
+    // Everything successful!
+    Ok(())
+}
+
+fn main() {
+    // The four TOML datetime kinds, and their Unix seconds where there are any
+    for text in ["2024-10-07T15:19:20.5+02:00", "2024-10-07 13:19:20", "2024-10-07", "13:19:20", "2024-02-30"] {
+        match text.parse::<TomlDatetime>() {
+            Ok(datetime) => match datetime.to_unix_seconds() {
+                Ok(seconds) => println!("{} ({}) = {} Unix seconds", datetime, datetime.kind_name(), seconds),
+                Err(e) => println!("{} ({}): {}", datetime, datetime.kind_name(), e),
+            },
+            Err(e) => println!("{}", e),
+        }
+    }
+
+    // Example CollaboratorTomlData instance, timestamp from Unix seconds
+    let updated_at_timestamp = match TomlDatetime::from_unix_seconds(1728307160) {
+        Ok(datetime) => datetime,
+        Err(e) => {
+            println!("{}", e);
+            return;
+        }
+    };
+    let collaborator = CollaboratorTomlData {
+        user_name: "Bob".to_string(),
+        user_salt_list: vec![0x123456789abcdef0, 0xabcdef0123456789],
+        ipv4_addresses: Some(vec![Ipv4Addr::new(192, 168, 1, 1), Ipv4Addr::new(10, 0, 0, 1)]),
+        ipv6_addresses: Some(vec![Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)]),
+        gpg_key_public: "-----BEGIN PGP PUBLIC KEY BLOCK----- ...".to_string(),
+        sync_interval: 300,
+        updated_at_timestamp,
+    };
+
+    // Written with an RFC 3339 timestamp, then read back
+    match serialize_collaborator_to_toml(&collaborator, TimestampFormat::Rfc3339) {
+        Ok(toml_string) => {
+            println!("Serialized TOML:\n{}", toml_string);
+
+            match deserialize_collaborator_from_toml(&toml_string) {
+                Ok(read_back) => println!(
+                    "Read back: {} = {} Unix seconds",
+                    read_back.updated_at_timestamp,
+                    read_back.updated_at_timestamp.to_unix_seconds().unwrap_or(-1)
+                ),
+                Err(e) => println!("Error reading back: {}", e),
+            }
+
+            // Write the TOML string to a file (example file path)
+            match write_toml_to_file("collaborator_data.toml", &toml_string) {
+                Ok(_) => println!("TOML data written to file successfully."),
+                Err(e) => println!("Error writing to file: {}", e),
+            }
+        }
+        Err(e) => println!("Error serializing to TOML: {}", e),
+    }
+
+    // Existing files with integer timestamps still load
+    match read_a_collaborator_setup_toml() {
+        Ok((collaborators, errors)) => {
+            if !errors.is_empty() {
+                println!("Errors encountered:");
+                for err in errors {
+                    println!("{}", err);
+                }
+            }
+
+            println!("Collaborators:");
+            for collaborator in &collaborators {
+                println!("{:#?}", collaborator);
+            }
+
+            // And can be written back with integer timestamps
+            if let Some(collaborator) = collaborators.first() {
+                match serialize_collaborator_to_toml(collaborator, TimestampFormat::UnixSeconds) {
+                    Ok(toml_string) => println!("{} with Unix seconds:\n{}", collaborator.user_name, toml_string),
+                    Err(e) => println!("Error serializing to TOML: {}", e),
+                }
+            }
+        }
+        Err(e) => {
+            println!("Error reading TOML files: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datetime(s: &str) -> TomlDatetime {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn civil_day_known_answers() {
+        for (year, month, day, days) in [
+            (1970, 1, 1, 0),
+            (2000, 2, 29, 11_016),
+            (1900, 2, 28, -25_509),
+            (1900, 3, 1, -25_508),
+            (1600, 2, 29, -135_081),
+        ] {
+            assert_eq!(days_from_civil(year, month, day), days, "{}-{}-{}", year, month, day);
+            assert_eq!(civil_from_days(days), (year, month, day));
+        }
+        assert!(is_leap_year(2000) && is_leap_year(2024));
+        assert!(!is_leap_year(1900) && !is_leap_year(2023));
+    }
+
+    #[test]
+    fn leap_days_are_checked() {
+        assert!("2000-02-29".parse::<TomlDatetime>().is_ok());
+        assert!("1900-02-29".parse::<TomlDatetime>().is_err());
+    }
+
+    #[test]
+    fn offsets_are_applied() {
+        assert_eq!(datetime("2024-10-07T07:49:20-05:30").to_unix_seconds().unwrap(), 1_728_307_160);
+        assert_eq!(datetime("2024-10-07T15:19:20+02:00").to_unix_seconds().unwrap(), 1_728_307_160);
+        assert_eq!(datetime("2024-10-07T07:49:20-05:30").to_string(), "2024-10-07T07:49:20-05:30");
+    }
+
+    #[test]
+    fn unix_seconds_round_trip() {
+        for seconds in [0, 951_782_400, 1_728_307_160, -2_203_891_200] {
+            let datetime = TomlDatetime::from_unix_seconds(seconds).unwrap();
+            assert_eq!(datetime.to_unix_seconds().unwrap(), seconds);
+            assert_eq!(datetime.to_string().parse::<TomlDatetime>().unwrap(), datetime);
+        }
+        assert_eq!(TomlDatetime::from_unix_seconds(0).unwrap().to_string(), "1970-01-01T00:00:00Z");
+        assert_eq!(TomlDatetime::from_unix_seconds(951_782_400).unwrap().to_string(), "2000-02-29T00:00:00Z");
+    }
+
+    #[test]
+    fn rfc3339_writes_local_values_as_utc() {
+        assert_eq!(datetime("2024-10-07").to_rfc3339().unwrap().to_string(), "2024-10-07T00:00:00Z");
+        assert_eq!(datetime("2024-10-07 13:19:20.5").to_rfc3339().unwrap().to_string(), "2024-10-07T13:19:20.5Z");
+        assert_eq!(datetime("2024-10-07T07:49:20-05:30").to_rfc3339().unwrap(), datetime("2024-10-07T07:49:20-05:30"));
+        assert!(datetime("13:19:20").to_rfc3339().is_err());
+    }
+}
@@ -0,0 +1,963 @@
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::ffi::OsStr;
+use std::str::FromStr;
+use toml::Value;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct CollaboratorTomlData {
+    user_name: String,
+    user_salt_list: Vec<u128>,
+    ip_addresses: Option<Vec<IpAddr>>,
+    gpg_key_public: String,
+    sync_interval: u64,
+    updated_at_timestamp: u64,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+enum ThisProjectError {
+    IoError(std::io::Error),
+    TomlVanillaDeserialStrError(String), // use without serede crate (good)
+    ParseIntError(ParseIntError),
+    ListError(String),
+}
+
+impl From<std::io::Error> for ThisProjectError {
+    fn from(err: std::io::Error) -> Self {
+        ThisProjectError::IoError(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ThisProjectError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ThisProjectError::ParseIntError(err)
+    }
+}
+
+impl fmt::Display for ThisProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThisProjectError::IoError(err) => write!(f, "IO Error: {}", err),
+            ThisProjectError::TomlVanillaDeserialStrError(err) => write!(f, "TOML Error: {}", err),
+            ThisProjectError::ParseIntError(err) => write!(f, "Parse Int Error: {}", err),
+            ThisProjectError::ListError(err) => write!(f, "List Error: {}", err),
+        }
+    }
+}
+
+/// A check on one list element; returns why the element is not allowed.
+type ElementPolicy<T> = fn(&T) -> Result<(), String>;
+
+/// How `extract_parsed_list` reads one list field.
+///
+/// - `required`: a missing key is an error. Otherwise a missing key, or an
+///   empty list, reads as `None`.
+/// - `min_len`, `max_len`: allowed number of entries when the key is present.
+/// - `element_policies`: checked for every parsed element, in order.
+struct ListRules<T: 'static> {
+    required: bool,
+    min_len: usize,
+    max_len: usize,
+    element_policies: &'static [ElementPolicy<T>],
+}
+
+/// `user_salt_list`: at least one salt, no zero salts.
+const SALT_LIST_RULES: ListRules<u128> = ListRules {
+    required: true,
+    min_len: 1,
+    max_len: 64,
+    element_policies: &[reject_zero_salt],
+};
+
+fn reject_zero_salt(salt: &u128) -> Result<(), String> {
+    if *salt == 0 {
+        Err("a salt of zero is not allowed".into())
+    } else {
+        Ok(())
+    }
+}
+
+// Helper function: salts are written as "0x" + hex
+fn parse_hex_salt(s: &str) -> Result<u128, String> {
+    u128::from_str_radix(s.trim_start_matches("0x"), 16).map_err(|e| e.to_string())
+}
+
+// Helper function: the counterpart of `parse_hex_salt`
+fn format_hex_salt(salt: &u128) -> String {
+    format!("0x{:x}", salt)
+}
+
+/// Extracts a list of strings at `key` and parses each with `T::from_str`,
+/// under the same `rules` as `extract_parsed_list_with`.
+fn extract_parsed_list<T>(
+    table: &toml::map::Map<String, Value>,
+    key: &str,
+    rules: &ListRules<T>,
+) -> Result<Option<Vec<T>>, ThisProjectError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    extract_parsed_list_with(table, key, rules, |s| s.parse::<T>().map_err(|e| e.to_string()))
+}
+
+/// Extracts a list of strings at `key`, parsing each with `parse` and
+/// checking it against `rules`.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::ListError` naming the key, and for element
+/// errors the element index, e.g. `ipv4_addresses[2]: "bad": invalid IPv4
+/// address syntax`. Fails on the first error:
+/// - the key is missing and `rules.required`
+/// - the value is not an array
+/// - an element is not a string, does not parse, or fails a policy
+/// - the number of entries is outside `rules.min_len..=rules.max_len`
+fn extract_parsed_list_with<T>(
+    table: &toml::map::Map<String, Value>,
+    key: &str,
+    rules: &ListRules<T>,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<Option<Vec<T>>, ThisProjectError> {
+    let arr = match table.get(key) {
+        Some(Value::Array(arr)) => arr,
+        Some(_) => return Err(ThisProjectError::ListError(format!("{}: expected an array", key))),
+        None if rules.required => return Err(ThisProjectError::ListError(format!("Missing {}", key))),
+        None => return Ok(None),
+    };
+
+    if arr.len() < rules.min_len || arr.len() > rules.max_len {
+        return Err(ThisProjectError::ListError(format!(
+            "{}: {} entries, expected {} to {}",
+            key, arr.len(), rules.min_len, rules.max_len
+        )));
+    }
+
+    let mut items = Vec::with_capacity(arr.len());
+    for (index, val) in arr.iter().enumerate() {
+        let s = match val {
+            Value::String(s) => s,
+            _ => return Err(ThisProjectError::ListError(format!("{}[{}]: expected a string", key, index))),
+        };
+        let item = parse(s).map_err(|e| ThisProjectError::ListError(format!("{}[{}]: \"{}\": {}", key, index, s, e)))?;
+        for policy in rules.element_policies {
+            policy(&item).map_err(|e| ThisProjectError::ListError(format!("{}[{}]: \"{}\": {}", key, index, s, e)))?;
+        }
+        items.push(item);
+    }
+
+    if items.is_empty() && !rules.required {
+        Ok(None)
+    } else {
+        Ok(Some(items))
+    }
+}
+
+/*
+Unified address list
+
+`ip_addresses` holds IPv4 and IPv6 addresses together:
+
+    ip_addresses = [
+        "10.0.0.1",
+        "fe80::1",
+    ]
+
+The legacy `ipv4_addresses` and `ipv6_addresses` lists are still read, and
+either family is accepted in either list. On reading, all three lists are
+merged in the order ip_addresses, ipv4_addresses, ipv6_addresses; each
+entry is normalized (an IPv4-mapped address such as `::ffff:10.0.0.1`
+becomes `10.0.0.1`) and later duplicates are dropped. The 64-entry limit
+applies to each list in the file, not to the merged list.
+
+The serializer still writes `ipv4_addresses` and `ipv6_addresses`, split by
+family, so readers that predate `ip_addresses` see every address. Writing
+one `ip_addresses` list (`AddressListLayout::Unified`) has to be asked for.
+*/
+
+/// `ip_addresses`, `ipv4_addresses`, `ipv6_addresses` as written in a file:
+/// optional, no unspecified address.
+const IP_LIST_RULES: ListRules<IpAddr> = ListRules {
+    required: false,
+    min_len: 0,
+    max_len: 64,
+    element_policies: &[reject_unspecified_ip],
+};
+
+/// The three source lists after `merge_address_lists`: each source is
+/// already checked against `IP_LIST_RULES`, so the merged list may hold up
+/// to all of their entries.
+const MERGED_IP_LIST_RULES: ListRules<IpAddr> = ListRules {
+    max_len: 3 * IP_LIST_RULES.max_len,
+    ..IP_LIST_RULES
+};
+
+/// How `serialize_collaborator_to_toml_with_layout` writes the addresses.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AddressListLayout {
+    /// `ipv4_addresses` and `ipv6_addresses`, split by family; readable by
+    /// every version of the loader.
+    Legacy,
+    /// One `ip_addresses` list. Loaders that predate `ip_addresses` ignore
+    /// the key and read the collaborator as having no addresses, so only
+    /// use this once every peer has been updated.
+    Unified,
+}
+
+fn reject_unspecified_ip(addr: &IpAddr) -> Result<(), String> {
+    if normalize_ip_addr(*addr).is_unspecified() {
+        Err(format!("the unspecified address {} is not a peer address", addr))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the canonical form of `addr`: IPv4-mapped IPv6 addresses
+/// (`::ffff:a.b.c.d`) become the IPv4 address; all others are unchanged.
+fn normalize_ip_addr(addr: IpAddr) -> IpAddr {
+    match addr {
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => IpAddr::V4(v4),
+            None => IpAddr::V6(v6),
+        },
+        IpAddr::V4(v4) => IpAddr::V4(v4),
+    }
+}
+
+/// What `merge_address_lists` changed in a table, and what rewriting the
+/// file would lose.
+#[derive(Debug, Default, Clone, PartialEq)]
+struct AddressMergeReport {
+    /// Address keys found in the table, in merge order.
+    keys: Vec<&'static str>,
+    /// Keys the serializer does not write, so rewriting would drop them.
+    dropped_keys: Vec<String>,
+    /// Whether the file has comments, which rewriting would drop.
+    has_comments: bool,
+    /// IPv6 entries in `ipv4_addresses` or IPv4 entries in `ipv6_addresses`.
+    misplaced: usize,
+    /// IPv4-mapped IPv6 entries unwrapped to IPv4.
+    unwrapped_mapped: usize,
+    /// Entries dropped as duplicates (after normalization).
+    duplicates_removed: usize,
+}
+
+impl AddressMergeReport {
+    /// True if the entries themselves changed (moved, unwrapped or dropped).
+    fn normalized_entries(&self) -> bool {
+        self.misplaced > 0 || self.unwrapped_mapped > 0 || self.duplicates_removed > 0
+    }
+
+    /// True if the file on disk differs from what the serializer would write
+    /// in `layout`.
+    fn needs_write(&self, layout: AddressListLayout) -> bool {
+        let other_layout_keys: &[&str] = match layout {
+            AddressListLayout::Legacy => &["ip_addresses"],
+            AddressListLayout::Unified => &["ipv4_addresses", "ipv6_addresses"],
+        };
+        self.normalized_entries() || self.keys.iter().any(|key| other_layout_keys.contains(key))
+    }
+
+    /// Whether rewriting the file would lose anything it holds.
+    fn loses_data(&self) -> bool {
+        !self.dropped_keys.is_empty() || self.has_comments
+    }
+}
+
+/// The keys the serializer writes, in either layout; anything else in a
+/// file is lost when the file is rewritten.
+const SERIALIZED_KEYS: [&str; 8] = [
+    "user_name",
+    "user_salt_list",
+    "ip_addresses",
+    "ipv4_addresses",
+    "ipv6_addresses",
+    "gpg_key_public",
+    "sync_interval",
+    "updated_at_timestamp",
+];
+
+/// Returns `true` if TOML text has a comment: a `#` outside of any string.
+///
+/// Only strings need tracking, as TOML allows `#` nowhere else outside a
+/// comment.
+fn has_toml_comment(toml_string: &str) -> bool {
+    let mut rest = toml_string;
+    while let Some(c) = rest.chars().next() {
+        let (delimiter, escapes) = match c {
+            '#' => return true,
+            '"' => (if rest.starts_with("\"\"\"") { "\"\"\"" } else { "\"" }, true),
+            '\'' => (if rest.starts_with("'''") { "'''" } else { "'" }, false),
+            _ => {
+                rest = &rest[c.len_utf8()..];
+                continue;
+            }
+        };
+
+        // Skip to the end of the string
+        rest = &rest[delimiter.len()..];
+        loop {
+            if rest.is_empty() {
+                return false;
+            }
+            if escapes && rest.starts_with('\\') {
+                // Skip the backslash and the escaped character
+                let escaped_len = rest[1..].chars().next().map_or(0, char::len_utf8);
+                rest = &rest[1 + escaped_len..];
+            } else if rest.starts_with(delimiter) {
+                // A multi-line string may end in up to two extra quotes
+                rest = &rest[delimiter.len()..];
+                if delimiter.len() == 3 {
+                    let quote = delimiter.chars().next().unwrap_or('"');
+                    rest = rest.trim_start_matches(quote);
+                }
+                break;
+            } else {
+                let c = rest.chars().next().map_or(1, char::len_utf8);
+                rest = &rest[c..];
+            }
+        }
+    }
+    false
+}
+
+impl fmt::Display for AddressMergeReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.keys.is_empty() {
+            return write!(f, "no addresses");
+        }
+        write!(f, "{}", self.keys.join(", "))?;
+        if self.loses_data() {
+            write!(f, " (")?;
+            if !self.dropped_keys.is_empty() {
+                write!(f, "keys not written back: {}", self.dropped_keys.join(", "))?;
+            }
+            if self.has_comments {
+                write!(f, "{}comments", if self.dropped_keys.is_empty() { "" } else { "; " })?;
+            }
+            write!(f, ")")?;
+        }
+        if !self.normalized_entries() {
+            return write!(f, ": entries already normalized");
+        }
+        write!(
+            f,
+            ": {} misplaced, {} IPv4-mapped unwrapped, {} duplicates removed",
+            self.misplaced, self.unwrapped_mapped, self.duplicates_removed
+        )
+    }
+}
+
+/// Merges `ipv4_addresses` and `ipv6_addresses` into `ip_addresses` in a
+/// TOML table, normalizing and deduplicating entries, and removes the
+/// legacy keys. An empty result removes `ip_addresses` too. Only the table
+/// in memory changes; `collaborator_from_toml_table` then reads the merged
+/// list under `MERGED_IP_LIST_RULES`.
+///
+/// # Error Handling
+///
+/// Errors from `extract_parsed_list` are returned and the table is left
+/// unchanged. Each source list is checked against `IP_LIST_RULES`, so an
+/// error names the key as written in the file.
+fn merge_address_lists(table: &mut toml::map::Map<String, Value>) -> Result<AddressMergeReport, ThisProjectError> {
+    let mut report = AddressMergeReport::default();
+
+    // (list key, the family that belongs in it)
+    let sources: [(&'static str, Option<bool>); 3] = [
+        ("ip_addresses", None),
+        ("ipv4_addresses", Some(true)),
+        ("ipv6_addresses", Some(false)),
+    ];
+
+    let mut merged: Vec<IpAddr> = Vec::new();
+    for (key, expect_ipv4) in sources.iter() {
+        let entries = extract_parsed_list::<IpAddr>(table, key, &IP_LIST_RULES)?.unwrap_or_default();
+        if table.contains_key(*key) {
+            report.keys.push(key);
+        }
+        for addr in entries {
+            if let Some(expect_ipv4) = expect_ipv4 {
+                if addr.is_ipv4() != *expect_ipv4 {
+                    report.misplaced += 1;
+                }
+            }
+            let normalized = normalize_ip_addr(addr);
+            if normalized != addr {
+                report.unwrapped_mapped += 1;
+            }
+            if merged.contains(&normalized) {
+                report.duplicates_removed += 1;
+            } else {
+                merged.push(normalized);
+            }
+        }
+    }
+
+    table.remove("ipv4_addresses");
+    table.remove("ipv6_addresses");
+    if merged.is_empty() {
+        table.remove("ip_addresses");
+    } else {
+        let values = merged.iter().map(|addr| Value::String(addr.to_string())).collect();
+        table.insert("ip_addresses".to_string(), Value::Array(values));
+    }
+    Ok(report)
+}
+
+/// The result of migrating one file in `migrate_address_lists_in_directory`.
+#[derive(Debug)]
+enum AddressMigrationStatus {
+    /// Already normalized and in the requested layout.
+    UpToDate(AddressMergeReport),
+    /// Would be rewritten (dry run only).
+    WouldRewrite(AddressMergeReport),
+    /// Rewritten in place.
+    Rewritten(AddressMergeReport),
+    /// Needs rewriting, but rewriting would drop keys or comments (see
+    /// `AddressMergeReport::loses_data`); the file is unchanged. Move what
+    /// would be lost out of the file (or delete it) and run again.
+    Refused(AddressMergeReport),
+}
+
+impl fmt::Display for AddressMigrationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AddressMigrationStatus::UpToDate(report) => write!(f, "up to date ({})", report),
+            AddressMigrationStatus::WouldRewrite(report) => write!(f, "would rewrite ({})", report),
+            AddressMigrationStatus::Rewritten(report) => write!(f, "rewritten ({})", report),
+            AddressMigrationStatus::Refused(report) => write!(f, "NOT rewritten, would lose data ({})", report),
+        }
+    }
+}
+
+/// Per-file results of `migrate_address_lists_in_directory`.
+type AddressMigrationResults = Vec<(PathBuf, Result<AddressMigrationStatus, ThisProjectError>)>;
+
+/// Rewrites every collaborator file in `dir_path` whose address lists are
+/// not normalized or not in `layout` (see `AddressMergeReport::needs_write`),
+/// or with `dry_run` only reports them. A rewritten file is first written to
+/// `<name>.migrating` and then renamed over the original, so a failed write
+/// never leaves a half-written collaborator file.
+///
+/// A file that rewriting would lose data from (unknown keys, comments) is
+/// not touched, but reported as `AddressMigrationStatus::Refused`.
+///
+/// Pass `AddressListLayout::Legacy` unless every peer reads `ip_addresses`;
+/// see `AddressListLayout::Unified`.
+///
+/// Returns one entry per `.toml` file, in file name order; a file that
+/// cannot be read or parsed is reported and left unchanged.
+fn migrate_address_lists_in_directory(
+    dir_path: &Path,
+    dry_run: bool,
+    layout: AddressListLayout,
+) -> Result<AddressMigrationResults, ThisProjectError> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir_path)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut results = Vec::new();
+    for path in paths {
+        let result = migrate_address_lists_in_file(&path, dry_run, layout);
+        results.push((path, result));
+    }
+    Ok(results)
+}
+
+// Helper function to merge (or plan the merge of) one file's address lists
+fn migrate_address_lists_in_file(
+    path: &Path,
+    dry_run: bool,
+    layout: AddressListLayout,
+) -> Result<AddressMigrationStatus, ThisProjectError> {
+    let toml_string = fs::read_to_string(path)?;
+    let (collaborator, report) = merge_and_read_collaborator_toml(&toml_string)?;
+    if !report.needs_write(layout) {
+        return Ok(AddressMigrationStatus::UpToDate(report));
+    }
+    if report.loses_data() {
+        return Ok(AddressMigrationStatus::Refused(report));
+    }
+
+    // Serialize before the dry-run check, so a dry run also reports lists that
+    // would be too long to write
+    let new_toml_string = serialize_collaborator_to_toml_with_layout(&collaborator, layout)?;
+    if dry_run {
+        return Ok(AddressMigrationStatus::WouldRewrite(report));
+    }
+
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".migrating");
+    let temp_path = PathBuf::from(temp_path);
+
+    write_toml_to_file(&temp_path.to_string_lossy(), &new_toml_string)?;
+    if let Err(e) = fs::rename(&temp_path, path) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(ThisProjectError::IoError(e));
+    }
+    Ok(AddressMigrationStatus::Rewritten(report))
+}
+
+// Helper function: TOML table -> CollaboratorTomlData, failing on the first bad field
+fn collaborator_from_toml_table(table: &toml::map::Map<String, Value>) -> Result<CollaboratorTomlData, ThisProjectError> {
+
+    // Extract user_name
+    let user_name = if let Some(Value::String(s)) = table.get("user_name") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing user_name".into()));
+    };
+
+    // Extract user_salt_list (required, so never None)
+    let user_salt_list = extract_parsed_list_with(table, "user_salt_list", &SALT_LIST_RULES, parse_hex_salt)?
+        .unwrap_or_default();
+
+    // Extract ip_addresses (after merge_address_lists, the only address list)
+    let ip_addresses = extract_parsed_list::<IpAddr>(table, "ip_addresses", &MERGED_IP_LIST_RULES)?;
+
+    // Extract gpg_key_public
+    let gpg_key_public = if let Some(Value::String(s)) = table.get("gpg_key_public") {
+        s.clone()
+    } else {
+        return Err(ThisProjectError::TomlVanillaDeserialStrError("Missing or invalid gpg_key_public".into()));
+    };
+
+    // Extract sync_interval
+    let sync_interval = extract_u64(table, "sync_interval")?;
+
+    // Extract updated_at_timestamp
+    let updated_at_timestamp = extract_u64(table, "updated_at_timestamp")?;
+
+    Ok(CollaboratorTomlData {
+        user_name,
+        user_salt_list,
+        ip_addresses,
+        gpg_key_public,
+        sync_interval,
+        updated_at_timestamp,
+    })
+}
+
+// Helper function to extract a u64 from a toml::Value::Table
+fn extract_u64(table: &toml::map::Map<String, Value>, key: &str) -> Result<u64, ThisProjectError> {
+    if let Some(Value::Integer(i)) = table.get(key) {
+        if let Ok(value) = u64::try_from(*i) {
+            Ok(value)
+        } else {
+            Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Invalid {}: Out of range for u64", key)))
+        }
+    } else {
+        Err(ThisProjectError::TomlVanillaDeserialStrError(format!("Missing or invalid {}", key)))
+    }
+}
+
+// Helper function: TOML text -> CollaboratorTomlData with merged address lists,
+// and what the merge did and rewriting the file would lose
+fn merge_and_read_collaborator_toml(toml_string: &str) -> Result<(CollaboratorTomlData, AddressMergeReport), ThisProjectError> {
+    let mut table = match toml::from_str::<Value>(toml_string) {
+        Ok(Value::Table(table)) => table,
+        Ok(_) => return Err(ThisProjectError::TomlVanillaDeserialStrError("Invalid TOML structure: Expected a table".into())),
+        Err(e) => return Err(ThisProjectError::TomlVanillaDeserialStrError(e.to_string())),
+    };
+    let mut report = merge_address_lists(&mut table)?;
+    report.dropped_keys = table.keys().filter(|key| !SERIALIZED_KEYS.contains(&key.as_str())).cloned().collect();
+    report.has_comments = has_toml_comment(toml_string);
+    let collaborator = collaborator_from_toml_table(&table)?;
+    Ok((collaborator, report))
+}
+
+// Helper function: TOML text -> CollaboratorTomlData
+fn deserialize_collaborator_from_toml(toml_string: &str) -> Result<CollaboratorTomlData, ThisProjectError> {
+    merge_and_read_collaborator_toml(toml_string).map(|(collaborator, _)| collaborator)
+}
+
+/// Toml Deserialization: Reads collaborator setup data from TOML files in a
+/// specified directory.
+///
+/// Every `*.toml` file in `project_graph_data/collaborator_files_address_book`
+/// is parsed with `deserialize_collaborator_from_toml`, which merges its
+/// address lists into `ip_addresses` in memory (see `merge_address_lists`);
+/// a file that does not parse is skipped and its error is added to the error
+/// vector. Files are not changed on disk; use
+/// `migrate_address_lists_in_directory` for that.
+///
+/// # Returns
+///
+/// Returns a `Result` containing:
+/// - `Ok`: A tuple with:
+///     - A vector of successfully parsed `CollaboratorTomlData` instances.
+///     - A vector of any `ThisProjectError` encountered during parsing.
+/// - `Err`: A `ThisProjectError` if there was an error reading the directory or any file.
+fn read_a_collaborator_setup_toml() -> Result<(Vec<CollaboratorTomlData>, Vec<ThisProjectError>), ThisProjectError> {
+    let mut collaborators = Vec::new();
+    let mut errors = Vec::new();
+    let dir_path = Path::new("project_graph_data/collaborator_files_address_book");
+
+    for entry in fs::read_dir(dir_path)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
+            let toml_string = fs::read_to_string(&path)?;
+
+            match deserialize_collaborator_from_toml(&toml_string) {
+                Ok(collaborator) => collaborators.push(collaborator),
+                Err(e) => errors.push(e),
+            }
+        }
+    }
+
+    Ok((collaborators, errors))
+}
+
+/// Serializes a `CollaboratorTomlData` struct into a TOML-formatted string,
+/// with the addresses in `AddressListLayout::Legacy`.
+fn serialize_collaborator_to_toml(collaborator: &CollaboratorTomlData) -> Result<String, ThisProjectError> {
+    serialize_collaborator_to_toml_with_layout(collaborator, AddressListLayout::Legacy)
+}
+
+/// Serializes a `CollaboratorTomlData` struct into a TOML-formatted string.
+///
+/// One `key = value` line per field, in struct order, with escaped strings
+/// and salts as `0x` hex strings. The addresses are normalized and written as
+/// `layout` says (the legacy layout groups them by family); an empty address
+/// list is left out.
+///
+/// # Error Handling
+///
+/// Returns `ThisProjectError::ListError` if a written address list would
+/// have more entries than `IP_LIST_RULES` allows, since the file could not
+/// be read back.
+fn serialize_collaborator_to_toml_with_layout(
+    collaborator: &CollaboratorTomlData,
+    layout: AddressListLayout,
+) -> Result<String, ThisProjectError> {
+    let mut toml_string = String::new();
+
+    // Add user_name
+    toml_string.push_str(&format!("user_name = \"{}\"\n", escape_toml_basic_string(&collaborator.user_name)));
+
+    // Add user_salt_list
+    serialize_list_with(&mut toml_string, "user_salt_list", &collaborator.user_salt_list, format_hex_salt);
+
+    // Add the address lists
+    if let Some(addresses) = &collaborator.ip_addresses {
+        let normalized: Vec<IpAddr> = addresses.iter().map(|addr| normalize_ip_addr(*addr)).collect();
+        match layout {
+            AddressListLayout::Legacy => {
+                let (ipv4, ipv6): (Vec<IpAddr>, Vec<IpAddr>) = normalized.into_iter().partition(IpAddr::is_ipv4);
+                serialize_address_list(&mut toml_string, "ipv4_addresses", &ipv4)?;
+                serialize_address_list(&mut toml_string, "ipv6_addresses", &ipv6)?;
+            }
+            AddressListLayout::Unified => serialize_address_list(&mut toml_string, "ip_addresses", &normalized)?,
+        }
+    }
+
+    // Add gpg_key_public
+    toml_string.push_str(&format!("gpg_key_public = \"{}\"\n", escape_toml_basic_string(&collaborator.gpg_key_public)));
+
+    // Add sync_interval
+    toml_string.push_str(&format!("sync_interval = {}\n", collaborator.sync_interval));
+
+    // Add updated_at_timestamp
+    toml_string.push_str(&format!("updated_at_timestamp = {}\n", collaborator.updated_at_timestamp));
+
+    Ok(toml_string)
+}
+
+// Helper function to write one address list, or nothing if it is empty
+fn serialize_address_list(toml_string: &mut String, key: &str, addresses: &[IpAddr]) -> Result<(), ThisProjectError> {
+    if addresses.len() > IP_LIST_RULES.max_len {
+        return Err(ThisProjectError::ListError(format!(
+            "{}: {} entries, at most {} can be written",
+            key, addresses.len(), IP_LIST_RULES.max_len
+        )));
+    }
+    if !addresses.is_empty() {
+        serialize_list(toml_string, key, addresses);
+    }
+    Ok(())
+}
+
+/// Writes `items` as a multi-line TOML array of strings using `Display`.
+fn serialize_list<T: fmt::Display>(toml_string: &mut String, key: &str, items: &[T]) {
+    serialize_list_with(toml_string, key, items, |item| item.to_string());
+}
+
+/// Writes `items` as a multi-line TOML array of strings, formatting each
+/// with `format` (the counterpart of the `parse` in `extract_parsed_list_with`).
+fn serialize_list_with<T>(toml_string: &mut String, key: &str, items: &[T], format: impl Fn(&T) -> String) {
+    toml_string.push_str(&format!("{} = [\n", key));
+    for item in items {
+        toml_string.push_str(&format!("    \"{}\",\n", escape_toml_basic_string(&format(item))));
+    }
+    toml_string.push_str("]\n");
+}
+
+// Helper function to escape a value for a TOML basic string ("...")
+fn escape_toml_basic_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Function to write a TOML string to a file
+fn write_toml_to_file(file_path: &str, toml_string: &str) -> Result<(), ThisProjectError> {
+    // Attempt to create the file.
+    let mut file = match File::create(file_path) {
+        Ok(file) => file,
+        Err(e) => return Err(ThisProjectError::IoError(e)),
+    };
+
+    // Attempt to write to the file.
+    if let Err(e) = file.write_all(toml_string.as_bytes()) {
+        return Err(ThisProjectError::IoError(e));
+    }
+
+    // Everything successful!
+    Ok(())
+}
+
+fn main() {
+    // Misplaced, IPv4-mapped and duplicate entries in the legacy lists
+    let legacy_toml = "user_name = \"Carol\"\nuser_salt_list = [\"0x1\"]\n\
+        ipv4_addresses = [\"10.0.0.1\", \"fe80::1\"]\n\
+        ipv6_addresses = [\"::ffff:10.0.0.1\", \"::ffff:192.168.1.7\", \"::1\"]\n\
+        gpg_key_public = \"\"\nsync_interval = 60\nupdated_at_timestamp = 0\n";
+    match merge_and_read_collaborator_toml(legacy_toml) {
+        Ok((collaborator, report)) => {
+            println!("{}", report);
+            println!("ip_addresses: {:?}", collaborator.ip_addresses);
+        }
+        Err(e) => println!("Error reading legacy file: {}", e),
+    }
+
+    // Example CollaboratorTomlData instance
+    let collaborator = CollaboratorTomlData {
+        user_name: "Bob".to_string(),
+        user_salt_list: vec![0x123456789abcdef0, 0xabcdef0123456789],
+        ip_addresses: Some(vec![
+            IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)),
+            IpAddr::V6(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1)),
+        ]),
+        gpg_key_public: "-----BEGIN PGP PUBLIC KEY BLOCK----- ...".to_string(),
+        sync_interval: 300,
+        updated_at_timestamp: 1728308000,
+    };
+
+    match serialize_collaborator_to_toml(&collaborator) {
+        Ok(toml_string) => {
+            println!("Serialized TOML:\n{}", toml_string);
+
+            // Write the TOML string to a file (example file path)
+            match write_toml_to_file("collaborator_data.toml", &toml_string) {
+                Ok(_) => println!("TOML data written to file successfully."),
+                Err(e) => println!("Error writing to file: {}", e),
+            }
+        }
+        Err(e) => println!("Error serializing to TOML: {}", e),
+    }
+
+    // What normalizing the address lists on disk would do, in each layout;
+    // dry runs only, the demo never rewrites the address book
+    let dir_path = Path::new("project_graph_data/collaborator_files_address_book");
+    for (layout, title) in [
+        (AddressListLayout::Legacy, "Address list normalization (dry run)"),
+        (AddressListLayout::Unified, "Switching to ip_addresses (dry run; loaders that predate it will see no addresses)"),
+    ] {
+        println!("{}:", title);
+        match migrate_address_lists_in_directory(dir_path, true, layout) {
+            Ok(results) => {
+                for (path, result) in results {
+                    match result {
+                        Ok(status) => println!("  {}: {}", path.display(), status),
+                        Err(e) => println!("  {}: {}", path.display(), e),
+                    }
+                }
+            }
+            Err(e) => println!("Error reading {}: {}", dir_path.display(), e),
+        }
+    }
+
+    match read_a_collaborator_setup_toml() {
+        Ok((collaborators, errors)) => {
+            if !errors.is_empty() {
+                println!("Errors encountered:");
+                for err in errors {
+                    println!("{}", err);
+                }
+            }
+
+            println!("Collaborators:");
+            for collaborator in collaborators {
+                println!("{:#?}", collaborator);
+            }
+        }
+        Err(e) => {
+            println!("Error reading TOML files: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collaborator_toml(address_lines: &str) -> String {
+        format!(
+            "user_name = \"Carol\"\nuser_salt_list = [\"0x1\"]\n{}gpg_key_public = \"\"\nsync_interval = 60\nupdated_at_timestamp = 0\n",
+            address_lines
+        )
+    }
+
+    fn toml_list(key: &str, addresses: &[String]) -> String {
+        let quoted: Vec<String> = addresses.iter().map(|a| format!("\"{}\"", a)).collect();
+        format!("{} = [{}]\n", key, quoted.join(", "))
+    }
+
+    fn ipv4_range(n: usize) -> Vec<String> {
+        (1..=n).map(|i| format!("10.0.0.{}", i)).collect()
+    }
+
+    fn ipv6_range(n: usize) -> Vec<String> {
+        (1..=n).map(|i| format!("fd00::{:x}", i)).collect()
+    }
+
+    #[test]
+    fn full_legacy_lists_merge_past_the_per_list_limit() {
+        let toml_string = collaborator_toml(&format!(
+            "{}{}",
+            toml_list("ipv4_addresses", &ipv4_range(40)),
+            toml_list("ipv6_addresses", &ipv6_range(40))
+        ));
+        let collaborator = deserialize_collaborator_from_toml(&toml_string).unwrap();
+        assert_eq!(collaborator.ip_addresses.unwrap().len(), 80);
+    }
+
+    #[test]
+    fn over_long_source_list_is_reported_under_its_own_key() {
+        let toml_string = collaborator_toml(&toml_list("ipv4_addresses", &ipv4_range(65)));
+        let err = deserialize_collaborator_from_toml(&toml_string).unwrap_err().to_string();
+        assert!(err.contains("ipv4_addresses: 65 entries"), "{}", err);
+    }
+
+    #[test]
+    fn serializer_keeps_the_legacy_lists_split_by_family() {
+        let toml_string = collaborator_toml(
+            "ipv4_addresses = [\"10.0.0.1\", \"fe80::1\"]\nipv6_addresses = [\"::ffff:10.0.0.2\"]\n",
+        );
+        let (collaborator, report) = merge_and_read_collaborator_toml(&toml_string).unwrap();
+        assert!(report.needs_write(AddressListLayout::Legacy));
+
+        let written = serialize_collaborator_to_toml(&collaborator).unwrap();
+        let table: toml::map::Map<String, Value> = toml::from_str(&written).unwrap();
+        assert!(!table.contains_key("ip_addresses"));
+        assert_eq!(
+            table.get("ipv4_addresses"),
+            Some(&Value::Array(vec![Value::String("10.0.0.1".into()), Value::String("10.0.0.2".into())]))
+        );
+        assert_eq!(table.get("ipv6_addresses"), Some(&Value::Array(vec![Value::String("fe80::1".into())])));
+
+        let (reread, report) = merge_and_read_collaborator_toml(&written).unwrap();
+        // The legacy layout groups the addresses by family
+        let mut expected = collaborator.ip_addresses.clone().unwrap();
+        expected.sort_by_key(|addr| addr.is_ipv6());
+        assert_eq!(reread.ip_addresses, Some(expected));
+        assert!(!report.needs_write(AddressListLayout::Legacy));
+        assert!(report.needs_write(AddressListLayout::Unified));
+    }
+
+    #[test]
+    fn unified_layout_refuses_a_list_it_could_not_read_back() {
+        let toml_string = collaborator_toml(&format!(
+            "{}{}",
+            toml_list("ipv4_addresses", &ipv4_range(40)),
+            toml_list("ipv6_addresses", &ipv6_range(40))
+        ));
+        let collaborator = deserialize_collaborator_from_toml(&toml_string).unwrap();
+        assert!(serialize_collaborator_to_toml_with_layout(&collaborator, AddressListLayout::Legacy).is_ok());
+        let err = serialize_collaborator_to_toml_with_layout(&collaborator, AddressListLayout::Unified).unwrap_err();
+        assert!(err.to_string().contains("ip_addresses: 80 entries"), "{}", err);
+    }
+
+    // A fresh directory under the system temp dir, removed by the caller
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("unified_ip_addresses_test_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn migration_rewrites_in_the_legacy_layout_once() {
+        let dir = temp_dir("once");
+        let path = dir.join("carol__collaborator.toml");
+        fs::write(&path, collaborator_toml("ip_addresses = [\"10.0.0.1\"]\nipv6_addresses = [\"10.0.0.2\"]\n")).unwrap();
+
+        let dry = migrate_address_lists_in_directory(&dir, true, AddressListLayout::Legacy).unwrap();
+        assert!(matches!(dry[0].1, Ok(AddressMigrationStatus::WouldRewrite(_))));
+        assert!(fs::read_to_string(&path).unwrap().contains("ip_addresses"));
+
+        let results = migrate_address_lists_in_directory(&dir, false, AddressListLayout::Legacy).unwrap();
+        assert!(matches!(results[0].1, Ok(AddressMigrationStatus::Rewritten(_))));
+        let written = fs::read_to_string(&path).unwrap();
+        assert!(!written.contains("ip_addresses"));
+        assert!(written.contains("ipv4_addresses"));
+
+        let again = migrate_address_lists_in_directory(&dir, false, AddressListLayout::Legacy).unwrap();
+        assert!(matches!(again[0].1, Ok(AddressMigrationStatus::UpToDate(_))));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn migration_refuses_to_drop_comments_or_unknown_keys() {
+        let dir = temp_dir("refused");
+        let commented = collaborator_toml("# primary laptop, do not remove\nip_addresses = [\"10.0.0.1\"] # office\n");
+        let unknown_key = collaborator_toml("ip_addresses = [\"10.0.0.1\"]\nnickname = \"caz\"\n");
+        fs::write(dir.join("carol__collaborator.toml"), &commented).unwrap();
+        fs::write(dir.join("dave__collaborator.toml"), &unknown_key).unwrap();
+
+        let results = migrate_address_lists_in_directory(&dir, false, AddressListLayout::Legacy).unwrap();
+        let carol = fs::read_to_string(dir.join("carol__collaborator.toml")).unwrap();
+        let dave = fs::read_to_string(dir.join("dave__collaborator.toml")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        match &results[0].1 {
+            Ok(AddressMigrationStatus::Refused(report)) => assert!(report.has_comments && report.dropped_keys.is_empty()),
+            other => panic!("{:?}", other),
+        }
+        match &results[1].1 {
+            Ok(AddressMigrationStatus::Refused(report)) => assert_eq!(report.dropped_keys, vec!["nickname".to_string()]),
+            other => panic!("{:?}", other),
+        }
+        assert_eq!(carol, commented);
+        assert_eq!(dave, unknown_key);
+    }
+
+    #[test]
+    fn comments_are_found_outside_strings_only() {
+        assert!(!has_toml_comment("a = \"#1\"\nb = '#2'\nc = \"\"\"\n#3\"\"\"\nd = \"\\\"#4\"\n"));
+        assert!(has_toml_comment("a = \"#1\" # note\n"));
+    }
+}